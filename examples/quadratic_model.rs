@@ -51,13 +51,13 @@ impl OptModel for QuadraticModel {
         current_solution: Self::SolutionType,
         _current_score: Self::ScoreType,
         rng: &mut R,
-    ) -> (Self::SolutionType, Self::TransitionType, NotNan<f64>) {
+    ) -> Result<(Self::SolutionType, Self::TransitionType, NotNan<f64>), LocalsearchError> {
         let k = rng.random_range(0..self.k);
         let v = self.dist.sample(rng);
         let mut new_solution = current_solution;
         new_solution[k] = v;
         let score = self.evaluate_solution(&new_solution);
-        (new_solution, (), score)
+        Ok((new_solution, (), score))
     }
 }
 
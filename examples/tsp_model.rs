@@ -132,7 +132,7 @@ impl OptModel for TSPModel {
         current_solution: Self::SolutionType,
         current_score: Self::ScoreType,
         rng: &mut R,
-    ) -> (Self::SolutionType, Self::TransitionType, Self::ScoreType) {
+    ) -> Result<(Self::SolutionType, Self::TransitionType, Self::ScoreType), LocalsearchError> {
         let (ind1, ind2) = select_two_indices(1, current_solution.len() - 1, rng);
 
         let mut new_solution = current_solution.clone();
@@ -160,7 +160,7 @@ impl OptModel for TSPModel {
         // create transition
         let trans = (removed_edges, inserted_edges);
 
-        (new_solution, trans, NotNan::new(new_score).unwrap())
+        Ok((new_solution, trans, NotNan::new(new_score).unwrap()))
     }
 }
 
@@ -203,6 +203,11 @@ impl TabuList for DequeTabuList {
     fn set_size(&mut self, n: usize) {
         self.buff = RingBuffer::new(n);
     }
+
+    fn items(&self) -> Vec<TransitionType> {
+        // appending a transition removing the same edge twice stores that edge once
+        self.buff.iter().map(|&e| ([e, e], [e, e])).collect()
+    }
 }
 
 // The output is wrapped in a Result to allow matching on errors
@@ -284,7 +289,8 @@ fn main() {
                     0.9,
                     NonZero::new(100).expect("update_frequency must be >= 1"),
                 )
-                .tune_initial_temperature(&tsp_model, None, 200, 0.5)
+                .tune_initial_temperature(&tsp_model, None, 200, 0.5, &mut rng)
+                .unwrap()
                 .tune_cooling_rate(n_iter),
             ),
         ),
@@ -299,7 +305,8 @@ fn main() {
                     Default::default(),
                     NonZero::new(100).expect("update_frequency must be >= 1"),
                 )
-                .tune_initial_temperature(&tsp_model, None, 200),
+                .tune_initial_temperature(&tsp_model, None, 200, &mut rng)
+                .unwrap(),
             ),
         ),
         (
@@ -314,7 +321,8 @@ fn main() {
                     NonZero::new(100).expect("update_frequency must be >= 1"),
                     16,
                 )
-                .tune_initial_temperature(&tsp_model, None, 200, 0.5)
+                .tune_initial_temperature(&tsp_model, None, 200, 0.5, &mut rng)
+                .unwrap()
                 .tune_cooling_rate(n_iter),
             ),
         ),
@@ -369,7 +377,7 @@ fn main() {
     for (name, optimizer) in optimizers {
         println!("run {}", name);
         pb.reset();
-        let (final_solution, final_score) = optimizer
+        let result = optimizer
             .run_with_callback(
                 &tsp_model,
                 initial_solution.clone(),
@@ -380,9 +388,12 @@ fn main() {
             .unwrap();
         pb.finish_and_clear();
         println!(
-            "final score = {}, num of cities {}",
-            final_score,
-            final_solution.len()
+            "final score = {}, num of cities {}, iterations {}, elapsed {:.2?}, stopped by {:?}",
+            result.score,
+            result.solution.len(),
+            result.n_iterations,
+            result.elapsed,
+            result.stop_reason
         );
     }
 
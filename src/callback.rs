@@ -1,6 +1,36 @@
 //! Callback of Optimization Iteration
 
-use std::{cell::RefCell, rc::Rc};
+use std::{
+    cell::RefCell,
+    rc::Rc,
+    sync::{Arc, mpsc},
+};
+
+use crate::Duration;
+
+/// OptMetrics holds algorithm-specific values of an iteration.
+/// Values that do not apply to the optimizer are `None`.
+///
+/// More metrics may be added in the future;
+/// optimizers implemented outside of this crate start from `OptMetrics::default()` and set the fields they have.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[non_exhaustive]
+pub struct OptMetrics {
+    /// current inverse temperature (annealing, metropolis and tsallis based optimizers)
+    pub beta: Option<f64>,
+    /// current water level (great deluge)
+    pub water_level: Option<f64>,
+    /// inverse temperature of each replica (parallel tempering)
+    pub betas: Option<Vec<f64>>,
+    /// current score of each replica, in the order of `betas` (parallel tempering)
+    pub replica_scores: Option<Vec<f64>>,
+    /// rate of accepted exchanges between replicas `i` and `i + 1` so far (parallel tempering)
+    pub swap_rates: Option<Vec<f64>>,
+    /// normalized resampling weight of each member (population annealing)
+    pub population_weights: Option<Vec<f64>>,
+    /// number of reheats so far, including at this iteration (simulated annealing with a reheat policy)
+    pub n_reheats: Option<usize>,
+}
 
 /// OptProgress expresses Optimization Progress that is passed to a [`OptCallbackFn`]
 #[derive(Debug, Clone)]
@@ -13,17 +43,147 @@ pub struct OptProgress<S, SC> {
     pub solution: Rc<RefCell<S>>,
     /// current best score
     pub score: SC,
+    /// score of the current (not best) solution;
+    /// the best current score among replicas or members for replica based optimizers
+    pub current_score: SC,
+    /// elapsed time of the optimization, excluding pauses
+    pub elapsed: Duration,
+    /// number of trial solutions evaluated so far
+    pub n_evaluations: usize,
+    /// algorithm-specific metrics
+    pub metrics: OptMetrics,
 }
 
-impl<S, SC: Ord> OptProgress<S, SC> {
-    /// constuctor of OptProgress
+impl<S, SC: Ord + Copy> OptProgress<S, SC> {
+    /// constuctor of OptProgress.
+    /// The current score is set to the best score, other statistics and metrics are left empty.
     pub fn new(iter: usize, acceptance_ratio: f64, solution: Rc<RefCell<S>>, score: SC) -> Self {
         Self {
             iter,
             acceptance_ratio,
             solution,
             score,
+            current_score: score,
+            elapsed: Duration::ZERO,
+            n_evaluations: 0,
+            metrics: OptMetrics::default(),
+        }
+    }
+}
+
+impl<S: Clone, SC: Copy> OptProgress<S, SC> {
+    /// Thread-safe copy of this progress, cloning the best solution into an [`Arc`]
+    pub fn snapshot(&self) -> ProgressSnapshot<S, SC> {
+        self.snapshot_with(Arc::new(self.solution.borrow().clone()))
+    }
+
+    fn snapshot_with(&self, solution: Arc<S>) -> ProgressSnapshot<S, SC> {
+        ProgressSnapshot {
+            iter: self.iter,
+            acceptance_ratio: self.acceptance_ratio,
+            solution,
+            score: self.score,
+            current_score: self.current_score,
+            elapsed: self.elapsed,
+            n_evaluations: self.n_evaluations,
+            metrics: self.metrics.clone(),
+        }
+    }
+}
+
+/// ProgressSnapshot is a `Send` copy of an [`OptProgress`] that can be passed to other threads
+#[derive(Debug, Clone)]
+pub struct ProgressSnapshot<S, SC> {
+    /// current iteration step
+    pub iter: usize,
+    /// acceptance ratio
+    pub acceptance_ratio: f64,
+    /// current best solution, shared between snapshots as long as it does not change
+    pub solution: Arc<S>,
+    /// current best score
+    pub score: SC,
+    /// score of the current (not best) solution
+    pub current_score: SC,
+    /// elapsed time of the optimization, excluding pauses
+    pub elapsed: Duration,
+    /// number of trial solutions evaluated so far
+    pub n_evaluations: usize,
+    /// algorithm-specific metrics
+    pub metrics: OptMetrics,
+}
+
+/// SnapshotSender is the sending side of a channel that receives [`ProgressSnapshot`]s
+/// from [`channel_callback`].
+///
+/// It is implemented for [`mpsc::Sender`] and [`mpsc::SyncSender`];
+/// implement it to forward progress to other channels (e.g. crossbeam).
+/// Sending must not block the optimizer and errors (e.g. a disconnected receiver) are ignored.
+pub trait SnapshotSender<T> {
+    /// Send `snapshot`, dropping it if the channel cannot take it right now
+    fn send_snapshot(&self, snapshot: T);
+}
+
+impl<T> SnapshotSender<T> for mpsc::Sender<T> {
+    fn send_snapshot(&self, snapshot: T) {
+        let _ = self.send(snapshot);
+    }
+}
+
+impl<T> SnapshotSender<T> for mpsc::SyncSender<T> {
+    fn send_snapshot(&self, snapshot: T) {
+        // a full channel drops the snapshot rather than blocking the optimizer
+        let _ = self.try_send(snapshot);
+    }
+}
+
+/// Callback that forwards progress to `sender` as [`ProgressSnapshot`]s,
+/// at most once every `min_interval` of optimization time.
+///
+/// The first progress is always forwarded. The best solution is only cloned when it has changed
+/// since the previous forwarded snapshot, otherwise the snapshots share it.
+/// Callbacks are invoked on the thread running the optimization loop for every optimizer
+/// (including parallel tempering and population annealing), so the returned callback does not need to be `Sync`.
+///
+/// Example
+///
+/// ```rust
+/// let (sender, receiver) = std::sync::mpsc::channel();
+/// let ui = std::thread::spawn(move || {
+///     for snapshot in receiver {
+///         println!("{}: {:?}", snapshot.iter, snapshot.score);
+///     }
+/// });
+/// let mut callback = channel_callback(sender, Duration::from_millis(100));
+/// opt.run_with_callback(&model, None, n_iter, time_limit, &mut callback);
+/// drop(callback); // closes the channel
+/// ui.join().unwrap();
+/// ```
+pub fn channel_callback<S, SC, T>(
+    sender: T,
+    min_interval: Duration,
+) -> impl FnMut(OptProgress<S, SC>)
+where
+    S: Clone,
+    SC: PartialEq + Copy,
+    T: SnapshotSender<ProgressSnapshot<S, SC>>,
+{
+    let mut last_sent: Option<Duration> = None;
+    let mut last_solution: Option<(SC, Arc<S>)> = None;
+    move |progress: OptProgress<S, SC>| {
+        if last_sent.is_some_and(|sent| progress.elapsed < sent + min_interval) {
+            return;
         }
+        last_sent = Some(progress.elapsed);
+        let solution = match &last_solution {
+            // the best solution is only replaced together with an improvement of the best score
+            Some((score, solution)) if *score == progress.score => Arc::clone(solution),
+            _ => {
+                let solution = Arc::new(progress.solution.borrow().clone());
+                last_solution = Some((progress.score, Arc::clone(&solution)));
+                solution
+            }
+        };
+        sender.send_snapshot(progress.snapshot_with(solution));
     }
 }
 
@@ -0,0 +1,47 @@
+//! Snapshot of a Running Optimization
+
+use crate::{AcceptanceCounter, AlgorithmState, Duration};
+
+/// Checkpoint is a snapshot of the full state of a running optimization.
+///
+/// It is taken through [`OptControl::request_checkpoint`](crate::OptControl::request_checkpoint)
+/// and restored with [`LocalSearchOptimizer::resume`](crate::optim::LocalSearchOptimizer::resume).
+/// With the `serde` feature it implements `Serialize` and `Deserialize`.
+///
+/// It holds the state of the optimizer only. Two things are not part of it:
+/// - the random number generator: the continued run is identical to an uninterrupted one
+///   only if `resume` is given the generator in the state it was in when the checkpoint was taken,
+///   e.g. the same generator object continued in the same process. A reseeded generator gives a different run.
+/// - the state of the [`StopCondition`](crate::stop_condition::StopCondition):
+///   the condition passed to `resume` starts afresh,
+///   e.g. [`Stagnation`](crate::stop_condition::Stagnation) counts its patience from the first check after resuming.
+#[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct Checkpoint<S, SC, T> {
+    /// number of iterations done
+    pub iter: usize,
+    /// elapsed time, excluding pauses
+    pub elapsed: Duration,
+    /// number of trial solutions evaluated
+    pub n_evaluations: usize,
+    /// best solution
+    pub best_solution: S,
+    /// score of the best solution
+    pub best_score: SC,
+    /// current solution and score of each chain:
+    /// a single one for most optimizers, one per replica for parallel tempering
+    /// and one per member for population annealing
+    pub current: Vec<(S, SC)>,
+    /// sliding window of recent acceptances,
+    /// empty for parallel tempering and population annealing which only count them within a round
+    pub acceptance_counter: AcceptanceCounter,
+    /// iterations since the last improvement, for returning to the best solution
+    pub return_stagnation_counter: usize,
+    /// iterations since the last improvement, for patience
+    pub patience_stagnation_counter: usize,
+    /// algorithm-specific state such as beta, water level or the replica ladder
+    pub algorithm_state: AlgorithmState,
+    /// contents of the tabu list, oldest first
+    pub tabu_list: Vec<T>,
+}
@@ -0,0 +1,361 @@
+//! Constrained Models and Penalties
+
+use std::{
+    cmp::Reverse,
+    num::NonZero,
+    sync::{
+        Mutex,
+        atomic::{AtomicU64, Ordering},
+    },
+};
+
+use ordered_float::NotNan;
+
+use crate::{
+    Energy, LocalsearchError, Maximize, OptModel, OptObserver, callback::OptProgress, utils::lock,
+};
+
+/// ConstrainedOptModel is an extension of [`OptModel`] for problems with constraints
+/// that the search may violate on its way to feasible solutions.
+///
+/// The score of the model is the objective alone, and constraint violations are reported separately.
+/// Wrap the model in [`PenalizedModel`] to optimize the objective penalized by the violations.
+pub trait ConstrainedOptModel: OptModel<ScoreType: Energy> {
+    /// Amount by which the solution violates each constraint, 0 for satisfied constraints.
+    /// The length must be the same for every solution.
+    fn violations(&self, solution: &Self::SolutionType) -> Vec<f64>;
+
+    /// Try to repair an infeasible solution, returning the repaired solution and its objective,
+    /// or `None` to keep it as is (the default).
+    /// Called by [`PenalizedModel`] on every generated solution that violates a constraint,
+    /// in addition to [`OptModel::preprocess_solution`].
+    #[allow(unused_variables)]
+    fn repair(
+        &self,
+        solution: &Self::SolutionType,
+        score: Self::ScoreType,
+        violations: &[f64],
+    ) -> Option<(Self::SolutionType, Self::ScoreType)> {
+        None
+    }
+}
+
+impl<M: ConstrainedOptModel> ConstrainedOptModel for Maximize<M> {
+    fn violations(&self, solution: &Self::SolutionType) -> Vec<f64> {
+        self.inner().violations(solution)
+    }
+
+    fn repair(
+        &self,
+        solution: &Self::SolutionType,
+        score: Self::ScoreType,
+        violations: &[f64],
+    ) -> Option<(Self::SolutionType, Self::ScoreType)> {
+        self.inner()
+            .repair(solution, score.0, violations)
+            .map(|(solution, score)| (solution, Reverse(score)))
+    }
+}
+
+/// Score of a [`PenalizedModel`]: the objective plus the weighted violations times the penalty multiplier.
+///
+/// Scores are compared by the penalized value, then by the violation and the objective.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PenalizedScore<ST> {
+    penalized: NotNan<f64>,
+    violation: NotNan<f64>,
+    objective: ST,
+}
+
+impl<ST: Energy> PenalizedScore<ST> {
+    /// Score of `objective` with the weighted sum of violations `violation`, penalized by `multiplier`
+    pub fn new(objective: ST, violation: f64, multiplier: f64) -> Self {
+        let penalized = objective.energy() + multiplier * violation;
+        Self {
+            penalized: NotNan::new(penalized).expect("penalized score is NaN"),
+            violation: NotNan::new(violation).expect("violation is NaN"),
+            objective,
+        }
+    }
+
+    /// Same score with the penalized value shifted by `offset`
+    fn offset(self, offset: f64) -> Self {
+        Self {
+            penalized: NotNan::new(self.penalized() + offset).expect("penalized score is NaN"),
+            ..self
+        }
+    }
+
+    /// Objective of the model
+    pub fn objective(&self) -> ST {
+        self.objective
+    }
+
+    /// Weighted sum of the constraint violations
+    pub fn violation(&self) -> f64 {
+        self.violation.into_inner()
+    }
+
+    /// Energy of the objective plus the penalty,
+    /// relative to the current solution for trial solutions of [`PenalizedModel`]
+    pub fn penalized(&self) -> f64 {
+        self.penalized.into_inner()
+    }
+
+    /// Whether no constraint is violated
+    pub fn is_feasible(&self) -> bool {
+        self.violation() <= 0.0
+    }
+}
+
+impl<ST: Energy> Energy for PenalizedScore<ST> {
+    fn energy(&self) -> f64 {
+        self.penalized()
+    }
+}
+
+/// Adapter optimizing the objective of a [`ConstrainedOptModel`] penalized by its constraint violations,
+/// so that the search can go through infeasible solutions.
+///
+/// The score is `objective + multiplier * sum(weight_i * violation_i)` ([`PenalizedScore`]).
+/// The constraint weights are fixed ([`PenalizedModel::with_weights`], 1 by default) while the multiplier
+/// can be adapted during the optimization by [`StrategicOscillation`].
+/// When the multiplier changes, the optimizer still holds the current and best scores penalized with the previous one.
+/// Trial scores are therefore offset like the score of the current solution they are generated from,
+/// so that the comparisons with the current solution always use the current multiplier,
+/// and the final score is penalized again by [`OptModel::postprocess_solution`].
+/// As the best penalized solution of an optimization depends on the multipliers along the way,
+/// the best feasible solution evaluated is tracked separately ([`PenalizedModel::best_feasible`]).
+///
+/// Example
+///
+/// ```rust
+/// let model = PenalizedModel::new(model, 1.0);
+/// let mut oscillation = StrategicOscillation::new(&model, NonZero::new(50).unwrap(), 1.5);
+/// opt.run_with_observer(&model, None, n_iter, time_limit, &mut Never, None, &mut rand::rng(), &mut oscillation)?;
+/// let (solution, objective) = model.best_feasible().unwrap();
+/// ```
+#[derive(Debug)]
+pub struct PenalizedModel<M: OptModel> {
+    model: M,
+    weights: Option<Vec<f64>>,
+    initial_multiplier: f64,
+    /// bits of the current multiplier
+    multiplier: AtomicU64,
+    best_feasible: Mutex<Option<(M::SolutionType, M::ScoreType)>>,
+}
+
+impl<M: ConstrainedOptModel> PenalizedModel<M> {
+    /// Penalize the violations of `model` by `multiplier`
+    pub fn new(model: M, multiplier: f64) -> Self {
+        Self {
+            model,
+            weights: None,
+            initial_multiplier: multiplier,
+            multiplier: AtomicU64::new(multiplier.to_bits()),
+            best_feasible: Mutex::new(None),
+        }
+    }
+
+    /// Set the positive weight of each constraint, in the order of [`ConstrainedOptModel::violations`]
+    pub fn with_weights(mut self, weights: Vec<f64>) -> Self {
+        self.weights = Some(weights);
+        self
+    }
+
+    /// Wrapped model
+    pub fn inner(&self) -> &M {
+        &self.model
+    }
+
+    /// Current penalty multiplier
+    pub fn multiplier(&self) -> f64 {
+        f64::from_bits(self.multiplier.load(Ordering::Relaxed))
+    }
+
+    /// Set the penalty multiplier for the solutions evaluated from now on
+    pub fn set_multiplier(&self, multiplier: f64) {
+        self.multiplier
+            .store(multiplier.to_bits(), Ordering::Relaxed);
+    }
+
+    /// Best feasible solution evaluated so far with its objective, if any
+    pub fn best_feasible(&self) -> Option<(M::SolutionType, M::ScoreType)> {
+        lock(&self.best_feasible).clone()
+    }
+
+    /// Forget the best feasible solution and restore the initial multiplier, e.g. before another optimization
+    pub fn reset(&self) {
+        *lock(&self.best_feasible) = None;
+        self.set_multiplier(self.initial_multiplier);
+    }
+
+    /// Weighted sum of violations
+    fn violation(&self, violations: &[f64]) -> f64 {
+        match &self.weights {
+            Some(weights) => violations.iter().zip(weights).map(|(v, w)| v * w).sum(),
+            None => violations.iter().sum(),
+        }
+    }
+
+    /// Repair the solution if infeasible, record it if it is the best feasible one, and penalize its objective
+    fn evaluate(
+        &self,
+        solution: M::SolutionType,
+        score: M::ScoreType,
+        multiplier: f64,
+    ) -> (M::SolutionType, PenalizedScore<M::ScoreType>) {
+        let mut violations = self.model.violations(&solution);
+        let (solution, score) = if violations.iter().any(|&v| v > 0.0)
+            && let Some((repaired, repaired_score)) =
+                self.model.repair(&solution, score, &violations)
+        {
+            violations = self.model.violations(&repaired);
+            (repaired, repaired_score)
+        } else {
+            (solution, score)
+        };
+        let score = PenalizedScore::new(score, self.violation(&violations), multiplier);
+        if score.is_feasible() {
+            let mut best_feasible = lock(&self.best_feasible);
+            if best_feasible
+                .as_ref()
+                .is_none_or(|(_, best)| score.objective < *best)
+            {
+                *best_feasible = Some((solution.clone(), score.objective));
+            }
+        }
+        (solution, score)
+    }
+}
+
+impl<M: ConstrainedOptModel> OptModel for PenalizedModel<M> {
+    type ScoreType = PenalizedScore<M::ScoreType>;
+    type SolutionType = M::SolutionType;
+    type TransitionType = M::TransitionType;
+
+    fn generate_random_solution<R: rand::Rng>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        let (solution, score) = self.model.generate_random_solution(rng)?;
+        Ok(self.evaluate(solution, score, self.multiplier()))
+    }
+
+    fn generate_trial_solution<R: rand::Rng>(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+        rng: &mut R,
+    ) -> Result<(Self::SolutionType, Self::TransitionType, Self::ScoreType), LocalsearchError> {
+        let (solution, transition, score) =
+            self.model
+                .generate_trial_solution(current_solution, current_score.objective, rng)?;
+        let multiplier = self.multiplier();
+        let (solution, score) = self.evaluate(solution, score, multiplier);
+        // the current score may have been penalized with an earlier multiplier:
+        // offset the trial by the same amount, so that their difference uses the current multiplier
+        let repenalized = PenalizedScore::new(
+            current_score.objective,
+            current_score.violation(),
+            multiplier,
+        );
+        let offset = current_score.penalized() - repenalized.penalized();
+        Ok((solution, transition, score.offset(offset)))
+    }
+
+    fn preprocess_solution(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        let (solution, score) = self
+            .model
+            .preprocess_solution(current_solution, current_score.objective)?;
+        Ok(self.evaluate(solution, score, self.multiplier()))
+    }
+
+    fn postprocess_solution(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        let (solution, score) = self
+            .model
+            .postprocess_solution(current_solution, current_score.objective)?;
+        let violation = self.violation(&self.model.violations(&solution));
+        let score = PenalizedScore::new(score, violation, self.multiplier());
+        Ok((solution, score))
+    }
+}
+
+/// Observer adapting the penalty multiplier of a [`PenalizedModel`] by strategic oscillation.
+///
+/// Every `period` iterations, the multiplier is multiplied by `factor` if the current solution
+/// was infeasible at each of these iterations, and divided by `factor` if it was feasible at each of them,
+/// so that the search oscillates around the boundary of the feasible region.
+/// The scores of the current and best solutions held by the optimizer keep the multiplier they were evaluated with.
+pub struct StrategicOscillation<'a, M: OptModel> {
+    model: &'a PenalizedModel<M>,
+    period: NonZero<usize>,
+    factor: f64,
+    min_multiplier: f64,
+    max_multiplier: f64,
+    n_iter: usize,
+    n_feasible: usize,
+}
+
+impl<'a, M: ConstrainedOptModel> StrategicOscillation<'a, M> {
+    /// Constructor of StrategicOscillation
+    ///
+    /// - `model` : the penalized model whose multiplier is adapted
+    /// - `period` : number of iterations between adaptations
+    /// - `factor` : factor by which the multiplier is raised or lowered, larger than 1
+    pub fn new(model: &'a PenalizedModel<M>, period: NonZero<usize>, factor: f64) -> Self {
+        Self {
+            model,
+            period,
+            factor,
+            min_multiplier: 1e-6,
+            max_multiplier: 1e6,
+            n_iter: 0,
+            n_feasible: 0,
+        }
+    }
+
+    /// Bound the multiplier to `[min_multiplier, max_multiplier]`, `[1e-6, 1e6]` by default
+    pub fn with_bounds(mut self, min_multiplier: f64, max_multiplier: f64) -> Self {
+        self.min_multiplier = min_multiplier;
+        self.max_multiplier = max_multiplier;
+        self
+    }
+}
+
+impl<M: ConstrainedOptModel>
+    OptObserver<M::SolutionType, PenalizedScore<M::ScoreType>, M::TransitionType>
+    for StrategicOscillation<'_, M>
+{
+    fn on_iteration(
+        &mut self,
+        progress: OptProgress<M::SolutionType, PenalizedScore<M::ScoreType>>,
+    ) {
+        self.n_iter += 1;
+        self.n_feasible += progress.current_score.is_feasible() as usize;
+        if self.n_iter < self.period.get() {
+            return;
+        }
+        let multiplier = self.model.multiplier();
+        let multiplier = if self.n_feasible == 0 {
+            multiplier * self.factor
+        } else if self.n_feasible == self.n_iter {
+            multiplier / self.factor
+        } else {
+            multiplier
+        };
+        self.model
+            .set_multiplier(multiplier.clamp(self.min_multiplier, self.max_multiplier));
+        self.n_iter = 0;
+        self.n_feasible = 0;
+    }
+}
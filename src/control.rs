@@ -0,0 +1,180 @@
+//! Control Handle of a Running Optimization
+
+use std::sync::{
+    Arc, Condvar, Mutex, PoisonError,
+    atomic::{AtomicBool, Ordering},
+};
+
+use crate::{Checkpoint, Duration, Instant, utils::lock};
+
+struct ControlState<S, SC, T> {
+    cancelled: AtomicBool,
+    paused: Mutex<bool>,
+    resumed: Condvar,
+    best: Mutex<Option<(S, SC)>>,
+    injected: Mutex<Option<(S, SC)>>,
+    checkpoint_requested: AtomicBool,
+    checkpoint: Mutex<Option<Checkpoint<S, SC, T>>>,
+    restored: Mutex<Option<Checkpoint<S, SC, T>>>,
+}
+
+/// OptControl is a `Send + Sync` handle to steer a running optimization from other threads.
+///
+/// Clones share the same state, so one clone can be passed to the optimizer
+/// (e.g. [`LocalSearchOptimizer::run_with_control`](crate::optim::LocalSearchOptimizer::run_with_control))
+/// while another one is kept by the controlling thread.
+///
+/// The optimizer checks the handle at the beginning of each iteration
+/// (of each exchange round for replica based optimizers):
+/// it takes a requested [`Checkpoint`], stops when cancelled, blocks while paused
+/// and adopts an injected solution as its current solution.
+/// Time spent paused does not count toward the time limit.
+///
+/// `S`, `SC` and `T` are the solution, score and transition types of the model.
+pub struct OptControl<S, SC, T> {
+    state: Arc<ControlState<S, SC, T>>,
+}
+
+impl<S, SC, T> Clone for OptControl<S, SC, T> {
+    fn clone(&self) -> Self {
+        Self {
+            state: Arc::clone(&self.state),
+        }
+    }
+}
+
+impl<S, SC, T> Default for OptControl<S, SC, T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<S, SC, T> OptControl<S, SC, T> {
+    /// Constructor of OptControl
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(ControlState {
+                cancelled: AtomicBool::new(false),
+                paused: Mutex::new(false),
+                resumed: Condvar::new(),
+                best: Mutex::new(None),
+                injected: Mutex::new(None),
+                checkpoint_requested: AtomicBool::new(false),
+                checkpoint: Mutex::new(None),
+                restored: Mutex::new(None),
+            }),
+        }
+    }
+
+    /// Request the optimization to stop. A paused optimization is woken up and stops.
+    pub fn cancel(&self) {
+        // hold the pause lock so that a waiting optimizer cannot miss the notification
+        let _paused = lock(&self.state.paused);
+        self.state.cancelled.store(true, Ordering::SeqCst);
+        self.state.resumed.notify_all();
+    }
+
+    /// Whether cancellation was requested
+    pub fn is_cancelled(&self) -> bool {
+        self.state.cancelled.load(Ordering::SeqCst)
+    }
+
+    /// Pause the optimization at the beginning of its next iteration
+    pub fn pause(&self) {
+        *lock(&self.state.paused) = true;
+    }
+
+    /// Resume a paused optimization
+    pub fn resume(&self) {
+        *lock(&self.state.paused) = false;
+        self.state.resumed.notify_all();
+    }
+
+    /// Whether the optimization is requested to be paused
+    pub fn is_paused(&self) -> bool {
+        *lock(&self.state.paused)
+    }
+
+    /// Replace the current solution of the optimization at its next iteration.
+    /// The solution also becomes the best one if its score is better.
+    /// A solution injected before the previous one was adopted replaces it.
+    pub fn inject(&self, solution: S, score: SC) {
+        *lock(&self.state.injected) = Some((solution, score));
+    }
+
+    /// Request a [`Checkpoint`] to be taken at the beginning of the next iteration,
+    /// before a cancellation is handled, so that cancelling right after the request still yields one.
+    /// Retrieve it with [`OptControl::take_checkpoint`].
+    pub fn request_checkpoint(&self) {
+        self.state
+            .checkpoint_requested
+            .store(true, Ordering::SeqCst);
+    }
+
+    /// Take the latest checkpoint, if one was taken since the last call
+    pub fn take_checkpoint(&self) -> Option<Checkpoint<S, SC, T>> {
+        lock(&self.state.checkpoint).take()
+    }
+
+    /// Make the next optimization run with this handle start from `checkpoint`.
+    /// [`LocalSearchOptimizer::resume`](crate::optim::LocalSearchOptimizer::resume) does this for you.
+    pub fn restore(&self, checkpoint: Checkpoint<S, SC, T>) {
+        *lock(&self.state.restored) = Some(checkpoint);
+    }
+
+    /// Whether a checkpoint was requested, resetting the request
+    pub(crate) fn take_checkpoint_request(&self) -> bool {
+        self.state
+            .checkpoint_requested
+            .swap(false, Ordering::SeqCst)
+    }
+
+    /// Store a checkpoint taken by the optimizer
+    pub(crate) fn store_checkpoint(&self, checkpoint: Checkpoint<S, SC, T>) {
+        *lock(&self.state.checkpoint) = Some(checkpoint);
+    }
+
+    /// Take the checkpoint to restore, if any
+    pub(crate) fn take_restored(&self) -> Option<Checkpoint<S, SC, T>> {
+        lock(&self.state.restored).take()
+    }
+
+    /// Block while paused and return the time spent waiting
+    pub(crate) fn wait_while_paused(&self) -> Duration {
+        let mut paused = lock(&self.state.paused);
+        if !*paused {
+            return Duration::ZERO;
+        }
+        let start_time = Instant::now();
+        while *paused && !self.is_cancelled() {
+            paused = self
+                .state
+                .resumed
+                .wait(paused)
+                .unwrap_or_else(PoisonError::into_inner);
+        }
+        Instant::now().duration_since(start_time)
+    }
+
+    /// Take the injected solution, if any
+    pub(crate) fn take_injected(&self) -> Option<(S, SC)> {
+        lock(&self.state.injected).take()
+    }
+}
+
+impl<S: Clone, SC: Copy, T> OptControl<S, SC, T> {
+    /// Current best solution and score, `None` until the optimization has started
+    pub fn best(&self) -> Option<(S, SC)> {
+        lock(&self.state.best).clone()
+    }
+
+    /// Current best score, `None` until the optimization has started
+    pub fn best_score(&self) -> Option<SC> {
+        lock(&self.state.best).as_ref().map(|(_, score)| *score)
+    }
+
+    /// Publish a new best solution
+    pub(crate) fn publish_best(&self, solution: &S, score: SC) {
+        *lock(&self.state.best) = Some((solution.clone(), score));
+    }
+}
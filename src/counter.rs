@@ -2,6 +2,7 @@ use std::collections::VecDeque;
 
 /// Sliding window based acceptance counter
 #[derive(Debug, Clone)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct AcceptanceCounter {
     window_size: usize,
     buffer: std::collections::VecDeque<u8>,
@@ -0,0 +1,151 @@
+use rand::Rng;
+
+use crate::{LocalsearchError, OptModel, TrialSolution};
+
+/// Outcome of [`DynOptModel::generate_trial_solution_dyn`]
+type DynTrialSolution<M> = Result<
+    (
+        <M as DynOptModel>::SolutionType,
+        <M as DynOptModel>::TransitionType,
+        <M as DynOptModel>::ScoreType,
+    ),
+    LocalsearchError,
+>;
+
+/// Boxed model, e.g. loaded from a registry at runtime, which every optimizer accepts as an [`OptModel`]
+pub type BoxedOptModel<S, T, SC> =
+    Box<dyn DynOptModel<SolutionType = S, TransitionType = T, ScoreType = SC>>;
+
+/// DynOptModel is the dyn-compatible counterpart of [`OptModel`], whose methods draw randomness
+/// from a `&mut dyn Rng` instead of being generic over the generator.
+///
+/// Every [`OptModel`] implements it, so any model can be boxed as a [`BoxedOptModel`] or borrowed as a
+/// `&dyn DynOptModel`, and such trait objects implement [`OptModel`] in turn,
+/// so that every optimizer can run them.
+///
+/// Example
+///
+/// ```rust
+/// let mut registry: HashMap<&str, BoxedOptModel<Vec<f64>, (), NotNan<f64>>> = HashMap::new();
+/// registry.insert("quadratic", Box::new(QuadraticModel::new(...)));
+/// let result = opt.run(&registry["quadratic"], None, n_iter, time_limit)?;
+/// ```
+pub trait DynOptModel: Sync + Send {
+    /// Type of the Score
+    type ScoreType: Ord + Copy + Sync + Send;
+    /// Type of the Solution
+    type SolutionType: Clone + Sync + Send;
+    /// Type of the Transition
+    type TransitionType: Clone + Sync + Send;
+
+    /// Same as [`OptModel::generate_random_solution`]
+    fn generate_random_solution_dyn(
+        &self,
+        rng: &mut dyn Rng,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError>;
+
+    /// Same as [`OptModel::generate_trial_solution`]
+    fn generate_trial_solution_dyn(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+        rng: &mut dyn Rng,
+    ) -> DynTrialSolution<Self>;
+
+    /// Same as [`OptModel::preprocess_solution`]
+    fn preprocess_solution_dyn(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError>;
+
+    /// Same as [`OptModel::postprocess_solution`]
+    fn postprocess_solution_dyn(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError>;
+}
+
+impl<M: OptModel> DynOptModel for M {
+    type ScoreType = M::ScoreType;
+    type SolutionType = M::SolutionType;
+    type TransitionType = M::TransitionType;
+
+    fn generate_random_solution_dyn(
+        &self,
+        mut rng: &mut dyn Rng,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        self.generate_random_solution(&mut rng)
+    }
+
+    fn generate_trial_solution_dyn(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+        mut rng: &mut dyn Rng,
+    ) -> DynTrialSolution<Self> {
+        self.generate_trial_solution(current_solution, current_score, &mut rng)
+    }
+
+    fn preprocess_solution_dyn(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        self.preprocess_solution(current_solution, current_score)
+    }
+
+    fn postprocess_solution_dyn(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        self.postprocess_solution(current_solution, current_score)
+    }
+}
+
+/// Trait objects of [`DynOptModel`] are models, and so are their boxed and borrowed forms
+impl<S, T, SC> OptModel
+    for dyn DynOptModel<SolutionType = S, TransitionType = T, ScoreType = SC> + '_
+where
+    S: Clone + Sync + Send,
+    T: Clone + Sync + Send,
+    SC: Ord + Copy + Sync + Send,
+{
+    type ScoreType = SC;
+    type SolutionType = S;
+    type TransitionType = T;
+
+    fn generate_random_solution<R: Rng>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        self.generate_random_solution_dyn(rng)
+    }
+
+    fn generate_trial_solution<R: Rng>(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+        rng: &mut R,
+    ) -> TrialSolution<Self> {
+        self.generate_trial_solution_dyn(current_solution, current_score, rng)
+    }
+
+    fn preprocess_solution(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        self.preprocess_solution_dyn(current_solution, current_score)
+    }
+
+    fn postprocess_solution(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        self.postprocess_solution_dyn(current_solution, current_score)
+    }
+}
@@ -9,4 +9,32 @@ pub enum LocalsearchError {
     /// Preprocessing of the solution failed.
     #[error("Preprocessing failed")]
     PreprocessError,
+    /// The optimizer configuration is invalid.
+    #[error("Invalid optimizer configuration: {0}")]
+    ConfigError(String),
+    /// A parameter of an optimizer is out of its valid range.
+    #[error("Invalid parameter `{name}`: must be {expected}")]
+    InvalidParameter {
+        /// name of the parameter
+        name: &'static str,
+        /// valid range of the parameter
+        expected: &'static str,
+    },
+    /// The model has no valid move from the current solution.
+    /// Returned by trial generation, it does not stop the optimization: the trial is rejected.
+    #[error("No valid move from the current solution")]
+    NoValidMove,
+    /// Error defined by the model, e.g. an invalid state reached while generating a trial solution.
+    #[error("Model error: {0}")]
+    ModelError(#[source] Box<dyn std::error::Error + Send + Sync>),
+    /// Model code panicked during the optimization, with the panic message.
+    #[error("Model panicked: {0}")]
+    ModelPanic(String),
+}
+
+impl LocalsearchError {
+    /// Wrap an error of the model, which can be recovered with `downcast_ref` on the payload
+    pub fn model<E: Into<Box<dyn std::error::Error + Send + Sync>>>(error: E) -> Self {
+        Self::ModelError(error.into())
+    }
 }
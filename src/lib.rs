@@ -4,19 +4,46 @@
 #![doc = include_str!("../README.md")]
 
 pub mod optim;
+pub mod stop_condition;
 pub mod utils;
 
 mod callback;
-pub use callback::{OptCallbackFn, OptProgress};
+pub use callback::{
+    OptCallbackFn, OptMetrics, OptProgress, ProgressSnapshot, SnapshotSender, channel_callback,
+};
+
+mod checkpoint;
+pub use checkpoint::Checkpoint;
+
+mod constraint;
+pub use constraint::{ConstrainedOptModel, PenalizedModel, PenalizedScore, StrategicOscillation};
+
+mod control;
+pub use control::OptControl;
 
 mod counter;
 pub use counter::AcceptanceCounter;
 
+mod dyn_model;
+pub use dyn_model::{BoxedOptModel, DynOptModel};
+
 mod error;
 pub use error::LocalsearchError;
 
 mod model;
-pub use model::OptModel;
+pub use model::{IncrementalOptModel, Maximize, OptModel, TrialSolution};
+
+mod observer;
+pub use observer::OptObserver;
+
+mod result;
+pub use result::{AlgorithmState, OptResult, StopReason};
+
+mod score;
+pub use score::{Energy, HardMediumSoftScore, ObjectiveVector};
+
+mod session;
+pub use session::{OptSession, OptStepper};
 
 mod time_wrapper;
 pub use time_wrapper::{Duration, Instant};
@@ -1,8 +1,23 @@
+use std::cmp::Reverse;
+
 use auto_impl::auto_impl;
 
 use crate::LocalsearchError;
 
-/// OptModel is a trait that defines requirements to be used with optimization algorithm
+/// Outcome of [`OptModel::generate_trial_solution`]: the trial solution, the transition and the trial score
+pub type TrialSolution<M> = Result<
+    (
+        <M as OptModel>::SolutionType,
+        <M as OptModel>::TransitionType,
+        <M as OptModel>::ScoreType,
+    ),
+    LocalsearchError,
+>;
+
+/// OptModel is a trait that defines requirements to be used with optimization algorithm.
+///
+/// Optimizers minimize the score. To maximize, use a [`Reverse`] score type,
+/// or wrap a model whose score is to be maximized in [`Maximize`].
 #[auto_impl(&, Box, Rc, Arc)]
 pub trait OptModel: Sync + Send {
     /// Type of the Score
@@ -18,13 +33,15 @@ pub trait OptModel: Sync + Send {
         rng: &mut R,
     ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError>;
 
-    /// Generate a new trial solution from current solution
+    /// Generate a new trial solution from current solution.
+    /// Return [`LocalsearchError::NoValidMove`] if there is no valid move from it, which only rejects the trial,
+    /// or another error (e.g. [`LocalsearchError::model`]) to stop the optimization.
     fn generate_trial_solution<R: rand::Rng>(
         &self,
         current_solution: Self::SolutionType,
         current_score: Self::ScoreType,
         rng: &mut R,
-    ) -> (Self::SolutionType, Self::TransitionType, Self::ScoreType);
+    ) -> TrialSolution<Self>;
 
     /// Preprocess the solution
     fn preprocess_solution(
@@ -40,7 +57,139 @@ pub trait OptModel: Sync + Send {
         &self,
         current_solution: Self::SolutionType,
         current_score: Self::ScoreType,
-    ) -> (Self::SolutionType, Self::ScoreType) {
-        (current_solution, current_score)
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        Ok((current_solution, current_score))
+    }
+}
+
+/// IncrementalOptModel is an extension of [`OptModel`] for models that can evaluate a move
+/// without building the trial solution.
+///
+/// Optimizers working on this trait propose transitions against a borrowed current solution
+/// and only apply the selected transition in place once it is accepted,
+/// so rejected candidates never clone the solution.
+pub trait IncrementalOptModel: OptModel {
+    /// Propose a transition from the current solution and return it with the score
+    /// the solution would have after applying it (typically `current_score` plus a delta).
+    /// Errors are handled like those of [`OptModel::generate_trial_solution`].
+    fn propose_transition<R: rand::Rng>(
+        &self,
+        current_solution: &Self::SolutionType,
+        current_score: Self::ScoreType,
+        rng: &mut R,
+    ) -> Result<(Self::TransitionType, Self::ScoreType), LocalsearchError>;
+
+    /// Apply the transition to the solution in place
+    fn apply_transition(
+        &self,
+        solution: &mut Self::SolutionType,
+        transition: &Self::TransitionType,
+    );
+}
+
+/// Adapter maximizing the score of the wrapped model.
+///
+/// The score becomes [`Reverse`] of the model's score, so that every optimizer, stop condition and
+/// tuner comparing scores by `Ord` or by [`Energy`](crate::Energy) prefers higher ones,
+/// and relative acceptance rules see the magnitude of the original score.
+/// Initial solutions and injected solutions take the reversed score `Reverse(score)`,
+/// and the result holds `Reverse(best_score)`.
+///
+/// Example
+///
+/// ```rust
+/// let model = Maximize::new(RevenueModel::new(...));
+/// let result = opt.run(&model, None, n_iter, time_limit)?;
+/// let Reverse(revenue) = result.score;
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Maximize<M> {
+    model: M,
+}
+
+impl<M> Maximize<M> {
+    /// Maximize the score of `model`
+    pub fn new(model: M) -> Self {
+        Self { model }
+    }
+
+    /// Wrapped model
+    pub fn inner(&self) -> &M {
+        &self.model
+    }
+
+    /// Unwrap the model
+    pub fn into_inner(self) -> M {
+        self.model
+    }
+}
+
+impl<M: OptModel> OptModel for Maximize<M> {
+    type ScoreType = Reverse<M::ScoreType>;
+    type SolutionType = M::SolutionType;
+    type TransitionType = M::TransitionType;
+
+    fn generate_random_solution<R: rand::Rng>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        let (solution, score) = self.model.generate_random_solution(rng)?;
+        Ok((solution, Reverse(score)))
+    }
+
+    fn generate_trial_solution<R: rand::Rng>(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+        rng: &mut R,
+    ) -> TrialSolution<Self> {
+        let (solution, transition, score) =
+            self.model
+                .generate_trial_solution(current_solution, current_score.0, rng)?;
+        Ok((solution, transition, Reverse(score)))
+    }
+
+    fn preprocess_solution(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        let (solution, score) = self
+            .model
+            .preprocess_solution(current_solution, current_score.0)?;
+        Ok((solution, Reverse(score)))
+    }
+
+    fn postprocess_solution(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        let (solution, score) = self
+            .model
+            .postprocess_solution(current_solution, current_score.0)?;
+        Ok((solution, Reverse(score)))
+    }
+}
+
+impl<M: IncrementalOptModel> IncrementalOptModel for Maximize<M> {
+    fn propose_transition<R: rand::Rng>(
+        &self,
+        current_solution: &Self::SolutionType,
+        current_score: Self::ScoreType,
+        rng: &mut R,
+    ) -> Result<(Self::TransitionType, Self::ScoreType), LocalsearchError> {
+        let (transition, score) =
+            self.model
+                .propose_transition(current_solution, current_score.0, rng)?;
+        Ok((transition, Reverse(score)))
+    }
+
+    fn apply_transition(
+        &self,
+        solution: &mut Self::SolutionType,
+        transition: &Self::TransitionType,
+    ) {
+        self.model.apply_transition(solution, transition);
     }
 }
@@ -0,0 +1,68 @@
+//! Observer of Optimization Events
+
+use crate::{StopReason, callback::OptProgress};
+
+/// OptObserver receives events of an optimization.
+/// Every hook has an empty default implementation, so an observer only implements the events it cares about.
+///
+/// Any [`OptCallbackFn`](crate::OptCallbackFn) closure is an observer whose `on_iteration` invokes the closure,
+/// so existing callbacks can be passed wherever an observer is expected.
+///
+/// `iter` is the iteration at which the event happened, counted like [`OptProgress::iter`].
+///
+/// Example
+///
+/// ```rust
+/// struct Log;
+///
+/// impl<S, T> OptObserver<S, NotNan<f64>, T> for Log {
+///     fn on_improvement(&mut self, iter: usize, _solution: &S, score: NotNan<f64>) {
+///         println!("{iter}: improved to {score}");
+///     }
+///
+///     fn on_stop(&mut self, iter: usize, stop_reason: StopReason) {
+///         println!("{iter}: stopped by {stop_reason:?}");
+///     }
+/// }
+///
+/// opt.run_with_observer(&model, None, n_iter, time_limit, &mut Never, None, &mut rand::rng(), &mut Log);
+/// ```
+#[allow(unused_variables)]
+pub trait OptObserver<S, SC, T> {
+    /// Invoked at the end of each iteration (or round of replica based optimizers)
+    fn on_iteration(&mut self, progress: OptProgress<S, SC>) {}
+
+    /// Invoked when the best score improves, including by an injected solution
+    fn on_improvement(&mut self, iter: usize, solution: &S, score: SC) {}
+
+    /// Invoked when the search returns to the best solution after `return_iter` iterations without improvement
+    fn on_return_to_best(&mut self, iter: usize, score: SC) {}
+
+    /// Invoked when an annealing schedule changes the inverse temperature to `beta`
+    fn on_temperature_update(&mut self, iter: usize, beta: f64) {}
+
+    /// Invoked when a reheat policy raises the temperature, i.e. lowers the inverse temperature to `beta` (simulated annealing).
+    /// Followed by [`OptObserver::on_temperature_update`] if `beta` differs from the one used during the iteration.
+    fn on_reheat(&mut self, iter: usize, beta: f64) {}
+
+    /// Invoked for each attempted exchange between replicas `replica` and `replica + 1` (parallel tempering)
+    fn on_replica_exchange(&mut self, iter: usize, replica: usize, accepted: bool) {}
+
+    /// Invoked after the population is resampled with the normalized `weights` of its members (population annealing)
+    fn on_population_resample(&mut self, iter: usize, weights: &[f64]) {}
+
+    /// Invoked when an accepted transition is appended to the tabu list (tabu search)
+    fn on_tabu_append(&mut self, iter: usize, transition: &T) {}
+
+    /// Invoked once when the optimization stops, with the number of iterations done
+    fn on_stop(&mut self, iter: usize, stop_reason: StopReason) {}
+}
+
+impl<F, S, SC, T> OptObserver<S, SC, T> for F
+where
+    F: FnMut(OptProgress<S, SC>),
+{
+    fn on_iteration(&mut self, progress: OptProgress<S, SC>) {
+        self(progress);
+    }
+}
@@ -1,35 +1,61 @@
 //! Optimization Algorithm
 
+mod acceptance;
 mod adaptive_annealing;
+mod amosa;
 mod base;
+mod config;
 mod epsilon_greedy;
 mod generic;
 mod great_deluge;
 mod hill_climbing;
+mod incremental;
+mod lexicographic;
 mod logistic_annealing;
 mod metropolis;
 mod parallel_tempering;
+mod pareto;
 mod population_annealing;
 mod random;
 mod relative_annealing;
+mod scalarization;
+mod schedule;
 mod simulated_annealing;
 mod tabu_search;
+mod temperature;
 mod tsallis;
 
+pub use acceptance::{AcceptanceCriterion, AcceptanceState};
 pub use adaptive_annealing::{
     AdaptiveAnnealingOptimizer, AdaptiveScheduler, TargetAccScheduleMode,
 };
+pub use amosa::{AmosaOptimizer, AmosaSession, ParetoCallbackFn, ParetoProgress, ParetoResult};
 pub use base::{LocalSearchOptimizer, TransitionProbabilityFn};
+pub use config::{
+    AdaptiveAnnealingConfig, AmosaConfig, EpsilonGreedyConfig, GenericLocalSearchConfig,
+    GreatDelugeConfig, HillClimbingConfig, LexicographicAnnealingConfig, LogisticAnnealingConfig,
+    MetropolisConfig, OptimizerConfig, ParallelTemperingConfig, PopulationAnnealingConfig,
+    RandomSearchConfig, RelativeAnnealingConfig, SimulatedAnnealingConfig, TabuSearchConfig,
+    TsallisRelativeAnnealingConfig,
+};
 pub use epsilon_greedy::EpsilonGreedyOptimizer;
 pub use generic::GenericLocalSearchOptimizer;
 pub use great_deluge::GreatDelugeOptimizer;
 pub use hill_climbing::HillClimbingOptimizer;
+pub use incremental::IncrementalOptimizer;
+pub use lexicographic::{LexicographicAnnealing, LexicographicAnnealingOptimizer};
 pub use logistic_annealing::LogisticAnnealingOptimizer;
 pub use metropolis::MetropolisOptimizer;
 pub use parallel_tempering::ParallelTemperingOptimizer;
+pub use pareto::ParetoArchive;
 pub use population_annealing::PopulationAnnealingOptimizer;
 pub use random::RandomSearchOptimizer;
 pub use relative_annealing::RelativeAnnealingOptimizer;
-pub use simulated_annealing::SimulatedAnnealingOptimizer;
+pub use scalarization::{
+    Scalarization, ScalarizationSweep, ScalarizedModel, ScalarizedScore, SweepResult,
+};
+pub use schedule::{Schedule, ScheduleClock};
+pub use simulated_annealing::{ReheatPolicy, SimulatedAnnealingOptimizer};
 pub use tabu_search::{TabuList, TabuSearchOptimizer};
+pub use temperature::{EnergySample, TemperatureEstimator};
 pub use tsallis::TsallisRelativeAnnealingOptimizer;
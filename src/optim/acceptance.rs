@@ -0,0 +1,91 @@
+use super::TransitionProbabilityFn;
+use crate::{AlgorithmState, Duration};
+
+/// Elapsed fraction of `time_limit`, between 0 and 1
+pub(crate) fn elapsed_fraction(elapsed: Duration, time_limit: Duration) -> f64 {
+    if time_limit.is_zero() {
+        return 1.0;
+    }
+    (elapsed.as_secs_f64() / time_limit.as_secs_f64()).min(1.0)
+}
+
+/// State of the optimization at the end of an iteration, passed to [`AcceptanceCriterion::update`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[non_exhaustive]
+pub struct AcceptanceState<ST> {
+    /// current iteration step, counted like [`OptProgress::iter`](crate::OptProgress::iter)
+    pub iter: usize,
+    /// maximum iterations of the optimization
+    pub n_iter: usize,
+    /// elapsed fraction of the time limit, between 0 and 1
+    pub elapsed_fraction: f64,
+    /// acceptance ratio over the recent iterations
+    pub acceptance_ratio: f64,
+    /// number of iterations since the best score last improved
+    pub stagnation: usize,
+    /// current best score
+    pub best_score: ST,
+    /// score of the current solution
+    pub current_score: ST,
+}
+
+/// Rule deciding whether a trial solution that does not improve the current score is accepted,
+/// with a state updated at the end of each iteration (e.g. the temperature of an annealing schedule).
+///
+/// Pass an implementation to [`GenericLocalSearchOptimizer::new`](super::GenericLocalSearchOptimizer::new),
+/// which clones it at the start of each optimization, to run a new acceptance rule
+/// (Barker, heat-bath, demon, ...). Every [`TransitionProbabilityFn`] is a stateless criterion.
+///
+/// Example
+///
+/// ```rust
+/// /// Barker acceptance with a geometric cooling schedule
+/// #[derive(Clone)]
+/// struct Barker {
+///     beta: f64,
+///     cooling_rate: f64,
+/// }
+///
+/// impl AcceptanceCriterion<NotNan<f64>> for Barker {
+///     fn acceptance_probability(&self, current: NotNan<f64>, trial: NotNan<f64>) -> f64 {
+///         1.0 / (1.0 + (self.beta * (trial - current).into_inner()).exp())
+///     }
+///
+///     fn update(&mut self, _state: &AcceptanceState<NotNan<f64>>) {
+///         self.beta /= self.cooling_rate;
+///     }
+/// }
+///
+/// let opt = GenericLocalSearchOptimizer::new(patience, n_trials, return_iter, Barker { beta: 1.0, cooling_rate: 0.99 });
+/// ```
+#[allow(unused_variables)]
+pub trait AcceptanceCriterion<ST> {
+    /// Probability to accept `trial_score` from `current_score`.
+    /// Only called for trial scores that are not lower than the current score, lower ones are always accepted.
+    fn acceptance_probability(&self, current_score: ST, trial_score: ST) -> f64;
+
+    /// Prepare the state for an optimization starting from `initial_score`
+    fn initialize(&mut self, initial_score: ST) {}
+
+    /// Update the state at the end of an iteration
+    fn update(&mut self, state: &AcceptanceState<ST>) {}
+
+    /// State reported to progress metrics, checkpoints and the result.
+    /// A change of `beta` across [`AcceptanceCriterion::update`] is reported as a temperature update to observers.
+    fn algorithm_state(&self) -> AlgorithmState {
+        AlgorithmState::default()
+    }
+
+    /// Continue from the state of a checkpoint, after [`AcceptanceCriterion::initialize`]
+    fn restore(&mut self, state: &AlgorithmState) {}
+}
+
+impl<ST, F> AcceptanceCriterion<ST> for F
+where
+    ST: Ord + Sync + Send + Copy,
+    F: TransitionProbabilityFn<ST>,
+{
+    fn acceptance_probability(&self, current_score: ST, trial_score: ST) -> f64 {
+        self(current_score, trial_score)
+    }
+}
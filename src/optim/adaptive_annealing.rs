@@ -1,17 +1,24 @@
-use std::{cell::RefCell, f64::consts::PI, num::NonZero, rc::Rc};
+use std::{f64::consts::PI, num::NonZero};
 
-use ordered_float::NotNan;
+use rand::Rng;
 
 use super::{
-    GenericLocalSearchOptimizer, LocalSearchOptimizer,
+    AcceptanceCriterion, AcceptanceState, AdaptiveAnnealingConfig, EnergySample,
+    GenericLocalSearchOptimizer, LocalSearchOptimizer, ScheduleClock, TemperatureEstimator,
+    config::{positive, positive_fraction},
     metropolis::{metropolis_transition, tune_temperature},
 };
 use crate::{
-    Duration, OptModel,
-    callback::{OptCallbackFn, OptProgress},
+    AlgorithmState, Duration, Energy, LocalsearchError, OptControl, OptModel, OptSession,
+    stop_condition::StopCondition,
 };
 
 #[derive(Clone, Copy, Debug, Default)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
 /// Target acceptance rate scheduling mode
 pub enum TargetAccScheduleMode {
     /// Linearly cool from initial_target_acc to final_target_acc
@@ -27,12 +34,19 @@ pub enum TargetAccScheduleMode {
 
 /// Scheduler for adaptive annealing optimizer
 #[derive(Clone, Copy, Debug)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
 pub struct AdaptiveScheduler {
     initial_target_acc: f64,
     final_target_acc: f64,
     schedule_mode: TargetAccScheduleMode,
     // speed parameter for temperature update
     gamma: f64,
+    // measure of the progress of the target acceptance rate schedule
+    clock: ScheduleClock,
 }
 
 impl Default for AdaptiveScheduler {
@@ -42,6 +56,7 @@ impl Default for AdaptiveScheduler {
             final_target_acc: 0.05,
             schedule_mode: TargetAccScheduleMode::Cosine,
             gamma: 0.05,
+            clock: ScheduleClock::Iterations,
         }
     }
 }
@@ -66,14 +81,28 @@ impl AdaptiveScheduler {
             final_target_acc,
             schedule_mode,
             gamma,
+            clock: ScheduleClock::Iterations,
         }
     }
 
-    fn calculate_target_acc(&self, current_iter: usize, total_iter: usize) -> f64 {
+    /// Sets the clock measuring the progress of the target acceptance rate schedule.
+    /// With [`ScheduleClock::ElapsedTime`], the final target is reached at the time limit.
+    pub fn with_clock(mut self, clock: ScheduleClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Error if a parameter is out of its valid range
+    pub(super) fn validate(&self) -> Result<(), LocalsearchError> {
+        positive_fraction("initial_target_acc", self.initial_target_acc)?;
+        positive_fraction("final_target_acc", self.final_target_acc)?;
+        positive("gamma", self.gamma)
+    }
+
+    fn calculate_target_acc(&self, fraction: f64) -> f64 {
         let initial_target_acc = self.initial_target_acc;
         let final_target_acc = self.final_target_acc;
         let schedule_mode = self.schedule_mode;
-        let fraction = current_iter as f64 / total_iter as f64;
         match schedule_mode {
             TargetAccScheduleMode::Linear => {
                 // linearly cool from initial_target_acc to final_target_acc
@@ -92,19 +121,52 @@ impl AdaptiveScheduler {
         }
     }
 
-    pub(crate) fn update_temperature(
+    pub(crate) fn update_temperature<ST>(
         &self,
         current_beta: f64,
-        current_iter: usize,
-        total_iter: usize,
-        acc: f64,
+        state: &AcceptanceState<ST>,
     ) -> f64 {
         // beta = beta * exp(-gamma * (target_acc - acc) / target_acc)
-        let target_acc = self.calculate_target_acc(current_iter, total_iter);
+        let target_acc = self.calculate_target_acc(self.clock.progress(state));
+        let acc = state.acceptance_ratio;
         current_beta * ((-self.gamma * (target_acc - acc) / target_acc).exp())
     }
 }
 
+/// Metropolis criterion whose inverse temperature is adapted by `scheduler`
+/// every `update_frequency` iterations
+#[derive(Clone, Copy)]
+struct AdaptiveCooling {
+    beta: f64,
+    scheduler: AdaptiveScheduler,
+    update_frequency: NonZero<usize>,
+}
+
+impl<ST: Energy> AcceptanceCriterion<ST> for AdaptiveCooling {
+    fn acceptance_probability(&self, current_score: ST, trial_score: ST) -> f64 {
+        metropolis_transition(self.beta)(current_score, trial_score)
+    }
+
+    fn update(&mut self, state: &AcceptanceState<ST>) {
+        if state.iter.is_multiple_of(self.update_frequency.get()) && state.iter > 0 {
+            self.beta = self.scheduler.update_temperature(self.beta, state);
+        }
+    }
+
+    fn algorithm_state(&self) -> AlgorithmState {
+        AlgorithmState {
+            beta: Some(self.beta),
+            ..Default::default()
+        }
+    }
+
+    fn restore(&mut self, state: &AlgorithmState) {
+        if let Some(beta) = state.beta {
+            self.beta = beta;
+        }
+    }
+}
+
 /// Optimizer that implements the adaptive annealing algorithm which tries to adapt temperature
 /// to realize target acceptance rate scheduling
 #[derive(Clone, Copy)]
@@ -124,6 +186,12 @@ pub struct AdaptiveAnnealingOptimizer {
 }
 
 impl AdaptiveAnnealingOptimizer {
+    /// Builder of the optimizer, starting from the default parameters of [`AdaptiveAnnealingConfig`]
+    /// and validating them in [`AdaptiveAnnealingConfig::build`]
+    pub fn builder() -> AdaptiveAnnealingConfig {
+        AdaptiveAnnealingConfig::default()
+    }
+
     /// Creates a new `AdaptiveAnnealingOptimizer` instance with the specified parameters.
     ///
     /// # Arguments
@@ -160,77 +228,92 @@ impl AdaptiveAnnealingOptimizer {
     /// - `model` : the model to optimize
     /// - `initial_solution` : the initial solution to start optimization. If None, a random solution will be generated.
     /// - `n_warmup` : number of warmup iterations to run
-    pub fn tune_initial_temperature<M: OptModel<ScoreType = NotNan<f64>>>(
+    /// - `rng` : random number generator used to draw the warmup trials
+    ///
+    /// Errors of the model other than [`LocalsearchError::NoValidMove`] are returned.
+    pub fn tune_initial_temperature<M: OptModel<ScoreType: Energy>, R: Rng>(
         self,
         model: &M,
         initial_solution: Option<(M::SolutionType, M::ScoreType)>,
         n_warmup: usize,
-    ) -> Self {
+        rng: &mut R,
+    ) -> Result<Self, LocalsearchError> {
         let tuned_beta = tune_temperature(
             model,
             initial_solution,
             n_warmup,
             self.scheduler.initial_target_acc,
-        );
+            rng,
+        )?;
 
-        Self {
+        Ok(Self {
             initial_beta: tuned_beta,
             ..self
-        }
+        })
+    }
+
+    /// Set the initial inverse temperature estimated from `sample` by `estimator`, unchanged if the sample is empty
+    ///
+    /// Returns [`LocalsearchError::InvalidParameter`] if the target acceptance of `estimator` is not in (0, 1).
+    pub fn estimate_initial_temperature(
+        self,
+        sample: &EnergySample,
+        estimator: TemperatureEstimator,
+    ) -> Result<Self, LocalsearchError> {
+        Ok(match estimator.estimate(sample)? {
+            Some(initial_beta) => Self {
+                initial_beta,
+                ..self
+            },
+            None => self,
+        })
     }
 }
 
-impl<M: OptModel<ScoreType = NotNan<f64>>> LocalSearchOptimizer<M> for AdaptiveAnnealingOptimizer {
-    /// Start optimization
+impl<M: OptModel<ScoreType: Energy>> LocalSearchOptimizer<M> for AdaptiveAnnealingOptimizer {
+    /// Start an optimization to be run step by step
     ///
     /// - `model` : the model to optimize
     /// - `initial_solution` : the initial solution to start optimization.
     /// - `initial_score` : the initial score of the initial solution
     /// - `n_iter`: maximum iterations
     /// - `time_limit`: maximum iteration time
-    /// - `callback` : callback function that will be invoked at the end of each iteration
-    fn optimize(
+    /// - `stop_condition`: additional condition to stop the optimization, checked at the end of each iteration
+    /// - `control`: handle to cancel, pause or inject solutions from other threads, if any
+    /// - `rng`: random number generator from which all randomness is drawn
+    fn start_optimization<'a>(
         &self,
-        model: &M,
+        model: &'a M,
         initial_solution: M::SolutionType,
         initial_score: M::ScoreType,
         n_iter: usize,
         time_limit: Duration,
-        callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
-    ) -> (M::SolutionType, M::ScoreType) {
-        let current_beta = Rc::new(RefCell::new(self.initial_beta));
-        let transition = {
-            let current_beta = Rc::clone(&current_beta);
-            move |current: NotNan<f64>, trial: NotNan<f64>| {
-                let beta = *current_beta.borrow();
-                metropolis_transition(beta)(current, trial)
-            }
-        };
-        let mut callback_with_update = |progress: OptProgress<M::SolutionType, M::ScoreType>| {
-            if progress.iter % self.update_frequency.get() == 0 && progress.iter > 0 {
-                let new_beta = self.scheduler.update_temperature(
-                    *current_beta.borrow(),
-                    progress.iter,
-                    n_iter,
-                    progress.acceptance_ratio,
-                );
-                current_beta.replace(new_beta);
-            }
-            callback(progress);
-        };
+        stop_condition: Box<dyn StopCondition<M::ScoreType> + 'a>,
+        control: Option<&'a OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: Box<dyn Rng + 'a>,
+    ) -> Result<OptSession<'a, M>, LocalsearchError>
+    where
+        Self: 'a,
+    {
         let generic_optimizer = GenericLocalSearchOptimizer::new(
             self.patience,
             self.n_trials,
             self.return_iter,
-            transition,
+            AdaptiveCooling {
+                beta: self.initial_beta,
+                scheduler: self.scheduler,
+                update_frequency: self.update_frequency,
+            },
         );
-        generic_optimizer.optimize(
+        generic_optimizer.start_optimization(
             model,
             initial_solution,
             initial_score,
             n_iter,
             time_limit,
-            &mut callback_with_update,
+            stop_condition,
+            control,
+            rng,
         )
     }
 }
@@ -0,0 +1,566 @@
+use std::num::NonZero;
+
+use rand::{Rng, RngExt as _};
+use rayon::prelude::*;
+
+use super::{
+    AmosaConfig, ParetoArchive, Schedule, ScheduleClock, acceptance::elapsed_fraction,
+    config::check, simulated_annealing::tune_cooling_rate,
+};
+use crate::{
+    Duration, Instant, LocalsearchError, ObjectiveVector, OptModel, StopReason,
+    counter::AcceptanceCounter,
+    utils::{catch_panic, split_rng},
+};
+
+/// Progress of a multi-objective optimization, passed to the callback at the end of each iteration
+pub struct ParetoProgress<'a, S, SC> {
+    /// current iteration step
+    pub iter: usize,
+    /// current inverse temperature
+    pub beta: f64,
+    /// acceptance ratio over the recent iterations
+    pub acceptance_ratio: f64,
+    /// score of the current solution
+    pub current_score: SC,
+    /// archive of the non-dominated solutions found so far
+    pub archive: &'a ParetoArchive<S, SC>,
+    /// elapsed time of the optimization
+    pub elapsed: Duration,
+    /// number of trial solutions evaluated
+    pub n_evaluations: usize,
+}
+
+/// Callback function of a multi-objective optimization, called with its [`ParetoProgress`]
+pub trait ParetoCallbackFn<S, SC>: for<'a> FnMut(ParetoProgress<'a, S, SC>) {}
+
+impl<F, S, SC> ParetoCallbackFn<S, SC> for F where F: for<'a> FnMut(ParetoProgress<'a, S, SC>) {}
+
+/// Outcome of a multi-objective optimization: the Pareto front together with statistics of the run
+#[derive(Debug, Clone)]
+pub struct ParetoResult<S, SC> {
+    /// mutually non-dominated `(solution, score)` pairs, sorted by score
+    pub front: Vec<(S, SC)>,
+    /// number of iterations done
+    pub n_iterations: usize,
+    /// number of trial solutions evaluated
+    pub n_evaluations: usize,
+    /// elapsed time of the optimization
+    pub elapsed: Duration,
+    /// acceptance ratio at the end of the optimization
+    pub acceptance_ratio: f64,
+    /// reason why the optimization stopped
+    pub stop_reason: StopReason,
+}
+
+/// Archived multi-objective simulated annealing (AMOSA, Bandyopadhyay et al. 2008)
+/// for models whose score is an [`ObjectiveVector`].
+///
+/// The optimizer keeps a [`ParetoArchive`] of the non-dominated solutions found so far.
+/// A trial solution is accepted according to the amount by which it is dominated by the current solution
+/// and the archive, with a probability decreasing as the inverse temperature rises.
+/// Non-dominated trial solutions always enter the archive, which is pruned by crowding distance.
+#[derive(Debug, Clone)]
+pub struct AmosaOptimizer {
+    /// Size of the archive after pruning
+    hard_limit: usize,
+    /// Size above which the archive is pruned
+    soft_limit: usize,
+    /// Number of random solutions seeding the archive
+    n_initial: usize,
+    /// Initial inverse temperature
+    initial_beta: f64,
+    /// Cooling rate
+    cooling_rate: f64,
+    /// Schedule of the inverse temperature replacing the cooling rate, if any
+    beta_schedule: Option<Schedule>,
+    /// Non-zero number of steps after which inverse temperature is updated
+    update_frequency: NonZero<usize>,
+}
+
+impl AmosaOptimizer {
+    /// Builder of the optimizer, starting from the default parameters of [`AmosaConfig`]
+    /// and validating them in [`AmosaConfig::build`]
+    pub fn builder() -> AmosaConfig {
+        AmosaConfig::default()
+    }
+
+    /// Constructor of AmosaOptimizer
+    ///
+    /// - `hard_limit` : size of the archive after pruning
+    /// - `soft_limit` : size above which the archive is pruned, at least `hard_limit`
+    /// - `initial_beta` : initial inverse temperature
+    /// - `cooling_rate` : cooling rate
+    /// - `update_frequency` : non-zero number of steps after which inverse temperature (beta) is updated
+    pub fn new(
+        hard_limit: usize,
+        soft_limit: usize,
+        initial_beta: f64,
+        cooling_rate: f64,
+        update_frequency: NonZero<usize>,
+    ) -> Self {
+        Self {
+            hard_limit,
+            soft_limit,
+            n_initial: soft_limit.max(1),
+            initial_beta,
+            cooling_rate,
+            beta_schedule: None,
+            update_frequency,
+        }
+    }
+
+    /// Seed the archive with `n_initial` random solutions in [`Self::run`], `soft_limit` by default
+    pub fn with_n_initial(mut self, n_initial: usize) -> Self {
+        self.n_initial = n_initial.max(1);
+        self
+    }
+
+    /// Set the inverse temperature from `schedule` every `update_frequency` steps, instead of multiplying it by the cooling rate
+    pub fn with_beta_schedule(mut self, schedule: Schedule) -> Self {
+        self.beta_schedule = Some(schedule);
+        self
+    }
+
+    /// Tune the cooling rate so that beta reaches 1e2 at the end of `n_iter` iterations
+    pub fn tune_cooling_rate(mut self, n_iter: usize) -> Self {
+        let n_updates = n_iter / self.update_frequency.get();
+        self.cooling_rate = tune_cooling_rate(self.initial_beta, 1e2, n_updates);
+        self
+    }
+
+    /// Cool beta geometrically to 1e2 following `clock`, e.g. over the time limit
+    pub fn tune_cooling_schedule(self, clock: ScheduleClock) -> Self {
+        let schedule = Schedule::geometric(self.initial_beta, 1e2).with_clock(clock);
+        self.with_beta_schedule(schedule)
+    }
+
+    /// Start optimization from `n_initial` random solutions
+    ///
+    /// - `model` : the model to optimize
+    /// - `n_iter`: maximum iterations
+    /// - `time_limit`: maximum iteration time
+    pub fn run<M: OptModel<ScoreType: ObjectiveVector>>(
+        &self,
+        model: &M,
+        n_iter: usize,
+        time_limit: Duration,
+    ) -> Result<ParetoResult<M::SolutionType, M::ScoreType>, LocalsearchError> {
+        self.run_with_rng(
+            model,
+            None,
+            n_iter,
+            time_limit,
+            &mut rand::rng(),
+            &mut |_| {},
+        )
+    }
+
+    /// Start optimization with a given random number generator and progress callback
+    ///
+    /// - `model` : the model to optimize
+    /// - `initial_solutions` : solutions seeding the archive, `n_initial` random solutions if `None`
+    /// - `n_iter`: maximum iterations
+    /// - `time_limit`: maximum iteration time
+    /// - `rng`: random number generator from which all randomness is drawn
+    /// - `callback` : callback function called at the end of each iteration with the current archive
+    pub fn run_with_rng<M: OptModel<ScoreType: ObjectiveVector>>(
+        &self,
+        model: &M,
+        initial_solutions: Option<Vec<(M::SolutionType, M::ScoreType)>>,
+        n_iter: usize,
+        time_limit: Duration,
+        rng: &mut dyn Rng,
+        callback: &mut dyn ParetoCallbackFn<M::SolutionType, M::ScoreType>,
+    ) -> Result<ParetoResult<M::SolutionType, M::ScoreType>, LocalsearchError> {
+        let mut session =
+            self.start_with_rng(model, initial_solutions, n_iter, time_limit, Box::new(rng))?;
+        session.step_with_callback(usize::MAX, callback)?;
+        session.finish()
+    }
+
+    /// Start an optimization to be run step by step, see [`AmosaSession`]
+    ///
+    /// - `model` : the model to optimize
+    /// - `initial_solutions` : solutions seeding the archive, `n_initial` random solutions if `None`
+    /// - `n_iter`: maximum iterations
+    /// - `time_limit`: maximum iteration time
+    /// - `rng`: random number generator from which all randomness is drawn
+    pub fn start_with_rng<'a, M: OptModel<ScoreType: ObjectiveVector>>(
+        &self,
+        model: &'a M,
+        initial_solutions: Option<Vec<(M::SolutionType, M::ScoreType)>>,
+        n_iter: usize,
+        time_limit: Duration,
+        mut rng: Box<dyn Rng + 'a>,
+    ) -> Result<AmosaSession<'a, M>, LocalsearchError> {
+        let initial_solutions = match initial_solutions {
+            Some(solutions) => {
+                non_empty(&solutions)?;
+                solutions
+            }
+            None => split_rng(&mut rng, self.n_initial)
+                .into_par_iter()
+                .map(|mut rng| catch_panic(|| model.generate_random_solution(&mut rng)))
+                .collect::<Result<Vec<_>, _>>()?,
+        };
+        let initial_solutions = initial_solutions
+            .into_iter()
+            .map(|(solution, score)| model.preprocess_solution(solution, score))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        self.start_optimization(model, initial_solutions, n_iter, time_limit, rng)
+    }
+
+    /// Start optimization
+    ///
+    /// - `model` : the model to optimize
+    /// - `initial_solutions` : non-empty solutions seeding the archive, the current solution is drawn among them
+    /// - `n_iter`: maximum iterations
+    /// - `time_limit`: maximum iteration time
+    /// - `rng`: random number generator from which all randomness is drawn
+    /// - `callback` : callback function called at the end of each iteration with the current archive
+    ///
+    /// An empty `initial_solutions` returns [`LocalsearchError::InvalidParameter`].
+    pub fn optimize<M: OptModel<ScoreType: ObjectiveVector>>(
+        &self,
+        model: &M,
+        initial_solutions: Vec<(M::SolutionType, M::ScoreType)>,
+        n_iter: usize,
+        time_limit: Duration,
+        rng: &mut dyn Rng,
+        callback: &mut dyn ParetoCallbackFn<M::SolutionType, M::ScoreType>,
+    ) -> Result<ParetoResult<M::SolutionType, M::ScoreType>, LocalsearchError> {
+        let mut session =
+            self.start_optimization(model, initial_solutions, n_iter, time_limit, Box::new(rng))?;
+        session.step_with_callback(usize::MAX, callback)?;
+        Ok(session.into_result())
+    }
+
+    /// Start an optimization to be run step by step, with the same arguments as [`Self::optimize`].
+    /// An empty `initial_solutions` returns [`LocalsearchError::InvalidParameter`].
+    pub fn start_optimization<'a, M: OptModel<ScoreType: ObjectiveVector>>(
+        &self,
+        model: &'a M,
+        initial_solutions: Vec<(M::SolutionType, M::ScoreType)>,
+        n_iter: usize,
+        time_limit: Duration,
+        mut rng: Box<dyn Rng + 'a>,
+    ) -> Result<AmosaSession<'a, M>, LocalsearchError> {
+        non_empty(&initial_solutions)?;
+        let mut archive = ParetoArchive::new(self.hard_limit, self.soft_limit);
+        for (solution, score) in initial_solutions {
+            archive.insert(solution, score);
+        }
+        let (current_solution, current_score) =
+            archive.members()[rng.random_range(0..archive.len())].clone();
+        Ok(AmosaSession {
+            cooling_rate: self.cooling_rate,
+            beta_schedule: self.beta_schedule.clone(),
+            update_frequency: self.update_frequency,
+            model,
+            n_iter,
+            time_limit,
+            rng,
+            start_time: Instant::now(),
+            archive,
+            current_solution,
+            current_score,
+            beta: self.initial_beta,
+            acceptance_counter: AcceptanceCounter::new(100),
+            n_evaluations: 0,
+            iter: 0,
+            stop_reason: None,
+        })
+    }
+}
+
+/// Error unless there is an initial solution to draw the current one from
+fn non_empty<T>(initial_solutions: &[T]) -> Result<(), LocalsearchError> {
+    check(
+        !initial_solutions.is_empty(),
+        "initial_solutions",
+        "non-empty",
+    )
+}
+
+/// AmosaSession is a resumable multi-objective optimization, run a few iterations at a time,
+/// the counterpart of [`OptSession`](crate::OptSession) for [`AmosaOptimizer`].
+///
+/// It is returned by [`AmosaOptimizer::start_with_rng`] and [`AmosaOptimizer::start_optimization`].
+/// Between steps, the archive and the current solution can be read,
+/// and the current solution replaced with [`AmosaSession::inject`].
+/// Time between steps counts toward `time_limit`.
+pub struct AmosaSession<'a, M: OptModel> {
+    cooling_rate: f64,
+    beta_schedule: Option<Schedule>,
+    update_frequency: NonZero<usize>,
+    model: &'a M,
+    n_iter: usize,
+    time_limit: Duration,
+    rng: Box<dyn Rng + 'a>,
+    start_time: Instant,
+    archive: ParetoArchive<M::SolutionType, M::ScoreType>,
+    current_solution: M::SolutionType,
+    current_score: M::ScoreType,
+    beta: f64,
+    acceptance_counter: AcceptanceCounter,
+    n_evaluations: usize,
+    iter: usize,
+    stop_reason: Option<StopReason>,
+}
+
+impl<M: OptModel<ScoreType: ObjectiveVector>> AmosaSession<'_, M> {
+    /// Run up to `n_steps` iterations, and return the reason why the optimization stopped, if it did.
+    /// An error of the model ends the optimization with [`StopReason::Failed`]: later steps return it without iterating.
+    pub fn step(&mut self, n_steps: usize) -> Result<Option<StopReason>, LocalsearchError> {
+        self.step_with_callback(n_steps, &mut |_| {})
+    }
+
+    /// Same as [`AmosaSession::step`], calling `callback` at the end of each iteration with the current archive
+    pub fn step_with_callback(
+        &mut self,
+        n_steps: usize,
+        callback: &mut dyn ParetoCallbackFn<M::SolutionType, M::ScoreType>,
+    ) -> Result<Option<StopReason>, LocalsearchError> {
+        for _ in 0..n_steps {
+            if self.stop_reason.is_some() {
+                break;
+            }
+            match self.iterate(callback) {
+                Ok(stop_reason) => self.stop_reason = stop_reason,
+                Err(e) => {
+                    self.stop_reason = Some(StopReason::Failed);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(self.stop_reason)
+    }
+
+    /// Run one iteration, or return the reason why the optimization stops
+    fn iterate(
+        &mut self,
+        callback: &mut dyn ParetoCallbackFn<M::SolutionType, M::ScoreType>,
+    ) -> Result<Option<StopReason>, LocalsearchError> {
+        if self.iter >= self.n_iter {
+            return Ok(Some(StopReason::MaxIterations));
+        }
+        let elapsed = Instant::now().duration_since(self.start_time);
+        if elapsed > self.time_limit {
+            return Ok(Some(StopReason::TimeLimit));
+        }
+
+        let accepted = match catch_panic(|| {
+            self.model.generate_trial_solution(
+                self.current_solution.clone(),
+                self.current_score,
+                &mut self.rng,
+            )
+        }) {
+            Ok((trial_solution, _, trial_score)) => transition(
+                &mut self.archive,
+                &mut self.current_solution,
+                &mut self.current_score,
+                trial_solution,
+                trial_score,
+                self.beta,
+                &mut self.rng,
+            ),
+            Err(LocalsearchError::NoValidMove) => false,
+            Err(e) => return Err(e),
+        };
+        self.n_evaluations += 1;
+        self.acceptance_counter.enqueue(accepted);
+
+        self.iter += 1;
+        if self.iter.is_multiple_of(self.update_frequency.get()) {
+            self.beta = match &self.beta_schedule {
+                Some(schedule) => {
+                    let elapsed_fraction = elapsed_fraction(
+                        Instant::now().duration_since(self.start_time),
+                        self.time_limit,
+                    );
+                    schedule.value(schedule.clock().progress_of(
+                        self.iter,
+                        self.n_iter,
+                        elapsed_fraction,
+                    ))
+                }
+                None => self.beta * self.cooling_rate,
+            };
+        }
+
+        callback(ParetoProgress {
+            iter: self.iter,
+            beta: self.beta,
+            acceptance_ratio: self.acceptance_counter.acceptance_ratio(),
+            current_score: self.current_score,
+            archive: &self.archive,
+            elapsed: Instant::now().duration_since(self.start_time),
+            n_evaluations: self.n_evaluations,
+        });
+        Ok(None)
+    }
+
+    /// Reason why the optimization stopped, `None` while it is running
+    pub fn stop_reason(&self) -> Option<StopReason> {
+        self.stop_reason
+    }
+
+    /// Whether the optimization stopped
+    pub fn is_finished(&self) -> bool {
+        self.stop_reason.is_some()
+    }
+
+    /// Number of iterations done
+    pub fn n_iterations(&self) -> usize {
+        self.iter
+    }
+
+    /// Current inverse temperature
+    pub fn beta(&self) -> f64 {
+        self.beta
+    }
+
+    /// Archive of the non-dominated solutions found so far, in the preprocessed representation of the model
+    pub fn archive(&self) -> &ParetoArchive<M::SolutionType, M::ScoreType> {
+        &self.archive
+    }
+
+    /// Current solution and its score
+    pub fn current(&self) -> (&M::SolutionType, M::ScoreType) {
+        (&self.current_solution, self.current_score)
+    }
+
+    /// Replace the current solution, which also enters the archive unless a member covers it.
+    /// The solution is in the preprocessed representation of the model.
+    pub fn inject(&mut self, solution: M::SolutionType, score: M::ScoreType) {
+        self.archive.insert(solution.clone(), score);
+        self.current_solution = solution;
+        self.current_score = score;
+    }
+
+    /// Result holding the Pareto front, in the preprocessed representation of the model.
+    /// An optimization that is still running ends as [`StopReason::Cancelled`].
+    pub fn into_result(self) -> ParetoResult<M::SolutionType, M::ScoreType> {
+        let mut front = self.archive.into_members();
+        front.sort_by_key(|(_, score)| *score);
+        ParetoResult {
+            front,
+            n_iterations: self.iter,
+            n_evaluations: self.n_evaluations,
+            elapsed: Instant::now().duration_since(self.start_time),
+            acceptance_ratio: self.acceptance_counter.acceptance_ratio(),
+            stop_reason: self.stop_reason.unwrap_or(StopReason::Cancelled),
+        }
+    }
+
+    /// Same as [`AmosaSession::into_result`], with the front postprocessed by the model,
+    /// to end a session returned by [`AmosaOptimizer::start_with_rng`]
+    pub fn finish(self) -> Result<ParetoResult<M::SolutionType, M::ScoreType>, LocalsearchError> {
+        let model = self.model;
+        let mut result = self.into_result();
+        result.front = result
+            .front
+            .into_iter()
+            .map(|(solution, score)| model.postprocess_solution(solution, score))
+            .collect::<Result<_, _>>()?;
+        Ok(result)
+    }
+}
+
+/// Move from the current solution to the trial solution by the rules of AMOSA, updating the archive.
+/// Returns whether the trial was accepted.
+fn transition<S: Clone, SC: ObjectiveVector>(
+    archive: &mut ParetoArchive<S, SC>,
+    current_solution: &mut S,
+    current_score: &mut SC,
+    trial_solution: S,
+    trial_score: SC,
+    beta: f64,
+    rng: &mut dyn Rng,
+) -> bool {
+    let ranges = objective_ranges(archive, &[*current_score, trial_score]);
+    let dominating: Vec<(usize, f64)> = archive
+        .members()
+        .iter()
+        .enumerate()
+        .filter(|(_, (_, score))| score.dominates(&trial_score))
+        .map(|(i, (_, score))| (i, domination_amount(score, &trial_score, &ranges)))
+        .collect();
+
+    let accepted = if current_score.dominates(&trial_score) {
+        // the trial is worse than the current solution: accept it with a probability
+        // decreasing with its average domination by the current solution and the archive
+        let total = domination_amount(current_score, &trial_score, &ranges)
+            + dominating.iter().map(|(_, amount)| amount).sum::<f64>();
+        let average = total / (dominating.len() + 1) as f64;
+        rng.random::<f64>() < acceptance_probability(beta, average)
+    } else if let Some(&(closest, min_amount)) =
+        dominating.iter().min_by(|(_, a), (_, b)| a.total_cmp(b))
+    {
+        if trial_score.dominates(current_score) {
+            // the trial improves the current solution but is dominated by the archive:
+            // move to the closest dominating member or to the trial
+            if rng.random::<f64>() < 1.0 / (1.0 + (-min_amount).exp()) {
+                (*current_solution, *current_score) = archive.members()[closest].clone();
+                false
+            } else {
+                true
+            }
+        } else {
+            let average =
+                dominating.iter().map(|(_, amount)| amount).sum::<f64>() / dominating.len() as f64;
+            rng.random::<f64>() < acceptance_probability(beta, average)
+        }
+    } else {
+        // no archive member dominates the trial
+        archive.insert(trial_solution.clone(), trial_score);
+        true
+    };
+    if accepted {
+        *current_solution = trial_solution;
+        *current_score = trial_score;
+    }
+    accepted
+}
+
+/// Probability to move to a solution dominated by `amount` at inverse temperature `beta`
+fn acceptance_probability(beta: f64, amount: f64) -> f64 {
+    1.0 / (1.0 + (beta * amount).exp())
+}
+
+/// Range of each objective over the archive and `others`, 1 where it is empty
+fn objective_ranges<S, SC: ObjectiveVector>(
+    archive: &ParetoArchive<S, SC>,
+    others: &[SC],
+) -> Vec<f64> {
+    let scores = || {
+        archive
+            .members()
+            .iter()
+            .map(|(_, score)| score)
+            .chain(others)
+    };
+    (0..others[0].n_objectives())
+        .map(|k| {
+            let (low, high) = scores()
+                .fold((f64::INFINITY, f64::NEG_INFINITY), |(low, high), s| {
+                    (low.min(s.objective(k)), high.max(s.objective(k)))
+                });
+            if high > low { high - low } else { 1.0 }
+        })
+        .collect()
+}
+
+/// Amount of domination between two scores:
+/// the product of their normalized differences over the objectives where they differ
+fn domination_amount<SC: ObjectiveVector>(a: &SC, b: &SC, ranges: &[f64]) -> f64 {
+    ranges
+        .iter()
+        .enumerate()
+        .map(|(k, range)| (a.objective(k) - b.objective(k)).abs() / range)
+        .filter(|diff| *diff > 0.0)
+        .product()
+}
@@ -1,11 +1,94 @@
 use auto_impl::auto_impl;
+use rand::Rng;
 
-use crate::{Duration, LocalsearchError, OptModel, callback::OptCallbackFn};
+use crate::{
+    Checkpoint, Duration, LocalsearchError, OptControl, OptModel, OptObserver, OptResult,
+    OptSession,
+    callback::OptCallbackFn,
+    stop_condition::{Never, StopCondition},
+};
 
 /// Optimizer that implements local search algorithm
 #[auto_impl(&, Box, Rc, Arc)]
 pub trait LocalSearchOptimizer<M: OptModel> {
-    /// Start optimization
+    /// Start an optimization to be run step by step, see [`OptSession`].
+    /// The session owns everything it needs from the optimizer, and borrows the model, `control` and `rng`
+    /// for as long as it lives.
+    /// Solutions are in the preprocessed representation of the model, as in [`LocalSearchOptimizer::optimize`].
+    #[allow(clippy::too_many_arguments)]
+    fn start_optimization<'a>(
+        &self,
+        model: &'a M,
+        initial_solution: M::SolutionType,
+        initial_score: M::ScoreType,
+        n_iter: usize,
+        time_limit: Duration,
+        stop_condition: Box<dyn StopCondition<M::ScoreType> + 'a>,
+        control: Option<&'a OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: Box<dyn Rng + 'a>,
+    ) -> Result<OptSession<'a, M>, LocalsearchError>
+    where
+        Self: 'a;
+
+    /// Start optimization, reporting events to `observer`,
+    /// and return the best solution together with statistics of the run.
+    /// Errors of the model, including panics of model code during trial generation, stop the optimization.
+    #[allow(clippy::too_many_arguments)]
+    fn optimize_with_observer(
+        &self,
+        model: &M,
+        initial_solution: M::SolutionType,
+        initial_score: M::ScoreType,
+        n_iter: usize,
+        time_limit: Duration,
+        stop_condition: &mut dyn StopCondition<M::ScoreType>,
+        control: Option<&OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: &mut dyn Rng,
+        observer: &mut dyn OptObserver<M::SolutionType, M::ScoreType, M::TransitionType>,
+    ) -> Result<OptResult<M::SolutionType, M::ScoreType>, LocalsearchError> {
+        let mut session = self.start_optimization(
+            model,
+            initial_solution,
+            initial_score,
+            n_iter,
+            time_limit,
+            Box::new(stop_condition),
+            control,
+            Box::new(rng),
+        )?;
+        session.step_with_observer(usize::MAX, observer)?;
+        Ok(session.into_result())
+    }
+
+    /// Start optimization and return the best solution together with statistics of the run
+    #[allow(clippy::too_many_arguments)]
+    fn optimize_with_result(
+        &self,
+        model: &M,
+        initial_solution: M::SolutionType,
+        initial_score: M::ScoreType,
+        n_iter: usize,
+        time_limit: Duration,
+        stop_condition: &mut dyn StopCondition<M::ScoreType>,
+        control: Option<&OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: &mut dyn Rng,
+        mut callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
+    ) -> Result<OptResult<M::SolutionType, M::ScoreType>, LocalsearchError> {
+        self.optimize_with_observer(
+            model,
+            initial_solution,
+            initial_score,
+            n_iter,
+            time_limit,
+            stop_condition,
+            control,
+            rng,
+            &mut callback,
+        )
+    }
+
+    /// Start optimization and return only the best solution and its score
+    #[allow(clippy::too_many_arguments)]
     fn optimize(
         &self,
         model: &M,
@@ -13,8 +96,24 @@ pub trait LocalSearchOptimizer<M: OptModel> {
         initial_score: M::ScoreType,
         n_iter: usize,
         time_limit: Duration,
+        stop_condition: &mut dyn StopCondition<M::ScoreType>,
+        control: Option<&OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: &mut dyn Rng,
         callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
-    ) -> (M::SolutionType, M::ScoreType);
+    ) -> Result<(M::SolutionType, M::ScoreType), LocalsearchError> {
+        self.optimize_with_result(
+            model,
+            initial_solution,
+            initial_score,
+            n_iter,
+            time_limit,
+            stop_condition,
+            control,
+            rng,
+            callback,
+        )
+        .map(OptResult::into_solution_and_score)
+    }
 
     /// generate initial solution if not given and run optimization
     fn run(
@@ -23,7 +122,7 @@ pub trait LocalSearchOptimizer<M: OptModel> {
         initial_solution_and_score: Option<(M::SolutionType, M::ScoreType)>,
         n_iter: usize,
         time_limit: Duration,
-    ) -> Result<(M::SolutionType, M::ScoreType), LocalsearchError> {
+    ) -> Result<OptResult<M::SolutionType, M::ScoreType>, LocalsearchError> {
         self.run_with_callback(
             model,
             initial_solution_and_score,
@@ -41,29 +140,237 @@ pub trait LocalSearchOptimizer<M: OptModel> {
         n_iter: usize,
         time_limit: Duration,
         callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
-    ) -> Result<(M::SolutionType, M::ScoreType), LocalsearchError> {
+    ) -> Result<OptResult<M::SolutionType, M::ScoreType>, LocalsearchError> {
+        self.run_with_rng(
+            model,
+            initial_solution_and_score,
+            n_iter,
+            time_limit,
+            &mut rand::rng(),
+            callback,
+        )
+    }
+
+    /// generate initial solution if not given and run optimization with callback,
+    /// drawing every random number from `rng`.
+    /// Passing a seeded generator (e.g. `StdRng::seed_from_u64`) makes the run reproducible.
+    fn run_with_rng(
+        &self,
+        model: &M,
+        initial_solution_and_score: Option<(M::SolutionType, M::ScoreType)>,
+        n_iter: usize,
+        time_limit: Duration,
+        rng: &mut dyn Rng,
+        callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
+    ) -> Result<OptResult<M::SolutionType, M::ScoreType>, LocalsearchError> {
+        self.run_with_stop_condition(
+            model,
+            initial_solution_and_score,
+            n_iter,
+            time_limit,
+            &mut Never,
+            rng,
+            callback,
+        )
+    }
+
+    /// generate initial solution if not given and run optimization with callback,
+    /// stopping early when `stop_condition` is met
+    #[allow(clippy::too_many_arguments)]
+    fn run_with_stop_condition(
+        &self,
+        model: &M,
+        initial_solution_and_score: Option<(M::SolutionType, M::ScoreType)>,
+        n_iter: usize,
+        time_limit: Duration,
+        stop_condition: &mut dyn StopCondition<M::ScoreType>,
+        rng: &mut dyn Rng,
+        callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
+    ) -> Result<OptResult<M::SolutionType, M::ScoreType>, LocalsearchError> {
+        self.run_with_control(
+            model,
+            initial_solution_and_score,
+            n_iter,
+            time_limit,
+            stop_condition,
+            None,
+            rng,
+            callback,
+        )
+    }
+
+    /// generate initial solution if not given and run optimization with callback,
+    /// steered by `control` from other threads (cancel, pause / resume, inject a solution).
+    /// Solutions exchanged through `control` are in the preprocessed representation of the model.
+    #[allow(clippy::too_many_arguments)]
+    fn run_with_control(
+        &self,
+        model: &M,
+        initial_solution_and_score: Option<(M::SolutionType, M::ScoreType)>,
+        n_iter: usize,
+        time_limit: Duration,
+        stop_condition: &mut dyn StopCondition<M::ScoreType>,
+        control: Option<&OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: &mut dyn Rng,
+        mut callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
+    ) -> Result<OptResult<M::SolutionType, M::ScoreType>, LocalsearchError> {
+        self.run_with_observer(
+            model,
+            initial_solution_and_score,
+            n_iter,
+            time_limit,
+            stop_condition,
+            control,
+            rng,
+            &mut callback,
+        )
+    }
+
+    /// generate initial solution if not given and run optimization,
+    /// reporting events (improvement, return to best, temperature update, ...) to `observer`.
+    /// Any [`OptCallbackFn`] is also an [`OptObserver`].
+    #[allow(clippy::too_many_arguments)]
+    fn run_with_observer(
+        &self,
+        model: &M,
+        initial_solution_and_score: Option<(M::SolutionType, M::ScoreType)>,
+        n_iter: usize,
+        time_limit: Duration,
+        stop_condition: &mut dyn StopCondition<M::ScoreType>,
+        control: Option<&OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        mut rng: &mut dyn Rng,
+        observer: &mut dyn OptObserver<M::SolutionType, M::ScoreType, M::TransitionType>,
+    ) -> Result<OptResult<M::SolutionType, M::ScoreType>, LocalsearchError> {
         let (initial_solution, initial_score) = match initial_solution_and_score {
             Some((solution, score)) => (solution, score),
-            None => {
-                let mut rng = rand::rng();
-                model.generate_random_solution(&mut rng)?
-            }
+            None => model.generate_random_solution(&mut rng)?,
         };
 
         let (initial_solution, initial_score) =
             model.preprocess_solution(initial_solution, initial_score)?;
 
-        let (solution, score) = self.optimize(
+        let result = self.optimize_with_observer(
+            model,
+            initial_solution,
+            initial_score,
+            n_iter,
+            time_limit,
+            stop_condition,
+            control,
+            rng,
+            observer,
+        )?;
+
+        let (solution, score) = model.postprocess_solution(result.solution, result.score)?;
+        Ok(OptResult {
+            solution,
+            score,
+            ..result
+        })
+    }
+
+    /// generate initial solution if not given and start an optimization to be run step by step,
+    /// ended with [`OptSession::finish`]
+    fn start<'a>(
+        &self,
+        model: &'a M,
+        initial_solution_and_score: Option<(M::SolutionType, M::ScoreType)>,
+        n_iter: usize,
+        time_limit: Duration,
+    ) -> Result<OptSession<'a, M>, LocalsearchError>
+    where
+        Self: 'a,
+    {
+        self.start_with_rng(
+            model,
+            initial_solution_and_score,
+            n_iter,
+            time_limit,
+            Box::new(rand::rng()),
+        )
+    }
+
+    /// generate initial solution if not given and start an optimization to be run step by step,
+    /// drawing every random number from `rng`
+    fn start_with_rng<'a>(
+        &self,
+        model: &'a M,
+        initial_solution_and_score: Option<(M::SolutionType, M::ScoreType)>,
+        n_iter: usize,
+        time_limit: Duration,
+        mut rng: Box<dyn Rng + 'a>,
+    ) -> Result<OptSession<'a, M>, LocalsearchError>
+    where
+        Self: 'a,
+    {
+        let (initial_solution, initial_score) = match initial_solution_and_score {
+            Some((solution, score)) => (solution, score),
+            None => model.generate_random_solution(&mut rng)?,
+        };
+
+        let (initial_solution, initial_score) =
+            model.preprocess_solution(initial_solution, initial_score)?;
+
+        self.start_optimization(
+            model,
+            initial_solution,
+            initial_score,
+            n_iter,
+            time_limit,
+            Box::new(Never),
+            None,
+            rng,
+        )
+    }
+
+    /// continue an optimization from a [`Checkpoint`] taken with [`OptControl::request_checkpoint`].
+    /// The continued run is identical to an uninterrupted one when `rng` is in its state at the checkpoint
+    /// and `stop_condition` is stateless, see [`Checkpoint`].
+    /// `n_iter` and `time_limit` are the limits of the whole optimization, including the part before the checkpoint.
+    /// `control`, if any, keeps steering the continued run.
+    #[allow(clippy::too_many_arguments)]
+    fn resume(
+        &self,
+        model: &M,
+        checkpoint: Checkpoint<M::SolutionType, M::ScoreType, M::TransitionType>,
+        n_iter: usize,
+        time_limit: Duration,
+        stop_condition: &mut dyn StopCondition<M::ScoreType>,
+        control: Option<&OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: &mut dyn Rng,
+        callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
+    ) -> Result<OptResult<M::SolutionType, M::ScoreType>, LocalsearchError> {
+        let owned_control;
+        let control = match control {
+            Some(control) => control,
+            None => {
+                owned_control = OptControl::new();
+                &owned_control
+            }
+        };
+        let initial_solution = checkpoint.best_solution.clone();
+        let initial_score = checkpoint.best_score;
+        control.restore(checkpoint);
+
+        // the checkpoint already holds preprocessed solutions
+        let result = self.optimize_with_result(
             model,
             initial_solution,
             initial_score,
             n_iter,
             time_limit,
+            stop_condition,
+            Some(control),
+            rng,
             callback,
-        );
+        )?;
 
-        let (solution, score) = model.postprocess_solution(solution, score);
-        Ok((solution, score))
+        let (solution, score) = model.postprocess_solution(result.solution, result.score)?;
+        Ok(OptResult {
+            solution,
+            score,
+            ..result
+        })
     }
 }
 
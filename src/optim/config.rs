@@ -0,0 +1,1303 @@
+use std::num::NonZero;
+
+use super::{
+    AcceptanceCriterion, AdaptiveAnnealingOptimizer, AdaptiveScheduler, AmosaOptimizer,
+    EpsilonGreedyOptimizer, GenericLocalSearchOptimizer, GreatDelugeOptimizer,
+    HillClimbingOptimizer, LexicographicAnnealingOptimizer, LocalSearchOptimizer,
+    LogisticAnnealingOptimizer, MetropolisOptimizer, ParallelTemperingOptimizer,
+    PopulationAnnealingOptimizer, RandomSearchOptimizer, ReheatPolicy, RelativeAnnealingOptimizer,
+    Schedule, ScheduleClock, SimulatedAnnealingOptimizer, TabuList, TabuSearchOptimizer,
+    TsallisRelativeAnnealingOptimizer,
+};
+use crate::{Energy, LocalsearchError, OptModel};
+
+const DEFAULT_PATIENCE: usize = 1000;
+const DEFAULT_N_TRIALS: usize = 16;
+const DEFAULT_RETURN_ITER: usize = 100;
+
+fn default_update_frequency() -> NonZero<usize> {
+    NonZero::new(100).expect("update_frequency must be >= 1")
+}
+
+/// Named setters of the fields of a configuration, wrapping the value with `=> Some` for optional ones
+macro_rules! setters {
+    ($($setter:ident($field:ident: $ty:ty) $(=> $wrap:ident)?;)*) => {
+        $(
+            #[doc = concat!("Set `", stringify!($field), "`")]
+            pub fn $setter(mut self, $field: $ty) -> Self {
+                self.$field = $($wrap)?($field);
+                self
+            }
+        )*
+    };
+}
+
+/// Conversion of a configuration into its optimizer with `build`
+macro_rules! impl_try_from {
+    ($config:ty => $optimizer:ty) => {
+        impl TryFrom<$config> for $optimizer {
+            type Error = LocalsearchError;
+
+            fn try_from(config: $config) -> Result<Self, Self::Error> {
+                config.build()
+            }
+        }
+    };
+}
+
+/// Error for the parameter `name` unless `valid`
+pub(super) fn check(
+    valid: bool,
+    name: &'static str,
+    expected: &'static str,
+) -> Result<(), LocalsearchError> {
+    if valid {
+        Ok(())
+    } else {
+        Err(LocalsearchError::InvalidParameter { name, expected })
+    }
+}
+
+/// Error if `value` is 0
+pub(super) fn at_least_one(name: &'static str, value: usize) -> Result<(), LocalsearchError> {
+    check(value >= 1, name, "at least 1")
+}
+
+/// Error unless `value` is finite and positive
+pub(super) fn positive(name: &'static str, value: f64) -> Result<(), LocalsearchError> {
+    check(
+        value.is_finite() && value > 0.0,
+        name,
+        "finite and positive",
+    )
+}
+
+/// Error unless `value` is finite and non-negative
+pub(super) fn non_negative(name: &'static str, value: f64) -> Result<(), LocalsearchError> {
+    check(
+        value.is_finite() && value >= 0.0,
+        name,
+        "finite and non-negative",
+    )
+}
+
+/// Error unless `value` is a probability
+pub(super) fn probability(name: &'static str, value: f64) -> Result<(), LocalsearchError> {
+    check((0.0..=1.0).contains(&value), name, "between 0 and 1")
+}
+
+/// Error unless `value` is a positive fraction, in (0, 1]
+pub(super) fn positive_fraction(name: &'static str, value: f64) -> Result<(), LocalsearchError> {
+    check(value > 0.0 && value <= 1.0, name, "in (0, 1]")
+}
+
+/// Configuration of [`RandomSearchOptimizer`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct RandomSearchConfig {
+    /// the optimizer will give up if there is no improvement of the score after this number of iterations
+    pub patience: usize,
+}
+
+impl Default for RandomSearchConfig {
+    fn default() -> Self {
+        Self {
+            patience: DEFAULT_PATIENCE,
+        }
+    }
+}
+
+impl RandomSearchConfig {
+    setters! {
+        with_patience(patience: usize);
+    }
+
+    /// Validate the parameters and build the optimizer
+    pub fn build(self) -> Result<RandomSearchOptimizer, LocalsearchError> {
+        Ok(RandomSearchOptimizer::new(self.patience))
+    }
+}
+
+impl_try_from!(RandomSearchConfig => RandomSearchOptimizer);
+
+/// Configuration of [`HillClimbingOptimizer`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct HillClimbingConfig {
+    /// the optimizer will give up if there is no improvement of the score after this number of iterations
+    pub patience: usize,
+    /// number of trial solutions to generate and evaluate at each iteration
+    pub n_trials: usize,
+}
+
+impl Default for HillClimbingConfig {
+    fn default() -> Self {
+        Self {
+            patience: DEFAULT_PATIENCE,
+            n_trials: DEFAULT_N_TRIALS,
+        }
+    }
+}
+
+impl HillClimbingConfig {
+    setters! {
+        with_patience(patience: usize);
+        with_n_trials(n_trials: usize);
+    }
+
+    /// Validate the parameters and build the optimizer
+    pub fn build(self) -> Result<HillClimbingOptimizer, LocalsearchError> {
+        at_least_one("n_trials", self.n_trials)?;
+        Ok(HillClimbingOptimizer::new(self.patience, self.n_trials))
+    }
+}
+
+impl_try_from!(HillClimbingConfig => HillClimbingOptimizer);
+
+/// Configuration of [`EpsilonGreedyOptimizer`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct EpsilonGreedyConfig {
+    /// the optimizer will give up if there is no improvement of the score after this number of iterations
+    pub patience: usize,
+    /// number of trial solutions to generate and evaluate at each iteration
+    pub n_trials: usize,
+    /// returns to the best solution if there is no improvement after this number of iterations
+    pub return_iter: usize,
+    /// probability to accept a transition that worsens the score
+    pub epsilon: f64,
+}
+
+impl Default for EpsilonGreedyConfig {
+    fn default() -> Self {
+        Self {
+            patience: DEFAULT_PATIENCE,
+            n_trials: DEFAULT_N_TRIALS,
+            return_iter: DEFAULT_RETURN_ITER,
+            epsilon: 0.1,
+        }
+    }
+}
+
+impl EpsilonGreedyConfig {
+    setters! {
+        with_patience(patience: usize);
+        with_n_trials(n_trials: usize);
+        with_return_iter(return_iter: usize);
+        with_epsilon(epsilon: f64);
+    }
+
+    /// Validate the parameters and build the optimizer
+    pub fn build(self) -> Result<EpsilonGreedyOptimizer, LocalsearchError> {
+        at_least_one("n_trials", self.n_trials)?;
+        probability("epsilon", self.epsilon)?;
+        Ok(EpsilonGreedyOptimizer::new(
+            self.patience,
+            self.n_trials,
+            self.return_iter,
+            self.epsilon,
+        ))
+    }
+}
+
+impl_try_from!(EpsilonGreedyConfig => EpsilonGreedyOptimizer);
+
+/// Configuration of [`MetropolisOptimizer`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct MetropolisConfig {
+    /// the optimizer will give up if there is no improvement of the score after this number of iterations
+    pub patience: usize,
+    /// number of trial solutions to generate and evaluate at each iteration
+    pub n_trials: usize,
+    /// returns to the best solution if there is no improvement after this number of iterations
+    pub return_iter: usize,
+    /// inverse temperature
+    pub beta: f64,
+}
+
+impl Default for MetropolisConfig {
+    fn default() -> Self {
+        Self {
+            patience: DEFAULT_PATIENCE,
+            n_trials: DEFAULT_N_TRIALS,
+            return_iter: DEFAULT_RETURN_ITER,
+            beta: 1.0,
+        }
+    }
+}
+
+impl MetropolisConfig {
+    setters! {
+        with_patience(patience: usize);
+        with_n_trials(n_trials: usize);
+        with_return_iter(return_iter: usize);
+        with_beta(beta: f64);
+    }
+
+    /// Validate the parameters and build the optimizer
+    pub fn build(self) -> Result<MetropolisOptimizer, LocalsearchError> {
+        at_least_one("n_trials", self.n_trials)?;
+        non_negative("beta", self.beta)?;
+        Ok(MetropolisOptimizer::new(
+            self.patience,
+            self.n_trials,
+            self.return_iter,
+            self.beta,
+        ))
+    }
+}
+
+impl_try_from!(MetropolisConfig => MetropolisOptimizer);
+
+/// Configuration of [`SimulatedAnnealingOptimizer`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct SimulatedAnnealingConfig {
+    /// the optimizer will give up if there is no improvement of the score after this number of iterations
+    pub patience: usize,
+    /// number of trial solutions to generate and evaluate at each iteration
+    pub n_trials: usize,
+    /// returns to the best solution if there is no improvement after this number of iterations
+    pub return_iter: usize,
+    /// initial inverse temperature
+    pub initial_beta: f64,
+    /// cooling rate
+    pub cooling_rate: f64,
+    /// if given, cool geometrically from `initial_beta` to 1e2 at the end of the optimization measured by this clock,
+    /// instead of using `cooling_rate`
+    pub cooling_clock: Option<ScheduleClock>,
+    /// number of steps after which the inverse temperature is updated
+    pub update_frequency: NonZero<usize>,
+    /// policy raising the temperature again when the search freezes, if any
+    pub reheat: Option<ReheatPolicy>,
+}
+
+impl Default for SimulatedAnnealingConfig {
+    fn default() -> Self {
+        Self {
+            patience: DEFAULT_PATIENCE,
+            n_trials: DEFAULT_N_TRIALS,
+            return_iter: DEFAULT_RETURN_ITER,
+            initial_beta: 1.0,
+            cooling_rate: 0.99,
+            cooling_clock: None,
+            update_frequency: default_update_frequency(),
+            reheat: None,
+        }
+    }
+}
+
+impl SimulatedAnnealingConfig {
+    setters! {
+        with_patience(patience: usize);
+        with_n_trials(n_trials: usize);
+        with_return_iter(return_iter: usize);
+        with_initial_beta(initial_beta: f64);
+        with_cooling_rate(cooling_rate: f64);
+        with_cooling_clock(cooling_clock: ScheduleClock) => Some;
+        with_update_frequency(update_frequency: NonZero<usize>);
+        with_reheat(reheat: ReheatPolicy) => Some;
+    }
+
+    /// Validate the parameters and build the optimizer
+    pub fn build(self) -> Result<SimulatedAnnealingOptimizer, LocalsearchError> {
+        at_least_one("n_trials", self.n_trials)?;
+        positive("initial_beta", self.initial_beta)?;
+        positive("cooling_rate", self.cooling_rate)?;
+        let optimizer = SimulatedAnnealingOptimizer::new(
+            self.patience,
+            self.n_trials,
+            self.return_iter,
+            self.initial_beta,
+            self.cooling_rate,
+            self.update_frequency,
+        );
+        let optimizer = match self.reheat {
+            Some(policy) => {
+                policy.validate()?;
+                optimizer.with_reheat(policy)
+            }
+            None => optimizer,
+        };
+        Ok(match self.cooling_clock {
+            Some(clock) => optimizer.tune_cooling_schedule(clock),
+            None => optimizer,
+        })
+    }
+}
+
+impl_try_from!(SimulatedAnnealingConfig => SimulatedAnnealingOptimizer);
+
+/// Configuration of [`AdaptiveAnnealingOptimizer`]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct AdaptiveAnnealingConfig {
+    /// the optimizer will give up if there is no improvement of the score after this number of iterations
+    pub patience: usize,
+    /// number of trial solutions to generate and evaluate at each iteration
+    pub n_trials: usize,
+    /// returns to the best solution if there is no improvement after this number of iterations
+    pub return_iter: usize,
+    /// initial inverse temperature
+    pub initial_beta: f64,
+    /// scheduler of the target acceptance rate
+    pub scheduler: AdaptiveScheduler,
+    /// number of steps after which the inverse temperature is updated
+    pub update_frequency: NonZero<usize>,
+}
+
+impl Default for AdaptiveAnnealingConfig {
+    fn default() -> Self {
+        Self {
+            patience: DEFAULT_PATIENCE,
+            n_trials: DEFAULT_N_TRIALS,
+            return_iter: DEFAULT_RETURN_ITER,
+            initial_beta: 1.0,
+            scheduler: AdaptiveScheduler::default(),
+            update_frequency: default_update_frequency(),
+        }
+    }
+}
+
+impl AdaptiveAnnealingConfig {
+    setters! {
+        with_patience(patience: usize);
+        with_n_trials(n_trials: usize);
+        with_return_iter(return_iter: usize);
+        with_initial_beta(initial_beta: f64);
+        with_scheduler(scheduler: AdaptiveScheduler);
+        with_update_frequency(update_frequency: NonZero<usize>);
+    }
+
+    /// Validate the parameters and build the optimizer
+    pub fn build(self) -> Result<AdaptiveAnnealingOptimizer, LocalsearchError> {
+        at_least_one("n_trials", self.n_trials)?;
+        positive("initial_beta", self.initial_beta)?;
+        self.scheduler.validate()?;
+        Ok(AdaptiveAnnealingOptimizer::new(
+            self.patience,
+            self.n_trials,
+            self.return_iter,
+            self.initial_beta,
+            self.scheduler,
+            self.update_frequency,
+        ))
+    }
+}
+
+impl_try_from!(AdaptiveAnnealingConfig => AdaptiveAnnealingOptimizer);
+
+/// Configuration of [`GreatDelugeOptimizer`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct GreatDelugeConfig {
+    /// the optimizer will give up if there is no improvement of the score after this number of iterations
+    pub patience: usize,
+    /// number of trial solutions to generate and evaluate at each iteration
+    pub n_trials: usize,
+    /// returns to the best solution if there is no improvement after this number of iterations
+    pub return_iter: usize,
+    /// initial water level relative to the initial score
+    pub level_factor: f64,
+    /// measure of the progress by which the water level is lowered
+    pub clock: ScheduleClock,
+}
+
+impl Default for GreatDelugeConfig {
+    fn default() -> Self {
+        Self {
+            patience: DEFAULT_PATIENCE,
+            n_trials: DEFAULT_N_TRIALS,
+            return_iter: DEFAULT_RETURN_ITER,
+            level_factor: 1.05,
+            clock: ScheduleClock::Iterations,
+        }
+    }
+}
+
+impl GreatDelugeConfig {
+    setters! {
+        with_patience(patience: usize);
+        with_n_trials(n_trials: usize);
+        with_return_iter(return_iter: usize);
+        with_level_factor(level_factor: f64);
+        with_clock(clock: ScheduleClock);
+    }
+
+    /// Validate the parameters and build the optimizer
+    pub fn build(self) -> Result<GreatDelugeOptimizer, LocalsearchError> {
+        at_least_one("n_trials", self.n_trials)?;
+        check(
+            self.level_factor.is_finite() && self.level_factor >= 1.0,
+            "level_factor",
+            "finite and at least 1",
+        )?;
+        Ok(GreatDelugeOptimizer::new(
+            self.patience,
+            self.n_trials,
+            self.return_iter,
+            self.level_factor,
+        )
+        .with_clock(self.clock))
+    }
+}
+
+impl_try_from!(GreatDelugeConfig => GreatDelugeOptimizer);
+
+/// Configuration of [`LogisticAnnealingOptimizer`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct LogisticAnnealingConfig {
+    /// the optimizer will give up if there is no improvement of the score after this number of iterations
+    pub patience: usize,
+    /// number of trial solutions to generate and evaluate at each iteration
+    pub n_trials: usize,
+    /// returns to the best solution if there is no improvement after this number of iterations
+    pub return_iter: usize,
+    /// weight to be multiplied with the relative score difference
+    pub w: f64,
+}
+
+impl Default for LogisticAnnealingConfig {
+    fn default() -> Self {
+        Self {
+            patience: DEFAULT_PATIENCE,
+            n_trials: DEFAULT_N_TRIALS,
+            return_iter: DEFAULT_RETURN_ITER,
+            w: 1e2,
+        }
+    }
+}
+
+impl LogisticAnnealingConfig {
+    setters! {
+        with_patience(patience: usize);
+        with_n_trials(n_trials: usize);
+        with_return_iter(return_iter: usize);
+        with_w(w: f64);
+    }
+
+    /// Validate the parameters and build the optimizer
+    pub fn build(self) -> Result<LogisticAnnealingOptimizer, LocalsearchError> {
+        at_least_one("n_trials", self.n_trials)?;
+        non_negative("w", self.w)?;
+        Ok(LogisticAnnealingOptimizer::new(
+            self.patience,
+            self.n_trials,
+            self.return_iter,
+            self.w,
+        ))
+    }
+}
+
+impl_try_from!(LogisticAnnealingConfig => LogisticAnnealingOptimizer);
+
+/// Configuration of [`RelativeAnnealingOptimizer`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct RelativeAnnealingConfig {
+    /// the optimizer will give up if there is no improvement of the score after this number of iterations
+    pub patience: usize,
+    /// number of trial solutions to generate and evaluate at each iteration
+    pub n_trials: usize,
+    /// returns to the best solution if there is no improvement after this number of iterations
+    pub return_iter: usize,
+    /// weight to be multiplied with the relative score difference
+    pub beta: f64,
+}
+
+impl Default for RelativeAnnealingConfig {
+    fn default() -> Self {
+        Self {
+            patience: DEFAULT_PATIENCE,
+            n_trials: DEFAULT_N_TRIALS,
+            return_iter: DEFAULT_RETURN_ITER,
+            beta: 1e2,
+        }
+    }
+}
+
+impl RelativeAnnealingConfig {
+    setters! {
+        with_patience(patience: usize);
+        with_n_trials(n_trials: usize);
+        with_return_iter(return_iter: usize);
+        with_beta(beta: f64);
+    }
+
+    /// Validate the parameters and build the optimizer
+    pub fn build(self) -> Result<RelativeAnnealingOptimizer, LocalsearchError> {
+        at_least_one("n_trials", self.n_trials)?;
+        non_negative("beta", self.beta)?;
+        Ok(RelativeAnnealingOptimizer::new(
+            self.patience,
+            self.n_trials,
+            self.return_iter,
+            self.beta,
+        ))
+    }
+}
+
+impl_try_from!(RelativeAnnealingConfig => RelativeAnnealingOptimizer);
+
+/// Configuration of [`TsallisRelativeAnnealingOptimizer`]
+#[derive(Debug, Clone, Copy)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct TsallisRelativeAnnealingConfig {
+    /// the optimizer will give up if there is no improvement of the score after this number of iterations
+    pub patience: usize,
+    /// number of trial solutions to generate and evaluate at each iteration
+    pub n_trials: usize,
+    /// returns to the best solution if there is no improvement after this number of iterations
+    pub return_iter: usize,
+    /// initial weight to be multiplied with the relative score difference
+    pub initial_beta: f64,
+    /// number of steps after which beta is updated
+    pub update_frequency: NonZero<usize>,
+    /// Tsallis parameter, assumed to be > 1.0
+    pub q: f64,
+    /// parameter ξ in the acceptance probability formula
+    pub xi: f64,
+    /// scheduler of the target acceptance rate, a constant rate of 0.3 if not given
+    pub scheduler: Option<AdaptiveScheduler>,
+}
+
+impl Default for TsallisRelativeAnnealingConfig {
+    fn default() -> Self {
+        Self {
+            patience: DEFAULT_PATIENCE,
+            n_trials: DEFAULT_N_TRIALS,
+            return_iter: DEFAULT_RETURN_ITER,
+            initial_beta: 1e2,
+            update_frequency: default_update_frequency(),
+            q: 2.5,
+            xi: 1.0,
+            scheduler: None,
+        }
+    }
+}
+
+impl TsallisRelativeAnnealingConfig {
+    setters! {
+        with_patience(patience: usize);
+        with_n_trials(n_trials: usize);
+        with_return_iter(return_iter: usize);
+        with_initial_beta(initial_beta: f64);
+        with_update_frequency(update_frequency: NonZero<usize>);
+        with_q(q: f64);
+        with_xi(xi: f64);
+        with_scheduler(scheduler: AdaptiveScheduler) => Some;
+    }
+
+    /// Validate the parameters and build the optimizer
+    pub fn build(self) -> Result<TsallisRelativeAnnealingOptimizer, LocalsearchError> {
+        at_least_one("n_trials", self.n_trials)?;
+        positive("initial_beta", self.initial_beta)?;
+        check(
+            self.q.is_finite() && self.q > 1.0,
+            "q",
+            "finite and greater than 1",
+        )?;
+        positive("xi", self.xi)?;
+        let optimizer = TsallisRelativeAnnealingOptimizer::new(
+            self.patience,
+            self.n_trials,
+            self.return_iter,
+            self.initial_beta,
+            self.update_frequency,
+            self.q,
+            self.xi,
+        );
+        Ok(match self.scheduler {
+            Some(scheduler) => {
+                scheduler.validate()?;
+                optimizer.with_scheduler(scheduler)
+            }
+            None => optimizer,
+        })
+    }
+}
+
+impl_try_from!(TsallisRelativeAnnealingConfig => TsallisRelativeAnnealingOptimizer);
+
+/// Configuration of [`ParallelTemperingOptimizer`]
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct ParallelTemperingConfig {
+    /// the optimizer will give up if there is no improvement of the score after this number of iterations
+    pub patience: usize,
+    /// number of trial solutions to generate and evaluate at each Metropolis step
+    pub n_trials: usize,
+    /// returns to the best solution if there is no improvement after this number of iterations
+    pub return_iter: usize,
+    /// number of replicas of the geometric ladder
+    pub n_replicas: usize,
+    /// lowest inverse temperature of the geometric ladder
+    pub beta_min: f64,
+    /// highest inverse temperature of the geometric ladder
+    pub beta_max: f64,
+    /// explicit inverse temperature ladder, replacing the geometric one if given
+    pub betas: Option<Vec<f64>>,
+    /// number of Metropolis steps to run per replica between exchange attempts
+    pub update_frequency: NonZero<usize>,
+}
+
+impl Default for ParallelTemperingConfig {
+    fn default() -> Self {
+        Self {
+            patience: DEFAULT_PATIENCE,
+            n_trials: DEFAULT_N_TRIALS,
+            return_iter: DEFAULT_RETURN_ITER,
+            n_replicas: 8,
+            beta_min: 1e-3,
+            beta_max: 1e2,
+            betas: None,
+            update_frequency: NonZero::new(10).expect("update_frequency must be >= 1"),
+        }
+    }
+}
+
+impl ParallelTemperingConfig {
+    setters! {
+        with_patience(patience: usize);
+        with_n_trials(n_trials: usize);
+        with_return_iter(return_iter: usize);
+        with_n_replicas(n_replicas: usize);
+        with_beta_min(beta_min: f64);
+        with_beta_max(beta_max: f64);
+        with_betas(betas: Vec<f64>) => Some;
+        with_update_frequency(update_frequency: NonZero<usize>);
+    }
+
+    /// Validate the parameters and build the optimizer
+    pub fn build(self) -> Result<ParallelTemperingOptimizer, LocalsearchError> {
+        at_least_one("n_trials", self.n_trials)?;
+        match self.betas {
+            Some(betas) => {
+                check(!betas.is_empty(), "betas", "non-empty")?;
+                for &beta in &betas {
+                    non_negative("betas", beta)?;
+                }
+                Ok(ParallelTemperingOptimizer::new(
+                    self.patience,
+                    self.n_trials,
+                    self.return_iter,
+                    betas,
+                    self.update_frequency,
+                ))
+            }
+            None => {
+                at_least_one("n_replicas", self.n_replicas)?;
+                positive("beta_min", self.beta_min)?;
+                check(
+                    self.beta_max.is_finite() && self.beta_max >= self.beta_min,
+                    "beta_max",
+                    "finite and at least beta_min",
+                )?;
+                Ok(ParallelTemperingOptimizer::with_geometric_betas(
+                    self.patience,
+                    self.n_trials,
+                    self.return_iter,
+                    self.n_replicas,
+                    self.beta_min,
+                    self.beta_max,
+                    self.update_frequency,
+                ))
+            }
+        }
+    }
+}
+
+impl_try_from!(ParallelTemperingConfig => ParallelTemperingOptimizer);
+
+/// Configuration of [`PopulationAnnealingOptimizer`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct PopulationAnnealingConfig {
+    /// the optimizer will give up if there is no improvement of the score after this number of iterations
+    pub patience: usize,
+    /// number of trial solutions to generate and evaluate at each iteration
+    pub n_trials: usize,
+    /// returns to the best solution if there is no improvement after this number of iterations
+    pub return_iter: usize,
+    /// initial inverse temperature
+    pub initial_beta: f64,
+    /// cooling rate
+    pub cooling_rate: f64,
+    /// if given, cool geometrically from `initial_beta` to 1e2 at the end of the optimization measured by this clock,
+    /// instead of using `cooling_rate`
+    pub cooling_clock: Option<ScheduleClock>,
+    /// number of steps to run each member before updating the population
+    pub update_frequency: NonZero<usize>,
+    /// number of members of the population
+    pub population_size: usize,
+}
+
+impl Default for PopulationAnnealingConfig {
+    fn default() -> Self {
+        Self {
+            patience: DEFAULT_PATIENCE,
+            n_trials: DEFAULT_N_TRIALS,
+            return_iter: DEFAULT_RETURN_ITER,
+            initial_beta: 1.0,
+            cooling_rate: 0.99,
+            cooling_clock: None,
+            update_frequency: default_update_frequency(),
+            population_size: 16,
+        }
+    }
+}
+
+impl PopulationAnnealingConfig {
+    setters! {
+        with_patience(patience: usize);
+        with_n_trials(n_trials: usize);
+        with_return_iter(return_iter: usize);
+        with_initial_beta(initial_beta: f64);
+        with_cooling_rate(cooling_rate: f64);
+        with_cooling_clock(cooling_clock: ScheduleClock) => Some;
+        with_update_frequency(update_frequency: NonZero<usize>);
+        with_population_size(population_size: usize);
+    }
+
+    /// Validate the parameters and build the optimizer
+    pub fn build(self) -> Result<PopulationAnnealingOptimizer, LocalsearchError> {
+        at_least_one("n_trials", self.n_trials)?;
+        positive("initial_beta", self.initial_beta)?;
+        positive("cooling_rate", self.cooling_rate)?;
+        at_least_one("population_size", self.population_size)?;
+        let optimizer = PopulationAnnealingOptimizer::new(
+            self.patience,
+            self.n_trials,
+            self.return_iter,
+            self.initial_beta,
+            self.cooling_rate,
+            self.update_frequency,
+            self.population_size,
+        );
+        Ok(match self.cooling_clock {
+            Some(clock) => optimizer.tune_cooling_schedule(clock),
+            None => optimizer,
+        })
+    }
+}
+
+impl_try_from!(PopulationAnnealingConfig => PopulationAnnealingOptimizer);
+
+/// Configuration of [`TabuSearchOptimizer`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct TabuSearchConfig {
+    /// the optimizer will give up if there is no improvement of the score after this number of iterations
+    pub patience: usize,
+    /// number of trial solutions to generate and evaluate at each iteration
+    pub n_trials: usize,
+    /// returns to the best solution if there is no improvement after this number of iterations
+    pub return_iter: usize,
+    /// size of the tabu list
+    pub tabu_size: usize,
+}
+
+impl Default for TabuSearchConfig {
+    fn default() -> Self {
+        Self {
+            patience: DEFAULT_PATIENCE,
+            n_trials: DEFAULT_N_TRIALS,
+            return_iter: DEFAULT_RETURN_ITER,
+            tabu_size: 10,
+        }
+    }
+}
+
+impl TabuSearchConfig {
+    setters! {
+        with_patience(patience: usize);
+        with_n_trials(n_trials: usize);
+        with_return_iter(return_iter: usize);
+        with_tabu_size(tabu_size: usize);
+    }
+
+    /// Validate the parameters and build the optimizer with the tabu list `T`
+    pub fn build<T: TabuList>(self) -> Result<TabuSearchOptimizer<T>, LocalsearchError> {
+        at_least_one("n_trials", self.n_trials)?;
+        Ok(TabuSearchOptimizer::new(
+            self.patience,
+            self.n_trials,
+            self.return_iter,
+            self.tabu_size,
+        ))
+    }
+}
+
+impl<T: TabuList> TryFrom<TabuSearchConfig> for TabuSearchOptimizer<T> {
+    type Error = LocalsearchError;
+
+    fn try_from(config: TabuSearchConfig) -> Result<Self, Self::Error> {
+        config.build()
+    }
+}
+
+/// Configuration of [`AmosaOptimizer`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct AmosaConfig {
+    /// size of the archive after pruning
+    pub hard_limit: usize,
+    /// size above which the archive is pruned
+    pub soft_limit: usize,
+    /// number of random solutions seeding the archive, `soft_limit` if not given
+    pub n_initial: Option<usize>,
+    /// initial inverse temperature
+    pub initial_beta: f64,
+    /// cooling rate
+    pub cooling_rate: f64,
+    /// if given, cool geometrically from `initial_beta` to 1e2 at the end of the optimization measured by this clock,
+    /// instead of using `cooling_rate`
+    pub cooling_clock: Option<ScheduleClock>,
+    /// number of steps after which the inverse temperature is updated
+    pub update_frequency: NonZero<usize>,
+}
+
+impl Default for AmosaConfig {
+    fn default() -> Self {
+        Self {
+            hard_limit: 20,
+            soft_limit: 40,
+            n_initial: None,
+            initial_beta: 1.0,
+            cooling_rate: 0.99,
+            cooling_clock: None,
+            update_frequency: default_update_frequency(),
+        }
+    }
+}
+
+impl AmosaConfig {
+    setters! {
+        with_hard_limit(hard_limit: usize);
+        with_soft_limit(soft_limit: usize);
+        with_n_initial(n_initial: usize) => Some;
+        with_initial_beta(initial_beta: f64);
+        with_cooling_rate(cooling_rate: f64);
+        with_cooling_clock(cooling_clock: ScheduleClock) => Some;
+        with_update_frequency(update_frequency: NonZero<usize>);
+    }
+
+    /// Validate the parameters and build the optimizer
+    pub fn build(self) -> Result<AmosaOptimizer, LocalsearchError> {
+        at_least_one("hard_limit", self.hard_limit)?;
+        check(
+            self.soft_limit >= self.hard_limit,
+            "soft_limit",
+            "at least hard_limit",
+        )?;
+        positive("initial_beta", self.initial_beta)?;
+        positive("cooling_rate", self.cooling_rate)?;
+        let optimizer = AmosaOptimizer::new(
+            self.hard_limit,
+            self.soft_limit,
+            self.initial_beta,
+            self.cooling_rate,
+            self.update_frequency,
+        );
+        let optimizer = match self.n_initial {
+            Some(n_initial) => {
+                at_least_one("n_initial", n_initial)?;
+                optimizer.with_n_initial(n_initial)
+            }
+            None => optimizer,
+        };
+        Ok(match self.cooling_clock {
+            Some(clock) => optimizer.tune_cooling_schedule(clock),
+            None => optimizer,
+        })
+    }
+}
+
+impl_try_from!(AmosaConfig => AmosaOptimizer);
+
+/// Configuration of [`LexicographicAnnealingOptimizer`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(default)
+)]
+pub struct LexicographicAnnealingConfig {
+    /// the optimizer will give up if there is no improvement of the score after this number of iterations
+    pub patience: usize,
+    /// number of trial solutions to generate and evaluate at each iteration
+    pub n_trials: usize,
+    /// returns to the best solution if there is no improvement after this number of iterations
+    pub return_iter: usize,
+    /// inverse temperature of the hard level
+    pub hard_beta: f64,
+    /// inverse temperature of the medium level
+    pub medium_beta: f64,
+    /// inverse temperature of the soft level
+    pub soft_beta: f64,
+    /// factor of the inverse temperatures at the end of the optimization, reached geometrically from 1
+    pub final_factor: f64,
+    /// clock measuring the progress toward `final_factor`
+    pub cooling_clock: ScheduleClock,
+}
+
+impl Default for LexicographicAnnealingConfig {
+    fn default() -> Self {
+        Self {
+            patience: DEFAULT_PATIENCE,
+            n_trials: DEFAULT_N_TRIALS,
+            return_iter: DEFAULT_RETURN_ITER,
+            hard_beta: 10.0,
+            medium_beta: 5.0,
+            soft_beta: 1.0,
+            final_factor: 100.0,
+            cooling_clock: ScheduleClock::Iterations,
+        }
+    }
+}
+
+impl LexicographicAnnealingConfig {
+    setters! {
+        with_patience(patience: usize);
+        with_n_trials(n_trials: usize);
+        with_return_iter(return_iter: usize);
+        with_hard_beta(hard_beta: f64);
+        with_medium_beta(medium_beta: f64);
+        with_soft_beta(soft_beta: f64);
+        with_final_factor(final_factor: f64);
+        with_cooling_clock(cooling_clock: ScheduleClock);
+    }
+
+    /// Validate the parameters and build the optimizer
+    pub fn build(self) -> Result<LexicographicAnnealingOptimizer, LocalsearchError> {
+        at_least_one("n_trials", self.n_trials)?;
+        non_negative("hard_beta", self.hard_beta)?;
+        non_negative("medium_beta", self.medium_beta)?;
+        non_negative("soft_beta", self.soft_beta)?;
+        positive("final_factor", self.final_factor)?;
+        let schedule = Schedule::geometric(1.0, self.final_factor).with_clock(self.cooling_clock);
+        Ok(LexicographicAnnealingOptimizer::new(
+            self.patience,
+            self.n_trials,
+            self.return_iter,
+            self.hard_beta,
+            self.medium_beta,
+            self.soft_beta,
+        )
+        .with_schedule(schedule))
+    }
+}
+
+impl_try_from!(LexicographicAnnealingConfig => LexicographicAnnealingOptimizer);
+
+/// Configuration of [`GenericLocalSearchOptimizer`] with the acceptance criterion `acceptance`,
+/// created by [`GenericLocalSearchOptimizer::builder`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GenericLocalSearchConfig<FT> {
+    /// the optimizer will give up if there is no improvement of the score after this number of iterations
+    pub patience: usize,
+    /// number of trial solutions to generate and evaluate at each iteration
+    pub n_trials: usize,
+    /// returns to the best solution if there is no improvement after this number of iterations
+    pub return_iter: usize,
+    /// acceptance criterion of the trial solutions
+    pub acceptance: FT,
+}
+
+impl<FT> GenericLocalSearchConfig<FT> {
+    /// Configuration with the default parameters and the acceptance criterion `acceptance`
+    pub fn new(acceptance: FT) -> Self {
+        Self {
+            patience: DEFAULT_PATIENCE,
+            n_trials: DEFAULT_N_TRIALS,
+            return_iter: DEFAULT_RETURN_ITER,
+            acceptance,
+        }
+    }
+
+    setters! {
+        with_patience(patience: usize);
+        with_n_trials(n_trials: usize);
+        with_return_iter(return_iter: usize);
+        with_acceptance(acceptance: FT);
+    }
+
+    /// Validate the parameters and build the optimizer
+    pub fn build<ST: Ord + Sync + Send + Copy>(
+        self,
+    ) -> Result<GenericLocalSearchOptimizer<ST, FT>, LocalsearchError>
+    where
+        FT: AcceptanceCriterion<ST>,
+    {
+        at_least_one("n_trials", self.n_trials)?;
+        Ok(GenericLocalSearchOptimizer::new(
+            self.patience,
+            self.n_trials,
+            self.return_iter,
+            self.acceptance,
+        ))
+    }
+}
+
+impl<ST: Ord + Sync + Send + Copy, FT: AcceptanceCriterion<ST>>
+    TryFrom<GenericLocalSearchConfig<FT>> for GenericLocalSearchOptimizer<ST, FT>
+{
+    type Error = LocalsearchError;
+
+    fn try_from(config: GenericLocalSearchConfig<FT>) -> Result<Self, Self::Error> {
+        config.build()
+    }
+}
+
+/// Configuration of any optimizer, tagged by the name of its algorithm.
+///
+/// With the `serde` feature it deserializes from e.g.
+/// `{"algorithm": "simulated_annealing", "n_trials": 32}`;
+/// omitted parameters take their default values.
+#[derive(Debug, Clone)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(tag = "algorithm", rename_all = "snake_case")
+)]
+#[non_exhaustive]
+pub enum OptimizerConfig {
+    /// [`RandomSearchOptimizer`], named `random_search`
+    RandomSearch(RandomSearchConfig),
+    /// [`HillClimbingOptimizer`], named `hill_climbing`
+    HillClimbing(HillClimbingConfig),
+    /// [`EpsilonGreedyOptimizer`], named `epsilon_greedy`
+    EpsilonGreedy(EpsilonGreedyConfig),
+    /// [`MetropolisOptimizer`], named `metropolis`
+    Metropolis(MetropolisConfig),
+    /// [`SimulatedAnnealingOptimizer`], named `simulated_annealing`
+    SimulatedAnnealing(SimulatedAnnealingConfig),
+    /// [`AdaptiveAnnealingOptimizer`], named `adaptive_annealing`
+    AdaptiveAnnealing(AdaptiveAnnealingConfig),
+    /// [`GreatDelugeOptimizer`], named `great_deluge`
+    GreatDeluge(GreatDelugeConfig),
+    /// [`LogisticAnnealingOptimizer`], named `logistic_annealing`
+    LogisticAnnealing(LogisticAnnealingConfig),
+    /// [`RelativeAnnealingOptimizer`], named `relative_annealing`
+    RelativeAnnealing(RelativeAnnealingConfig),
+    /// [`TsallisRelativeAnnealingOptimizer`], named `tsallis_relative_annealing`
+    TsallisRelativeAnnealing(TsallisRelativeAnnealingConfig),
+    /// [`ParallelTemperingOptimizer`], named `parallel_tempering`
+    ParallelTempering(ParallelTemperingConfig),
+    /// [`PopulationAnnealingOptimizer`], named `population_annealing`
+    PopulationAnnealing(PopulationAnnealingConfig),
+    /// [`TabuSearchOptimizer`], named `tabu_search`
+    TabuSearch(TabuSearchConfig),
+}
+
+impl OptimizerConfig {
+    /// Names of all algorithms, as accepted by [`OptimizerConfig::from_name`].
+    /// Only [`OptimizerConfig::ORDINAL_ALGORITHMS`] can be built for models whose score is not an [`Energy`].
+    pub const ALGORITHMS: [&'static str; 13] = [
+        "random_search",
+        "hill_climbing",
+        "epsilon_greedy",
+        "metropolis",
+        "simulated_annealing",
+        "adaptive_annealing",
+        "great_deluge",
+        "logistic_annealing",
+        "relative_annealing",
+        "tsallis_relative_annealing",
+        "parallel_tempering",
+        "population_annealing",
+        "tabu_search",
+    ];
+
+    /// Names of the algorithms that only compare scores, which [`OptimizerConfig::build_ordinal`] builds
+    /// for models whose score is `Ord` without being an [`Energy`], e.g. [`HardMediumSoftScore`](crate::HardMediumSoftScore)
+    pub const ORDINAL_ALGORITHMS: [&'static str; 4] = [
+        "random_search",
+        "hill_climbing",
+        "epsilon_greedy",
+        "tabu_search",
+    ];
+
+    /// Default configuration of the algorithm called `name`
+    pub fn from_name(name: &str) -> Result<Self, LocalsearchError> {
+        let config = match name {
+            "random_search" => Self::RandomSearch(Default::default()),
+            "hill_climbing" => Self::HillClimbing(Default::default()),
+            "epsilon_greedy" => Self::EpsilonGreedy(Default::default()),
+            "metropolis" => Self::Metropolis(Default::default()),
+            "simulated_annealing" => Self::SimulatedAnnealing(Default::default()),
+            "adaptive_annealing" => Self::AdaptiveAnnealing(Default::default()),
+            "great_deluge" => Self::GreatDeluge(Default::default()),
+            "logistic_annealing" => Self::LogisticAnnealing(Default::default()),
+            "relative_annealing" => Self::RelativeAnnealing(Default::default()),
+            "tsallis_relative_annealing" => Self::TsallisRelativeAnnealing(Default::default()),
+            "parallel_tempering" => Self::ParallelTempering(Default::default()),
+            "population_annealing" => Self::PopulationAnnealing(Default::default()),
+            "tabu_search" => Self::TabuSearch(Default::default()),
+            _ => {
+                return Err(LocalsearchError::ConfigError(format!(
+                    "unknown algorithm `{name}`"
+                )));
+            }
+        };
+        Ok(config)
+    }
+
+    /// Name of the algorithm
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::RandomSearch(_) => "random_search",
+            Self::HillClimbing(_) => "hill_climbing",
+            Self::EpsilonGreedy(_) => "epsilon_greedy",
+            Self::Metropolis(_) => "metropolis",
+            Self::SimulatedAnnealing(_) => "simulated_annealing",
+            Self::AdaptiveAnnealing(_) => "adaptive_annealing",
+            Self::GreatDeluge(_) => "great_deluge",
+            Self::LogisticAnnealing(_) => "logistic_annealing",
+            Self::RelativeAnnealing(_) => "relative_annealing",
+            Self::TsallisRelativeAnnealing(_) => "tsallis_relative_annealing",
+            Self::ParallelTempering(_) => "parallel_tempering",
+            Self::PopulationAnnealing(_) => "population_annealing",
+            Self::TabuSearch(_) => "tabu_search",
+        }
+    }
+
+    /// Build the configured optimizer.
+    /// Tabu search needs the type of its tabu list, use [`OptimizerConfig::build_with_tabu_list`] for it.
+    pub fn build<M: OptModel<ScoreType: Energy>>(
+        &self,
+    ) -> Result<Box<dyn LocalSearchOptimizer<M> + Send + Sync>, LocalsearchError> {
+        let optimizer: Box<dyn LocalSearchOptimizer<M> + Send + Sync> = match self.clone() {
+            Self::RandomSearch(config) => Box::new(config.build()?),
+            Self::HillClimbing(config) => Box::new(config.build()?),
+            Self::EpsilonGreedy(config) => Box::new(config.build()?),
+            Self::Metropolis(config) => Box::new(config.build()?),
+            Self::SimulatedAnnealing(config) => Box::new(config.build()?),
+            Self::AdaptiveAnnealing(config) => Box::new(config.build()?),
+            Self::GreatDeluge(config) => Box::new(config.build()?),
+            Self::LogisticAnnealing(config) => Box::new(config.build()?),
+            Self::RelativeAnnealing(config) => Box::new(config.build()?),
+            Self::TsallisRelativeAnnealing(config) => Box::new(config.build()?),
+            Self::ParallelTempering(config) => Box::new(config.build()?),
+            Self::PopulationAnnealing(config) => Box::new(config.build()?),
+            Self::TabuSearch(_) => {
+                return Err(LocalsearchError::ConfigError(
+                    "tabu_search needs a tabu list, use build_with_tabu_list".to_string(),
+                ));
+            }
+        };
+        Ok(optimizer)
+    }
+
+    /// Build the configured optimizer, using `T` as the tabu list of tabu search
+    pub fn build_with_tabu_list<M, T>(
+        &self,
+    ) -> Result<Box<dyn LocalSearchOptimizer<M> + Send + Sync>, LocalsearchError>
+    where
+        M: OptModel<ScoreType: Energy>,
+        T: TabuList<Item = M::TransitionType> + Send + Sync + 'static,
+    {
+        match self {
+            Self::TabuSearch(config) => Ok(Box::new(config.build::<T>()?)),
+            _ => self.build(),
+        }
+    }
+
+    /// Build the configured optimizer for a model whose score is only `Ord`.
+    /// Algorithms outside [`OptimizerConfig::ORDINAL_ALGORITHMS`] need an [`Energy`] score
+    /// and return [`LocalsearchError::InvalidParameter`].
+    /// Tabu search needs the type of its tabu list, use [`OptimizerConfig::build_ordinal_with_tabu_list`] for it.
+    pub fn build_ordinal<M: OptModel>(
+        &self,
+    ) -> Result<Box<dyn LocalSearchOptimizer<M> + Send + Sync>, LocalsearchError> {
+        let optimizer: Box<dyn LocalSearchOptimizer<M> + Send + Sync> = match self.clone() {
+            Self::RandomSearch(config) => Box::new(config.build()?),
+            Self::HillClimbing(config) => Box::new(config.build()?),
+            Self::EpsilonGreedy(config) => Box::new(config.build()?),
+            Self::TabuSearch(_) => {
+                return Err(LocalsearchError::ConfigError(
+                    "tabu_search needs a tabu list, use build_ordinal_with_tabu_list".to_string(),
+                ));
+            }
+            _ => {
+                return Err(LocalsearchError::InvalidParameter {
+                    name: "algorithm",
+                    expected: "random_search, hill_climbing, epsilon_greedy or tabu_search for a score without energy",
+                });
+            }
+        };
+        Ok(optimizer)
+    }
+
+    /// Same as [`OptimizerConfig::build_ordinal`], using `T` as the tabu list of tabu search
+    pub fn build_ordinal_with_tabu_list<M, T>(
+        &self,
+    ) -> Result<Box<dyn LocalSearchOptimizer<M> + Send + Sync>, LocalsearchError>
+    where
+        M: OptModel,
+        T: TabuList<Item = M::TransitionType> + Send + Sync + 'static,
+    {
+        match self {
+            Self::TabuSearch(config) => Ok(Box::new(config.build::<T>()?)),
+            _ => self.build_ordinal(),
+        }
+    }
+}
@@ -1,5 +1,13 @@
-use super::{GenericLocalSearchOptimizer, base::LocalSearchOptimizer};
-use crate::{Duration, OptModel, callback::OptCallbackFn};
+use rand::Rng;
+
+use super::{
+    AcceptanceCriterion, AcceptanceState, EpsilonGreedyConfig, GenericLocalSearchOptimizer,
+    Schedule, base::LocalSearchOptimizer,
+};
+use crate::{
+    AlgorithmState, Duration, LocalsearchError, OptControl, OptModel, OptSession,
+    stop_condition::StopCondition,
+};
 
 fn transition_prob<T: PartialOrd>(current: T, trial: T, epsilon: f64) -> f64 {
     if trial < current {
@@ -8,18 +16,58 @@ fn transition_prob<T: PartialOrd>(current: T, trial: T, epsilon: f64) -> f64 {
     epsilon
 }
 
+/// Epsilon-greedy acceptance with a scheduled epsilon
+#[derive(Clone)]
+struct EpsilonAcceptance {
+    epsilon: f64,
+    schedule: Schedule,
+}
+
+impl<ST: PartialOrd> AcceptanceCriterion<ST> for EpsilonAcceptance {
+    fn acceptance_probability(&self, current_score: ST, trial_score: ST) -> f64 {
+        transition_prob(current_score, trial_score, self.epsilon)
+    }
+
+    fn initialize(&mut self, _initial_score: ST) {
+        self.epsilon = self.schedule.value(0.0);
+    }
+
+    fn update(&mut self, state: &AcceptanceState<ST>) {
+        self.epsilon = self.schedule.value_at(state);
+    }
+
+    fn algorithm_state(&self) -> AlgorithmState {
+        AlgorithmState {
+            epsilon: Some(self.epsilon),
+            ..Default::default()
+        }
+    }
+
+    fn restore(&mut self, state: &AlgorithmState) {
+        if let Some(epsilon) = state.epsilon {
+            self.epsilon = epsilon;
+        }
+    }
+}
+
 /// Optimizer that implements epsilon-greedy algorithm.
 /// Unlike a total greedy algorithm such as hill climbing,
-/// it allows transitions that worsens the score with a fixed probability
-#[derive(Clone, Copy)]
+/// it allows transitions that worsens the score with a fixed or scheduled probability
+#[derive(Clone)]
 pub struct EpsilonGreedyOptimizer {
     patience: usize,
     n_trials: usize,
     return_iter: usize,
-    epsilon: f64,
+    epsilon: Schedule,
 }
 
 impl EpsilonGreedyOptimizer {
+    /// Builder of the optimizer, starting from the default parameters of [`EpsilonGreedyConfig`]
+    /// and validating them in [`EpsilonGreedyConfig::build`]
+    pub fn builder() -> EpsilonGreedyConfig {
+        EpsilonGreedyConfig::default()
+    }
+
     /// Constructor of EpsilonGreedyOptimizer
     ///
     /// - `patience` : the optimizer will give up
@@ -32,42 +80,61 @@ impl EpsilonGreedyOptimizer {
             patience,
             n_trials,
             return_iter,
-            epsilon,
+            epsilon: Schedule::constant(epsilon),
         }
     }
+
+    /// Replace the fixed epsilon with a schedule, e.g. a decaying epsilon.
+    /// Values must be in [0, 1].
+    pub fn with_epsilon_schedule(mut self, schedule: Schedule) -> Self {
+        self.epsilon = schedule;
+        self
+    }
 }
 
 impl<M: OptModel> LocalSearchOptimizer<M> for EpsilonGreedyOptimizer {
-    /// Start optimization
+    /// Start an optimization to be run step by step
     ///
     /// - `model` : the model to optimize
     /// - `initial_solution` : the initial solution to start optimization
     /// - `initial_score` : the initial score of the initial solution
     /// - `n_iter`: maximum iterations
     /// - `time_limit`: maximum iteration time
-    /// - `callback` : callback function that will be invoked at the end of each iteration
-    fn optimize(
+    /// - `stop_condition`: additional condition to stop the optimization, checked at the end of each iteration
+    /// - `control`: handle to cancel, pause or inject solutions from other threads, if any
+    /// - `rng`: random number generator from which all randomness is drawn
+    fn start_optimization<'a>(
         &self,
-        model: &M,
+        model: &'a M,
         initial_solution: M::SolutionType,
         initial_score: M::ScoreType,
         n_iter: usize,
         time_limit: Duration,
-        callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
-    ) -> (M::SolutionType, M::ScoreType) {
+        stop_condition: Box<dyn StopCondition<M::ScoreType> + 'a>,
+        control: Option<&'a OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: Box<dyn Rng + 'a>,
+    ) -> Result<OptSession<'a, M>, LocalsearchError>
+    where
+        Self: 'a,
+    {
         let optimizer = GenericLocalSearchOptimizer::new(
             self.patience,
             self.n_trials,
             self.return_iter,
-            |current, trial| transition_prob(current, trial, self.epsilon),
+            EpsilonAcceptance {
+                epsilon: self.epsilon.value(0.0),
+                schedule: self.epsilon.clone(),
+            },
         );
-        optimizer.optimize(
+        optimizer.start_optimization(
             model,
             initial_solution,
             initial_score,
             n_iter,
             time_limit,
-            callback,
+            stop_condition,
+            control,
+            rng,
         )
     }
 }
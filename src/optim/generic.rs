@@ -1,13 +1,19 @@
 use std::{cell::RefCell, marker::PhantomData, rc::Rc};
 
-use rand::RngExt as _;
+use rand::{Rng, RngExt as _, rngs::SmallRng};
 use rayon::prelude::*;
 
-use super::{LocalSearchOptimizer, TransitionProbabilityFn};
+use super::{
+    AcceptanceCriterion, AcceptanceState, GenericLocalSearchConfig, IncrementalOptimizer,
+    LocalSearchOptimizer, acceptance::elapsed_fraction,
+};
 use crate::{
-    Duration, Instant, OptModel,
-    callback::{OptCallbackFn, OptProgress},
+    AlgorithmState, Checkpoint, Duration, IncrementalOptModel, Instant, LocalsearchError,
+    OptControl, OptModel, OptObserver, OptResult, OptSession, OptStepper, StopReason,
+    callback::{OptCallbackFn, OptMetrics, OptProgress},
     counter::AcceptanceCounter,
+    stop_condition::{StopCondition, StopState},
+    utils::{catch_panic, split_rng},
 };
 
 /// Result of an optimization step, containing information about the best and last solutions,
@@ -23,46 +29,81 @@ pub struct StepResult<S, ST> {
     pub last_score: ST,
     /// Acceptance counter for the step.
     pub acceptance_counter: AcceptanceCounter,
+    /// Number of trial solutions evaluated during this step.
+    pub n_evaluations: usize,
+    /// Number of iterations done during this step.
+    pub n_iterations: usize,
+    /// Elapsed time of this step.
+    pub elapsed: Duration,
+    /// Reason why this step stopped.
+    pub stop_reason: StopReason,
+    /// Algorithm-specific state at the end of this step.
+    pub algorithm_state: AlgorithmState,
+}
+
+impl<S, ST> StepResult<S, ST> {
+    /// Convert into an [`OptResult`] holding the best solution
+    pub fn into_result(self) -> OptResult<S, ST> {
+        OptResult {
+            solution: self.best_solution,
+            score: self.best_score,
+            n_iterations: self.n_iterations,
+            n_evaluations: self.n_evaluations,
+            elapsed: self.elapsed,
+            acceptance_ratio: self.acceptance_counter.acceptance_ratio(),
+            stop_reason: self.stop_reason,
+            algorithm_state: self.algorithm_state,
+        }
+    }
 }
 
 /// Optimizer that implements local search algorithm
-/// Given a function f that converts a float number to probability,
+/// Given an acceptance criterion f that converts a pair of scores to probability,
 /// the trial solution is accepted by the following procedure
 ///
 /// 1. p <- f(current_score, trial_score)
 /// 2. accept if p > rand(0, 1)
+///
+/// The criterion is cloned at the start of each optimization and its state is updated at the end of each iteration.
 #[derive(Clone, Copy)]
-pub struct GenericLocalSearchOptimizer<
-    ST: Ord + Sync + Send + Copy,
-    FT: TransitionProbabilityFn<ST>,
-> {
+pub struct GenericLocalSearchOptimizer<ST: Ord + Sync + Send + Copy, FT: AcceptanceCriterion<ST>> {
     patience: usize,
     n_trials: usize,
     return_iter: usize,
-    score_func: FT,
+    acceptance: FT,
     phantom: PhantomData<ST>,
 }
 
-impl<ST: Ord + Sync + Send + Copy, FT: TransitionProbabilityFn<ST>>
+impl<ST: Ord + Sync + Send + Copy, FT: AcceptanceCriterion<ST>>
     GenericLocalSearchOptimizer<ST, FT>
 {
+    /// Builder of the optimizer with the acceptance criterion `acceptance`, starting from the default parameters
+    /// of [`GenericLocalSearchConfig`] and validating them in [`GenericLocalSearchConfig::build`]
+    pub fn builder(acceptance: FT) -> GenericLocalSearchConfig<FT> {
+        GenericLocalSearchConfig::new(acceptance)
+    }
+
     /// Constructor of BaseLocalSearchOptimizer
     ///
     /// - `patience` : the optimizer will give up
     ///   if there is no improvement of the score after this number of iterations
     /// - `n_trials` : number of trial solutions to generate and evaluate at each iteration
     /// - `return_iter` : returns to the current best solution if there is no improvement after this number of iterations.
-    /// - `score_func` : score function to calculate transition probability.
-    pub fn new(patience: usize, n_trials: usize, return_iter: usize, score_func: FT) -> Self {
+    /// - `acceptance` : acceptance criterion, e.g. a score function to calculate transition probability.
+    pub fn new(patience: usize, n_trials: usize, return_iter: usize, acceptance: FT) -> Self {
         Self {
             patience,
             n_trials,
             return_iter,
-            score_func,
+            acceptance,
             phantom: PhantomData,
         }
     }
+}
 
+impl<ST: Ord + Sync + Send + Copy, FT: AcceptanceCriterion<ST> + Clone>
+    GenericLocalSearchOptimizer<ST, FT>
+{
     /// Start optimization, returns the best solution and last solution
     ///
     /// - `model` : the model to optimize
@@ -70,7 +111,11 @@ impl<ST: Ord + Sync + Send + Copy, FT: TransitionProbabilityFn<ST>>
     /// - `initial_score` : the initial score of the initial solution
     /// - `n_iter`: maximum iterations
     /// - `time_limit`: maximum iteration time
+    /// - `stop_condition`: additional condition to stop the optimization, checked at the end of each iteration
+    /// - `control`: handle to cancel, pause or inject solutions from other threads, if any
+    /// - `rng` : random number generator from which all randomness of this step is drawn
     /// - `callback` : callback function that will be invoked at the end of each iteration
+    #[allow(clippy::too_many_arguments)]
     pub fn step<M: OptModel<ScoreType = ST>>(
         &self,
         model: &M,
@@ -78,134 +123,648 @@ impl<ST: Ord + Sync + Send + Copy, FT: TransitionProbabilityFn<ST>>
         initial_score: M::ScoreType,
         n_iter: usize,
         time_limit: Duration,
-        callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
-    ) -> StepResult<M::SolutionType, M::ScoreType> {
-        let start_time = Instant::now();
-        let mut rng = rand::rng();
-        let mut current_solution = initial_solution;
-        let mut current_score = initial_score;
-        let best_solution = Rc::new(RefCell::new(current_solution.clone()));
-        let mut best_score = current_score;
-        let mut acceptance_counter = AcceptanceCounter::new(100);
-        // Separate stagnation counters: one for triggering a return to best, one for early stopping (patience)
-        let mut return_stagnation_counter = 0;
-        let mut patience_stagnation_counter = 0;
-
-        for it in 0..n_iter {
-            // 1. Update time and iteration counters
-            let duration = Instant::now().duration_since(start_time);
-            if duration > time_limit {
-                break;
+        stop_condition: &mut dyn StopCondition<M::ScoreType>,
+        control: Option<&OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: &mut dyn Rng,
+        mut callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
+    ) -> Result<StepResult<M::SolutionType, M::ScoreType>, LocalsearchError> {
+        self.step_with_observer(
+            model,
+            initial_solution,
+            initial_score,
+            n_iter,
+            time_limit,
+            stop_condition,
+            control,
+            rng,
+            &mut callback,
+        )
+    }
+
+    /// Same as [`GenericLocalSearchOptimizer::step`] but for an [`IncrementalOptModel`].
+    /// Trial transitions are evaluated against the borrowed current solution
+    /// and only the accepted one is applied in place.
+    #[allow(clippy::too_many_arguments)]
+    pub fn step_incremental<M: IncrementalOptModel<ScoreType = ST>>(
+        &self,
+        model: &M,
+        initial_solution: M::SolutionType,
+        initial_score: M::ScoreType,
+        n_iter: usize,
+        time_limit: Duration,
+        stop_condition: &mut dyn StopCondition<M::ScoreType>,
+        control: Option<&OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: &mut dyn Rng,
+        mut callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
+    ) -> Result<StepResult<M::SolutionType, M::ScoreType>, LocalsearchError> {
+        self.step_incremental_with_observer(
+            model,
+            initial_solution,
+            initial_score,
+            n_iter,
+            time_limit,
+            stop_condition,
+            control,
+            rng,
+            &mut callback,
+        )
+    }
+
+    /// Same as [`GenericLocalSearchOptimizer::step`], reporting events to `observer`
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn step_with_observer<M: OptModel<ScoreType = ST>>(
+        &self,
+        model: &M,
+        initial_solution: M::SolutionType,
+        initial_score: M::ScoreType,
+        n_iter: usize,
+        time_limit: Duration,
+        stop_condition: &mut dyn StopCondition<M::ScoreType>,
+        control: Option<&OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: &mut dyn Rng,
+        observer: &mut dyn OptObserver<M::SolutionType, M::ScoreType, M::TransitionType>,
+    ) -> Result<StepResult<M::SolutionType, M::ScoreType>, LocalsearchError> {
+        self.step_with(
+            initial_solution,
+            initial_score,
+            n_iter,
+            time_limit,
+            stop_condition,
+            control,
+            rng,
+            observer,
+            propose_solution(model),
+            |current_solution, trial_solution| *current_solution = trial_solution,
+        )
+    }
+
+    /// Same as [`GenericLocalSearchOptimizer::step_incremental`], reporting events to `observer`
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn step_incremental_with_observer<M: IncrementalOptModel<ScoreType = ST>>(
+        &self,
+        model: &M,
+        initial_solution: M::SolutionType,
+        initial_score: M::ScoreType,
+        n_iter: usize,
+        time_limit: Duration,
+        stop_condition: &mut dyn StopCondition<M::ScoreType>,
+        control: Option<&OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: &mut dyn Rng,
+        observer: &mut dyn OptObserver<M::SolutionType, M::ScoreType, M::TransitionType>,
+    ) -> Result<StepResult<M::SolutionType, M::ScoreType>, LocalsearchError> {
+        self.step_with(
+            initial_solution,
+            initial_score,
+            n_iter,
+            time_limit,
+            stop_condition,
+            control,
+            rng,
+            observer,
+            propose_transition(model),
+            |current_solution, transition| model.apply_transition(current_solution, &transition),
+        )
+    }
+
+    /// Core loop shared by [`GenericLocalSearchOptimizer::step`] and [`GenericLocalSearchOptimizer::step_incremental`],
+    /// running a [`GenericStepper`] until it stops.
+    ///
+    /// - `observer` : observer notified of the events of the loop
+    /// - `propose` : generates a trial candidate and its score from the current solution,
+    ///   [`LocalsearchError::NoValidMove`] rejecting the trial and other errors stopping the loop
+    /// - `materialize` : turns the current solution into the accepted candidate
+    #[allow(clippy::too_many_arguments)]
+    fn step_with<S, T, C, P, A>(
+        &self,
+        initial_solution: S,
+        initial_score: ST,
+        n_iter: usize,
+        time_limit: Duration,
+        stop_condition: &mut dyn StopCondition<ST>,
+        control: Option<&OptControl<S, ST, T>>,
+        rng: &mut dyn Rng,
+        observer: &mut dyn OptObserver<S, ST, T>,
+        propose: P,
+        materialize: A,
+    ) -> Result<StepResult<S, ST>, LocalsearchError>
+    where
+        S: Clone + Sync + Send,
+        C: Send,
+        P: Fn(&S, ST, &mut SmallRng) -> Result<(C, ST), LocalsearchError> + Sync,
+        A: FnMut(&mut S, C),
+    {
+        let mut stepper = GenericStepper::new(
+            self,
+            initial_solution,
+            initial_score,
+            n_iter,
+            time_limit,
+            Box::new(stop_condition),
+            control,
+            Box::new(rng),
+            propose,
+            materialize,
+        );
+        let stop_reason = loop {
+            if let Some(stop_reason) = stepper.iterate(observer)? {
+                break stop_reason;
             }
+        };
+        observer.on_stop(stepper.n_iterations, stop_reason);
+        Ok(stepper.into_step_result(stop_reason))
+    }
+}
+
+/// Trial candidate and its score, proposed from the current solution
+type Proposal<C, ST> = Result<(C, ST), LocalsearchError>;
+
+/// Generates a trial solution and its score from the current solution
+fn propose_solution<M: OptModel>(
+    model: &M,
+) -> impl Fn(&M::SolutionType, M::ScoreType, &mut SmallRng) -> Proposal<M::SolutionType, M::ScoreType>
++ Sync
++ '_ {
+    |current_solution, current_score, trial_rng| {
+        let (solution, _, score) =
+            model.generate_trial_solution(current_solution.clone(), current_score, trial_rng)?;
+        Ok((solution, score))
+    }
+}
+
+/// Proposes a transition and its score from the borrowed current solution
+fn propose_transition<M: IncrementalOptModel>(
+    model: &M,
+) -> impl Fn(&M::SolutionType, M::ScoreType, &mut SmallRng) -> Proposal<M::TransitionType, M::ScoreType>
++ Sync
++ '_ {
+    |current_solution, current_score, trial_rng| {
+        model.propose_transition(current_solution, current_score, trial_rng)
+    }
+}
+
+/// State of the loop of [`GenericLocalSearchOptimizer`], advanced one iteration at a time.
+/// Trial candidates of type `C` are generated by `propose` and accepted by `materialize`,
+/// see [`GenericLocalSearchOptimizer::step_with`].
+struct GenericStepper<'a, S, ST, T, C, FT, P, A> {
+    patience: usize,
+    n_trials: usize,
+    return_iter: usize,
+    n_iter: usize,
+    time_limit: Duration,
+    stop_condition: Box<dyn StopCondition<ST> + 'a>,
+    control: Option<&'a OptControl<S, ST, T>>,
+    rng: Box<dyn Rng + 'a>,
+    propose: P,
+    materialize: A,
+    start_time: Instant,
+    current_solution: S,
+    current_score: ST,
+    best_solution: Rc<RefCell<S>>,
+    best_score: ST,
+    acceptance: FT,
+    acceptance_counter: AcceptanceCounter,
+    // Separate stagnation counters: one for triggering a return to best, one for early stopping (patience)
+    return_stagnation_counter: usize,
+    patience_stagnation_counter: usize,
+    n_evaluations: usize,
+    n_iterations: usize,
+    injected: Option<(S, ST)>,
+    phantom: PhantomData<fn(C)>,
+}
 
-            let (trial_solution, trial_score) = (0..self.n_trials)
-                .into_par_iter()
-                .map(|_| {
-                    let mut rng = rand::rng();
-                    let (solution, _, score) = model.generate_trial_solution(
-                        current_solution.clone(),
-                        current_score,
-                        &mut rng,
-                    );
-                    (solution, score)
-                })
-                .min_by_key(|(_, score)| *score)
-                .unwrap();
-
-            // 2. Update best solution and score
-            if trial_score < best_score {
-                best_solution.replace(trial_solution.clone());
-                best_score = trial_score;
-                return_stagnation_counter = 0;
-                patience_stagnation_counter = 0;
-            } else {
-                return_stagnation_counter += 1;
-                patience_stagnation_counter += 1;
+impl<'a, S, ST, T, C, FT, P, A> GenericStepper<'a, S, ST, T, C, FT, P, A>
+where
+    S: Clone + Sync + Send,
+    ST: Ord + Sync + Send + Copy,
+    FT: AcceptanceCriterion<ST> + Clone,
+    C: Send,
+    P: Fn(&S, ST, &mut SmallRng) -> Result<(C, ST), LocalsearchError> + Sync,
+    A: FnMut(&mut S, C),
+{
+    /// Clone the criterion of `optimizer` and start from the initial solution,
+    /// or from the checkpoint restored through `control`, if any
+    #[allow(clippy::too_many_arguments)]
+    fn new(
+        optimizer: &GenericLocalSearchOptimizer<ST, FT>,
+        initial_solution: S,
+        initial_score: ST,
+        n_iter: usize,
+        time_limit: Duration,
+        stop_condition: Box<dyn StopCondition<ST> + 'a>,
+        control: Option<&'a OptControl<S, ST, T>>,
+        rng: Box<dyn Rng + 'a>,
+        propose: P,
+        materialize: A,
+    ) -> Self {
+        let mut acceptance = optimizer.acceptance.clone();
+        acceptance.initialize(initial_score);
+        let mut stepper = Self {
+            patience: optimizer.patience,
+            n_trials: optimizer.n_trials,
+            return_iter: optimizer.return_iter,
+            n_iter,
+            time_limit,
+            stop_condition,
+            control,
+            rng,
+            propose,
+            materialize,
+            start_time: Instant::now(),
+            best_solution: Rc::new(RefCell::new(initial_solution.clone())),
+            current_solution: initial_solution,
+            current_score: initial_score,
+            best_score: initial_score,
+            acceptance,
+            acceptance_counter: AcceptanceCounter::new(100),
+            return_stagnation_counter: 0,
+            patience_stagnation_counter: 0,
+            n_evaluations: 0,
+            n_iterations: 0,
+            injected: None,
+            phantom: PhantomData,
+        };
+
+        if let Some(control) = control {
+            // Continue from a checkpoint, if any
+            if let Some(checkpoint) = control.take_restored() {
+                stepper.restore(checkpoint);
             }
+            control.publish_best(&stepper.best_solution.borrow(), stepper.best_score);
+        }
+        stepper
+    }
 
-            // 3. Update accepted counter and transitions
-            let accepted = if trial_score < current_score {
-                true
-            } else {
-                let p = (self.score_func)(current_score, trial_score);
-                let r: f64 = rng.random();
-                p > r
-            };
+    /// Result of [`GenericLocalSearchOptimizer::step`], with the last solution
+    fn into_step_result(self, stop_reason: StopReason) -> StepResult<S, ST> {
+        let best_solution = (*self.best_solution.borrow()).clone();
+        StepResult {
+            best_solution,
+            best_score: self.best_score,
+            last_solution: self.current_solution,
+            last_score: self.current_score,
+            acceptance_counter: self.acceptance_counter,
+            n_evaluations: self.n_evaluations,
+            n_iterations: self.n_iterations,
+            elapsed: Instant::now().duration_since(self.start_time),
+            stop_reason,
+            algorithm_state: self.acceptance.algorithm_state(),
+        }
+    }
+}
 
-            acceptance_counter.enqueue(accepted);
+impl<S, ST, T, C, FT, P, A> OptStepper<S, ST, T> for GenericStepper<'_, S, ST, T, C, FT, P, A>
+where
+    S: Clone + Sync + Send,
+    ST: Ord + Sync + Send + Copy,
+    FT: AcceptanceCriterion<ST> + Clone,
+    C: Send,
+    P: Fn(&S, ST, &mut SmallRng) -> Result<(C, ST), LocalsearchError> + Sync,
+    A: FnMut(&mut S, C),
+{
+    fn iterate(
+        &mut self,
+        observer: &mut dyn OptObserver<S, ST, T>,
+    ) -> Result<Option<StopReason>, LocalsearchError> {
+        let it = self.n_iterations;
+        if it >= self.n_iter {
+            return Ok(Some(StopReason::MaxIterations));
+        }
 
-            // 4. Update current solution and score
-            if accepted {
-                current_solution = trial_solution;
-                current_score = trial_score;
+        // 0. Handle external control: pause, checkpoint and cancellation, then injected solution
+        if let Some(control) = self.control {
+            // time spent paused does not count toward the time limit
+            self.start_time += control.wait_while_paused();
+            if control.take_checkpoint_request() {
+                control.store_checkpoint(self.checkpoint());
+            }
+            if control.is_cancelled() {
+                return Ok(Some(StopReason::Cancelled));
             }
+        }
+        let injected = self
+            .injected
+            .take()
+            .or_else(|| self.control.and_then(OptControl::take_injected));
+        if let Some((solution, score)) = injected {
+            self.current_solution = solution;
+            self.current_score = score;
+            if score < self.best_score {
+                self.best_score = score;
+                self.best_solution.replace(self.current_solution.clone());
+                if let Some(control) = self.control {
+                    control.publish_best(&self.current_solution, score);
+                }
+                observer.on_improvement(it, &self.current_solution, score);
+                self.return_stagnation_counter = 0;
+                self.patience_stagnation_counter = 0;
+            }
+        }
 
-            // 5. Check and handle return to best
-            if return_stagnation_counter == self.return_iter {
-                current_solution = best_solution.borrow().clone();
-                current_score = best_score;
-                return_stagnation_counter = 0;
+        // 1. Update time and iteration counters
+        let duration = Instant::now().duration_since(self.start_time);
+        if duration > self.time_limit {
+            return Ok(Some(StopReason::TimeLimit));
+        }
+        self.n_iterations = it + 1;
+
+        let propose = &self.propose;
+        let current_solution = &self.current_solution;
+        let current_score = self.current_score;
+        let trials = split_rng(&mut *self.rng, self.n_trials)
+            .into_par_iter()
+            .map(|mut trial_rng| {
+                catch_panic(|| propose(current_solution, current_score, &mut trial_rng))
+            })
+            .collect();
+        let trial = best_trial(trials)?;
+        let trial_score = trial.as_ref().map(|(_, score)| *score);
+        self.n_evaluations += self.n_trials;
+
+        // 2. Update best score
+        // An improvement over the best is always accepted below,
+        // so the best solution is copied from the current one after step 4.
+        let improved = trial_score.is_some_and(|score| score < self.best_score);
+        if let Some(trial_score) = trial_score
+            && improved
+        {
+            self.best_score = trial_score;
+            self.return_stagnation_counter = 0;
+            self.patience_stagnation_counter = 0;
+        } else {
+            self.return_stagnation_counter += 1;
+            self.patience_stagnation_counter += 1;
+        }
+
+        // 3. Update accepted counter and transitions
+        let accepted = match trial_score {
+            // no valid move
+            None => false,
+            Some(trial_score) if trial_score < current_score => true,
+            Some(trial_score) => {
+                let p = self
+                    .acceptance
+                    .acceptance_probability(current_score, trial_score);
+                let r: f64 = self.rng.random();
+                p > r
             }
+        };
+
+        self.acceptance_counter.enqueue(accepted);
 
-            // 6. Check patience
-            if patience_stagnation_counter == self.patience {
-                break;
+        // 4. Update current solution and score
+        if accepted && let Some((trial_candidate, trial_score)) = trial {
+            (self.materialize)(&mut self.current_solution, trial_candidate);
+            self.current_score = trial_score;
+        }
+        if improved {
+            self.best_solution.replace(self.current_solution.clone());
+            if let Some(control) = self.control {
+                control.publish_best(&self.current_solution, self.current_score);
             }
+            observer.on_improvement(it, &self.current_solution, self.current_score);
+        }
+
+        // 5. Check and handle return to best
+        if self.return_stagnation_counter == self.return_iter {
+            self.current_solution = self.best_solution.borrow().clone();
+            self.current_score = self.best_score;
+            self.return_stagnation_counter = 0;
+            observer.on_return_to_best(it, self.best_score);
+        }
 
-            // 7. Update algorithm-specific state (none)
+        // 6. Check patience and stop condition
+        if self.patience_stagnation_counter == self.patience {
+            return Ok(Some(StopReason::Patience));
+        }
+        let stop_state = StopState {
+            iter: self.n_iterations,
+            elapsed: Instant::now().duration_since(self.start_time),
+            n_evaluations: self.n_evaluations,
+            current_score: self.current_score,
+            best_score: self.best_score,
+        };
+        if self.stop_condition.should_stop(&stop_state) {
+            return Ok(Some(StopReason::StopCondition));
+        }
 
-            // 8. Invoke callback
-            let progress = OptProgress::new(
-                it,
-                acceptance_counter.acceptance_ratio(),
-                best_solution.clone(),
-                best_score,
-            );
-            callback(progress);
+        // 7. Update algorithm-specific state: the acceptance criterion
+        // metrics report the state used during this iteration
+        let state = self.acceptance.algorithm_state();
+        self.acceptance.update(&AcceptanceState {
+            iter: it,
+            n_iter: self.n_iter,
+            elapsed_fraction: elapsed_fraction(stop_state.elapsed, self.time_limit),
+            acceptance_ratio: self.acceptance_counter.acceptance_ratio(),
+            stagnation: self.patience_stagnation_counter,
+            best_score: self.best_score,
+            current_score: self.current_score,
+        });
+        let updated_state = self.acceptance.algorithm_state();
+        if updated_state.n_reheats != state.n_reheats
+            && let Some(beta) = updated_state.beta
+        {
+            observer.on_reheat(it, beta);
+        }
+        if updated_state.beta != state.beta
+            && let Some(beta) = updated_state.beta
+        {
+            observer.on_temperature_update(it, beta);
         }
 
-        let best_solution = (*best_solution.borrow()).clone();
-        StepResult {
-            best_solution,
-            best_score,
-            last_solution: current_solution,
-            last_score: current_score,
-            acceptance_counter,
+        // 8. Notify observer
+        let progress = OptProgress {
+            iter: it,
+            acceptance_ratio: self.acceptance_counter.acceptance_ratio(),
+            solution: self.best_solution.clone(),
+            score: self.best_score,
+            current_score: self.current_score,
+            elapsed: stop_state.elapsed,
+            n_evaluations: self.n_evaluations,
+            metrics: OptMetrics {
+                beta: state.beta,
+                water_level: state.water_level,
+                n_reheats: updated_state.n_reheats,
+                ..Default::default()
+            },
+        };
+        observer.on_iteration(progress);
+        Ok(None)
+    }
+
+    fn n_iterations(&self) -> usize {
+        self.n_iterations
+    }
+
+    fn best_score(&self) -> ST {
+        self.best_score
+    }
+
+    fn checkpoint(&self) -> Checkpoint<S, ST, T> {
+        Checkpoint {
+            iter: self.n_iterations,
+            elapsed: Instant::now().duration_since(self.start_time),
+            n_evaluations: self.n_evaluations,
+            best_solution: self.best_solution.borrow().clone(),
+            best_score: self.best_score,
+            current: vec![(self.current_solution.clone(), self.current_score)],
+            acceptance_counter: self.acceptance_counter.clone(),
+            return_stagnation_counter: self.return_stagnation_counter,
+            patience_stagnation_counter: self.patience_stagnation_counter,
+            algorithm_state: self.acceptance.algorithm_state(),
+            tabu_list: Vec::new(),
+        }
+    }
+
+    fn restore(&mut self, checkpoint: Checkpoint<S, ST, T>) {
+        let now = Instant::now();
+        self.start_time = now.checked_sub(checkpoint.elapsed).unwrap_or(now);
+        self.n_iterations = checkpoint.iter;
+        self.n_evaluations = checkpoint.n_evaluations;
+        self.best_solution.replace(checkpoint.best_solution);
+        self.best_score = checkpoint.best_score;
+        if let Some((solution, score)) = checkpoint.current.into_iter().next() {
+            self.current_solution = solution;
+            self.current_score = score;
+        }
+        self.acceptance_counter = checkpoint.acceptance_counter;
+        self.return_stagnation_counter = checkpoint.return_stagnation_counter;
+        self.patience_stagnation_counter = checkpoint.patience_stagnation_counter;
+        self.acceptance.restore(&checkpoint.algorithm_state);
+    }
+
+    fn inject(&mut self, solution: S, score: ST) {
+        self.injected = Some((solution, score));
+    }
+
+    fn result(&self, stop_reason: StopReason) -> OptResult<S, ST> {
+        OptResult {
+            solution: self.best_solution.borrow().clone(),
+            score: self.best_score,
+            n_iterations: self.n_iterations,
+            n_evaluations: self.n_evaluations,
+            elapsed: Instant::now().duration_since(self.start_time),
+            acceptance_ratio: self.acceptance_counter.acceptance_ratio(),
+            stop_reason,
+            algorithm_state: self.acceptance.algorithm_state(),
         }
     }
 }
 
+/// Trials of an iteration that have a valid move, in order.
+/// [`LocalsearchError::NoValidMove`] trials are left out and any other error is returned.
+pub(crate) fn valid_trials<C>(
+    trials: Vec<Result<C, LocalsearchError>>,
+) -> Result<Vec<C>, LocalsearchError> {
+    let mut valid = Vec::with_capacity(trials.len());
+    for trial in trials {
+        match trial {
+            Ok(trial) => valid.push(trial),
+            Err(LocalsearchError::NoValidMove) => {}
+            Err(e) => return Err(e),
+        }
+    }
+    Ok(valid)
+}
+
+/// Best of the trials of an iteration, `None` if none has a valid move.
+/// Among equal scores the first trial is kept, so that the choice does not depend on rayon scheduling.
+fn best_trial<C, ST: Ord>(
+    trials: Vec<Result<(C, ST), LocalsearchError>>,
+) -> Result<Option<(C, ST)>, LocalsearchError> {
+    Ok(valid_trials(trials)?
+        .into_iter()
+        .reduce(|best, trial| if trial.1 < best.1 { trial } else { best }))
+}
+
 impl<ST, FT, M> LocalSearchOptimizer<M> for GenericLocalSearchOptimizer<ST, FT>
 where
     ST: Ord + Sync + Send + Copy,
-    FT: TransitionProbabilityFn<ST>,
+    FT: AcceptanceCriterion<ST> + Clone,
     M: OptModel<ScoreType = ST>,
 {
-    /// Start optimization
+    /// Start an optimization to be run step by step
     ///
     /// - `model` : the model to optimize
     /// - `initial_solution` : the initial solution to start optimization
     /// - `initial_score` : the initial score of the initial solution
     /// - `n_iter`: maximum iterations
     /// - `time_limit`: maximum iteration time
-    /// - `callback` : callback function that will be invoked at the end of each iteration
-    fn optimize(
+    /// - `stop_condition`: additional condition to stop the optimization, checked at the end of each iteration
+    /// - `control`: handle to cancel, pause or inject solutions from other threads, if any
+    /// - `rng` : random number generator from which all randomness is drawn
+    fn start_optimization<'a>(
         &self,
-        model: &M,
+        model: &'a M,
         initial_solution: M::SolutionType,
         initial_score: M::ScoreType,
         n_iter: usize,
         time_limit: Duration,
-        callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
-    ) -> (M::SolutionType, M::ScoreType) {
-        let step_result = self.step(
-            model,
+        stop_condition: Box<dyn StopCondition<M::ScoreType> + 'a>,
+        control: Option<&'a OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: Box<dyn Rng + 'a>,
+    ) -> Result<OptSession<'a, M>, LocalsearchError>
+    where
+        Self: 'a,
+    {
+        let stepper = GenericStepper::new(
+            self,
+            initial_solution,
+            initial_score,
+            n_iter,
+            time_limit,
+            stop_condition,
+            control,
+            rng,
+            propose_solution(model),
+            |current_solution, trial_solution| *current_solution = trial_solution,
+        );
+        Ok(OptSession::new(model, stepper))
+    }
+}
+
+impl<ST, FT, M> LocalSearchOptimizer<M>
+    for IncrementalOptimizer<GenericLocalSearchOptimizer<ST, FT>>
+where
+    ST: Ord + Sync + Send + Copy,
+    FT: AcceptanceCriterion<ST> + Clone,
+    M: IncrementalOptModel<ScoreType = ST>,
+{
+    /// Start an optimization to be run step by step, applying only accepted transitions to the current solution
+    ///
+    /// - `model` : the model to optimize
+    /// - `initial_solution` : the initial solution to start optimization
+    /// - `initial_score` : the initial score of the initial solution
+    /// - `n_iter`: maximum iterations
+    /// - `time_limit`: maximum iteration time
+    /// - `stop_condition`: additional condition to stop the optimization, checked at the end of each iteration
+    /// - `control`: handle to cancel, pause or inject solutions from other threads, if any
+    /// - `rng` : random number generator from which all randomness is drawn
+    fn start_optimization<'a>(
+        &self,
+        model: &'a M,
+        initial_solution: M::SolutionType,
+        initial_score: M::ScoreType,
+        n_iter: usize,
+        time_limit: Duration,
+        stop_condition: Box<dyn StopCondition<M::ScoreType> + 'a>,
+        control: Option<&'a OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: Box<dyn Rng + 'a>,
+    ) -> Result<OptSession<'a, M>, LocalsearchError>
+    where
+        Self: 'a,
+    {
+        let stepper = GenericStepper::new(
+            &self.optimizer,
             initial_solution,
             initial_score,
             n_iter,
             time_limit,
-            callback,
+            stop_condition,
+            control,
+            rng,
+            propose_transition(model),
+            |current_solution, transition| model.apply_transition(current_solution, &transition),
         );
-        (step_result.best_solution, step_result.best_score)
+        Ok(OptSession::new(model, stepper))
     }
 }
@@ -1,18 +1,71 @@
-use std::{cell::RefCell, rc::Rc};
+use rand::Rng;
 
-use ordered_float::NotNan;
-
-use super::{GenericLocalSearchOptimizer, base::LocalSearchOptimizer};
+use super::{
+    AcceptanceCriterion, AcceptanceState, GenericLocalSearchOptimizer, GreatDelugeConfig, Schedule,
+    ScheduleClock, base::LocalSearchOptimizer,
+};
 use crate::{
-    Duration, OptModel,
-    callback::{OptCallbackFn, OptProgress},
+    AlgorithmState, Duration, Energy, LocalsearchError, OptControl, OptModel, OptSession,
+    stop_condition::StopCondition,
 };
 
+/// Acceptance below a water level that is lowered from its initial value to the best score
+#[derive(Clone)]
+struct WaterLevel {
+    level_factor: f64,
+    initial_level: f64,
+    level: f64,
+    /// lowered fraction of the gap between the initial level and the best score
+    schedule: Schedule,
+}
+
+impl<ST: Energy> AcceptanceCriterion<ST> for WaterLevel {
+    fn acceptance_probability(&self, _current_score: ST, trial_score: ST) -> f64 {
+        if trial_score.energy() < self.level {
+            1.0
+        } else {
+            0.0
+        }
+    }
+
+    fn initialize(&mut self, initial_score: ST) {
+        // raise the level by a fraction of the magnitude, whatever the sign of the score
+        let energy = initial_score.energy();
+        self.initial_level = energy + energy.abs() * (self.level_factor - 1.0);
+        self.level = self.initial_level;
+    }
+
+    fn update(&mut self, state: &AcceptanceState<ST>) {
+        // Update water level using the current best score
+        let lowered = self.schedule.value_at(state);
+        let best_f = state.best_score.energy();
+        self.level = self.initial_level - (self.initial_level - best_f) * lowered;
+    }
+
+    fn algorithm_state(&self) -> AlgorithmState {
+        AlgorithmState {
+            water_level: Some(self.level),
+            initial_water_level: Some(self.initial_level),
+            ..Default::default()
+        }
+    }
+
+    // Continue lowering the water level of a checkpoint
+    fn restore(&mut self, state: &AlgorithmState) {
+        if let Some(initial_level) = state.initial_water_level {
+            self.initial_level = initial_level;
+        }
+        if let Some(level) = state.water_level {
+            self.level = level;
+        }
+    }
+}
+
 /// Optimizer that implements the Great Deluge Algorithm (GDA).
 /// Unlike probabilistic methods like simulated annealing, GDA uses a deterministic
 /// threshold ("water level") that decreases adaptively over iterations.
 /// A trial solution is accepted if its score is below or equal to the current water level.
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct GreatDelugeOptimizer {
     /// Patience: the optimizer will give up if there is no improvement after this many iterations
     patience: usize,
@@ -20,11 +73,19 @@ pub struct GreatDelugeOptimizer {
     n_trials: usize,
     /// Return to the current best solution if there is no improvement after this many iterations
     return_iter: usize,
-    /// Factor to initialize the water level as initial_score * level_factor
+    /// Factor to initialize the water level above the initial score, by `level_factor - 1` times its magnitude
     level_factor: f64,
+    /// Lowered fraction of the gap between the initial water level and the best score
+    level_schedule: Schedule,
 }
 
 impl GreatDelugeOptimizer {
+    /// Builder of the optimizer, starting from the default parameters of [`GreatDelugeConfig`]
+    /// and validating them in [`GreatDelugeConfig::build`]
+    pub fn builder() -> GreatDelugeConfig {
+        GreatDelugeConfig::default()
+    }
+
     /// Constructor for GreatDelugeOptimizer
     ///
     /// - `patience`: the optimizer will give up if there is no improvement after this many iterations
@@ -37,66 +98,70 @@ impl GreatDelugeOptimizer {
             n_trials,
             return_iter,
             level_factor,
+            level_schedule: Schedule::linear(0.0, 1.0),
         }
     }
+
+    /// Set the fraction of the gap between the initial water level and the best score by which the level is lowered,
+    /// going linearly from 0 to 1 by default
+    pub fn with_level_schedule(mut self, schedule: Schedule) -> Self {
+        self.level_schedule = schedule;
+        self
+    }
+
+    /// Measure the progress by which the water level is lowered with `clock`.
+    /// With [`ScheduleClock::ElapsedTime`], the level reaches the best score at the time limit.
+    pub fn with_clock(mut self, clock: ScheduleClock) -> Self {
+        self.level_schedule = self.level_schedule.with_clock(clock);
+        self
+    }
 }
 
-impl<M: OptModel<ScoreType = NotNan<f64>>> LocalSearchOptimizer<M> for GreatDelugeOptimizer {
-    /// Start optimization
+impl<M: OptModel<ScoreType: Energy>> LocalSearchOptimizer<M> for GreatDelugeOptimizer {
+    /// Start an optimization to be run step by step
     ///
     /// - `model`: the model to optimize
     /// - `initial_solution`: the initial solution to start optimization
     /// - `initial_score`: the initial score of the initial solution
     /// - `n_iter`: maximum iterations
     /// - `time_limit`: maximum iteration time
-    /// - `callback`: callback function that will be invoked at the end of each iteration
-    fn optimize(
+    /// - `stop_condition`: additional condition to stop the optimization, checked at the end of each iteration
+    /// - `control`: handle to cancel, pause or inject solutions from other threads, if any
+    /// - `rng`: random number generator from which all randomness is drawn
+    fn start_optimization<'a>(
         &self,
-        model: &M,
+        model: &'a M,
         initial_solution: M::SolutionType,
         initial_score: M::ScoreType,
         n_iter: usize,
         time_limit: Duration,
-        callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
-    ) -> (M::SolutionType, M::ScoreType) {
-        // Initialize water level
-        let initial_level = initial_score.into_inner() * self.level_factor;
-        let water_level = Rc::new(RefCell::new(initial_level));
-
-        let transition_fn = {
-            // Clone the Rc so the closure owns its water_level reference, similar to SimulatedAnnealingOptimizer
-            let water_level = Rc::clone(&water_level);
-            move |_current: NotNan<f64>, trial: NotNan<f64>| -> f64 {
-                let wl = *water_level.borrow();
-                if trial.into_inner() < wl { 1.0 } else { 0.0 }
-            }
-        };
-
+        stop_condition: Box<dyn StopCondition<M::ScoreType> + 'a>,
+        control: Option<&'a OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: Box<dyn Rng + 'a>,
+    ) -> Result<OptSession<'a, M>, LocalsearchError>
+    where
+        Self: 'a,
+    {
         let optimizer = GenericLocalSearchOptimizer::new(
             self.patience,
             self.n_trials,
             self.return_iter,
-            transition_fn,
+            WaterLevel {
+                level_factor: self.level_factor,
+                initial_level: 0.0,
+                level: 0.0,
+                schedule: self.level_schedule.clone(),
+            },
         );
-
-        let mut wrapped_callback = |progress: OptProgress<M::SolutionType, M::ScoreType>| {
-            // Update water level using the current best score from progress
-            let progress_ratio = (progress.iter as f64) / (n_iter as f64);
-            let best_f = progress.score.into_inner();
-            let new_level = initial_level - (initial_level - best_f) * progress_ratio;
-            water_level.replace(new_level);
-
-            // Call the original callback
-            callback(progress);
-        };
-
-        optimizer.optimize(
+        optimizer.start_optimization(
             model,
             initial_solution,
             initial_score,
             n_iter,
             time_limit,
-            &mut wrapped_callback,
+            stop_condition,
+            control,
+            rng,
         )
     }
 }
@@ -1,5 +1,9 @@
-use super::{EpsilonGreedyOptimizer, LocalSearchOptimizer};
-use crate::{Duration, OptModel, callback::OptCallbackFn};
+use rand::Rng;
+
+use super::{EpsilonGreedyOptimizer, HillClimbingConfig, LocalSearchOptimizer};
+use crate::{
+    Duration, LocalsearchError, OptControl, OptModel, OptSession, stop_condition::StopCondition,
+};
 
 /// Optimizer that implements simple hill climbing algorithm
 #[derive(Clone, Copy)]
@@ -9,6 +13,12 @@ pub struct HillClimbingOptimizer {
 }
 
 impl HillClimbingOptimizer {
+    /// Builder of the optimizer, starting from the default parameters of [`HillClimbingConfig`]
+    /// and validating them in [`HillClimbingConfig::build`]
+    pub fn builder() -> HillClimbingConfig {
+        HillClimbingConfig::default()
+    }
+
     /// - `patience` : the optimizer will give up
     ///   if there is no improvement of the score after this number of iterations
     /// - `n_trials` : number of trial solutions to generate and evaluate at each iteration
@@ -18,31 +28,40 @@ impl HillClimbingOptimizer {
 }
 
 impl<M: OptModel> LocalSearchOptimizer<M> for HillClimbingOptimizer {
-    /// Start optimization
+    /// Start an optimization to be run step by step
     ///
     /// - `model` : the model to optimize
     /// - `initial_solution` : the initial solution to start optimization
     /// - `initial_score` : the initial score of the initial solution
     /// - `n_iter`: maximum iterations
     /// - `time_limit`: maximum iteration time
-    /// - `callback` : callback function that will be invoked at the end of each iteration
-    fn optimize(
+    /// - `stop_condition`: additional condition to stop the optimization, checked at the end of each iteration
+    /// - `control`: handle to cancel, pause or inject solutions from other threads, if any
+    /// - `rng`: random number generator from which all randomness is drawn
+    fn start_optimization<'a>(
         &self,
-        model: &M,
+        model: &'a M,
         initial_solution: M::SolutionType,
         initial_score: M::ScoreType,
         n_iter: usize,
         time_limit: Duration,
-        callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
-    ) -> (M::SolutionType, M::ScoreType) {
+        stop_condition: Box<dyn StopCondition<M::ScoreType> + 'a>,
+        control: Option<&'a OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: Box<dyn Rng + 'a>,
+    ) -> Result<OptSession<'a, M>, LocalsearchError>
+    where
+        Self: 'a,
+    {
         let optimizer = EpsilonGreedyOptimizer::new(self.patience, self.n_trials, usize::MAX, 0.0);
-        optimizer.optimize(
+        optimizer.start_optimization(
             model,
             initial_solution,
             initial_score,
             n_iter,
             time_limit,
-            callback,
+            stop_condition,
+            control,
+            rng,
         )
     }
 }
@@ -0,0 +1,20 @@
+/// Wrapper that runs an optimizer on an [`IncrementalOptModel`](crate::IncrementalOptModel).
+///
+/// Trial moves are proposed and scored against the borrowed current solution,
+/// and only the accepted transition is applied in place, so the solution is never cloned for rejected candidates.
+/// It is available for [`GenericLocalSearchOptimizer`](super::GenericLocalSearchOptimizer),
+/// [`TabuSearchOptimizer`](super::TabuSearchOptimizer) and
+/// [`SimulatedAnnealingOptimizer`](super::SimulatedAnnealingOptimizer).
+#[derive(Clone, Copy, Debug)]
+pub struct IncrementalOptimizer<O> {
+    pub(crate) optimizer: O,
+}
+
+impl<O> IncrementalOptimizer<O> {
+    /// Constructor of IncrementalOptimizer
+    ///
+    /// - `optimizer` : the optimizer whose algorithm and parameters are used
+    pub fn new(optimizer: O) -> Self {
+        Self { optimizer }
+    }
+}
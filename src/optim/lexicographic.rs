@@ -0,0 +1,200 @@
+use rand::Rng;
+
+use super::{
+    AcceptanceCriterion, AcceptanceState, GenericLocalSearchOptimizer,
+    LexicographicAnnealingConfig, LocalSearchOptimizer, Schedule,
+};
+use crate::{
+    AlgorithmState, Duration, HardMediumSoftScore, LocalsearchError, OptControl, OptModel,
+    OptSession, stop_condition::StopCondition,
+};
+
+/// Metropolis acceptance over the lexicographic deltas of a [`HardMediumSoftScore`],
+/// for annealing-style search with [`LexicographicAnnealingOptimizer`] or [`GenericLocalSearchOptimizer`].
+///
+/// Each level has its own inverse temperature. The first level at which the trial score differs
+/// from the current one decides alone: a worsening `delta` at that level is accepted with probability
+/// `exp(-beta_level * delta)`, whatever the lower levels do. A larger `beta` for the hard level than
+/// for the soft one makes the search cross infeasible regions less often than it accepts soft worsening.
+///
+/// All levels are multiplied by the same factor given by a [`Schedule`], constant 1 by default.
+/// The soft level's inverse temperature is reported as `beta` and the three levels as
+/// `betas` (`[hard, medium, soft]`) of the [`AlgorithmState`].
+///
+/// Example
+///
+/// ```rust
+/// // the factor grows from 1 to 100, i.e. cools geometrically
+/// let acceptance = LexicographicAnnealing::new(10.0, 5.0, 1.0).with_schedule(Schedule::geometric(1.0, 100.0));
+/// let opt = GenericLocalSearchOptimizer::new(patience, n_trials, return_iter, acceptance);
+/// ```
+#[derive(Debug, Clone)]
+pub struct LexicographicAnnealing {
+    /// inverse temperatures `[hard, medium, soft]` at factor 1
+    initial_betas: [f64; 3],
+    /// current inverse temperatures `[hard, medium, soft]`
+    betas: [f64; 3],
+    /// factor of the inverse temperatures over the progress
+    schedule: Schedule,
+}
+
+impl LexicographicAnnealing {
+    /// Constructor of LexicographicAnnealing
+    ///
+    /// - `hard_beta` : inverse temperature of the hard level
+    /// - `medium_beta` : inverse temperature of the medium level
+    /// - `soft_beta` : inverse temperature of the soft level
+    pub fn new(hard_beta: f64, medium_beta: f64, soft_beta: f64) -> Self {
+        let betas = [hard_beta, medium_beta, soft_beta];
+        Self {
+            initial_betas: betas,
+            betas,
+            schedule: Schedule::constant(1.0),
+        }
+    }
+
+    /// Scale every level's inverse temperature by a schedule, e.g. to anneal
+    pub fn with_schedule(mut self, schedule: Schedule) -> Self {
+        self.schedule = schedule;
+        self
+    }
+
+    /// Current inverse temperatures `[hard, medium, soft]`
+    pub fn betas(&self) -> [f64; 3] {
+        self.betas
+    }
+
+    fn scale(&mut self, factor: f64) {
+        self.betas = self.initial_betas.map(|beta| beta * factor);
+    }
+}
+
+impl AcceptanceCriterion<HardMediumSoftScore> for LexicographicAnnealing {
+    fn acceptance_probability(
+        &self,
+        current_score: HardMediumSoftScore,
+        trial_score: HardMediumSoftScore,
+    ) -> f64 {
+        match current_score.deciding_level(&trial_score) {
+            Some((level, delta)) if delta > 0 => (-self.betas[level] * delta as f64).exp(),
+            _ => 1.0,
+        }
+    }
+
+    fn initialize(&mut self, _initial_score: HardMediumSoftScore) {
+        self.scale(self.schedule.value(0.0));
+    }
+
+    fn update(&mut self, state: &AcceptanceState<HardMediumSoftScore>) {
+        self.scale(self.schedule.value_at(state));
+    }
+
+    fn algorithm_state(&self) -> AlgorithmState {
+        AlgorithmState {
+            beta: Some(self.betas[2]),
+            betas: Some(self.betas.to_vec()),
+            ..Default::default()
+        }
+    }
+
+    fn restore(&mut self, state: &AlgorithmState) {
+        if let Some(betas) = &state.betas
+            && let Ok(betas) = <[f64; 3]>::try_from(betas.as_slice())
+        {
+            self.betas = betas;
+        }
+    }
+}
+
+/// Optimizer that anneals over a [`HardMediumSoftScore`] with [`LexicographicAnnealing`] acceptance,
+/// the counterpart of [`SimulatedAnnealingOptimizer`](super::SimulatedAnnealingOptimizer) for lexicographic scores
+#[derive(Clone)]
+pub struct LexicographicAnnealingOptimizer {
+    patience: usize,
+    n_trials: usize,
+    return_iter: usize,
+    acceptance: LexicographicAnnealing,
+}
+
+impl LexicographicAnnealingOptimizer {
+    /// Builder of the optimizer, starting from the default parameters of [`LexicographicAnnealingConfig`]
+    /// and validating them in [`LexicographicAnnealingConfig::build`]
+    pub fn builder() -> LexicographicAnnealingConfig {
+        LexicographicAnnealingConfig::default()
+    }
+
+    /// Constructor of LexicographicAnnealingOptimizer
+    ///
+    /// - `patience` : the optimizer will give up
+    ///   if there is no improvement of the score after this number of iterations
+    /// - `n_trials` : number of trial solutions to generate and evaluate at each iteration
+    /// - `return_iter` : returns to the current best solution if there is no improvement after this number of iterations
+    /// - `hard_beta`, `medium_beta`, `soft_beta` : inverse temperatures of the levels, see [`LexicographicAnnealing::new`]
+    pub fn new(
+        patience: usize,
+        n_trials: usize,
+        return_iter: usize,
+        hard_beta: f64,
+        medium_beta: f64,
+        soft_beta: f64,
+    ) -> Self {
+        Self {
+            patience,
+            n_trials,
+            return_iter,
+            acceptance: LexicographicAnnealing::new(hard_beta, medium_beta, soft_beta),
+        }
+    }
+
+    /// Scale every level's inverse temperature by a schedule, e.g. `Schedule::geometric(1.0, 100.0)` to anneal
+    pub fn with_schedule(mut self, schedule: Schedule) -> Self {
+        self.acceptance = self.acceptance.with_schedule(schedule);
+        self
+    }
+}
+
+impl<M: OptModel<ScoreType = HardMediumSoftScore>> LocalSearchOptimizer<M>
+    for LexicographicAnnealingOptimizer
+{
+    /// Start an optimization to be run step by step
+    ///
+    /// - `model` : the model to optimize
+    /// - `initial_solution` : the initial solution to start optimization
+    /// - `initial_score` : the initial score of the initial solution
+    /// - `n_iter`: maximum iterations
+    /// - `time_limit`: maximum iteration time
+    /// - `stop_condition`: additional condition to stop the optimization, checked at the end of each iteration
+    /// - `control`: handle to cancel, pause or inject solutions from other threads, if any
+    /// - `rng`: random number generator from which all randomness is drawn
+    fn start_optimization<'a>(
+        &self,
+        model: &'a M,
+        initial_solution: M::SolutionType,
+        initial_score: M::ScoreType,
+        n_iter: usize,
+        time_limit: Duration,
+        stop_condition: Box<dyn StopCondition<M::ScoreType> + 'a>,
+        control: Option<&'a OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: Box<dyn Rng + 'a>,
+    ) -> Result<OptSession<'a, M>, LocalsearchError>
+    where
+        Self: 'a,
+    {
+        let optimizer = GenericLocalSearchOptimizer::new(
+            self.patience,
+            self.n_trials,
+            self.return_iter,
+            self.acceptance.clone(),
+        );
+        optimizer.start_optimization(
+            model,
+            initial_solution,
+            initial_score,
+            n_iter,
+            time_limit,
+            stop_condition,
+            control,
+            rng,
+        )
+    }
+}
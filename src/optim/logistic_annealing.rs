@@ -1,7 +1,13 @@
-use ordered_float::NotNan;
+use rand::Rng;
 
-use super::{GenericLocalSearchOptimizer, LocalSearchOptimizer};
-use crate::{Duration, OptModel, callback::OptCallbackFn};
+use super::{
+    AcceptanceCriterion, AcceptanceState, GenericLocalSearchOptimizer, LocalSearchOptimizer,
+    LogisticAnnealingConfig, Schedule,
+};
+use crate::{
+    AlgorithmState, Duration, Energy, LocalsearchError, OptControl, OptModel, OptSession,
+    stop_condition::StopCondition,
+};
 
 fn transition_prob<T: Into<f64>>(current: T, trial: T, w: f64) -> f64 {
     let current = current.into();
@@ -13,6 +19,40 @@ fn transition_prob<T: Into<f64>>(current: T, trial: T, w: f64) -> f64 {
     2.0 / (1.0 + (w * d).exp())
 }
 
+/// Logistic acceptance with a scheduled weight `w`
+#[derive(Clone)]
+struct LogisticAcceptance {
+    w: f64,
+    schedule: Schedule,
+}
+
+impl<ST: Energy> AcceptanceCriterion<ST> for LogisticAcceptance {
+    fn acceptance_probability(&self, current_score: ST, trial_score: ST) -> f64 {
+        transition_prob(current_score.energy(), trial_score.energy(), self.w)
+    }
+
+    fn initialize(&mut self, _initial_score: ST) {
+        self.w = self.schedule.value(0.0);
+    }
+
+    fn update(&mut self, state: &AcceptanceState<ST>) {
+        self.w = self.schedule.value_at(state);
+    }
+
+    fn algorithm_state(&self) -> AlgorithmState {
+        AlgorithmState {
+            w: Some(self.w),
+            ..Default::default()
+        }
+    }
+
+    fn restore(&mut self, state: &AlgorithmState) {
+        if let Some(w) = state.w {
+            self.w = w;
+        }
+    }
+}
+
 /// Optimizer that implements logistic annealing algorithm
 /// In this model, unlike simulated annealing, whether accept the trial solution or not is calculated based on relative score difference
 ///
@@ -24,15 +64,21 @@ fn transition_prob<T: Into<f64>>(current: T, trial: T, w: f64) -> f64 {
 /// so the acceptance direction is correct for any sign of `current_score`.
 /// `current_score == 0` is clamped to `f64::EPSILON`, keeping the result finite
 /// and the acceptance direction intact (improvement accepted, worsening rejected).
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct LogisticAnnealingOptimizer {
     patience: usize,
     n_trials: usize,
     return_iter: usize,
-    w: f64,
+    w: Schedule,
 }
 
 impl LogisticAnnealingOptimizer {
+    /// Builder of the optimizer, starting from the default parameters of [`LogisticAnnealingConfig`]
+    /// and validating them in [`LogisticAnnealingConfig::build`]
+    pub fn builder() -> LogisticAnnealingConfig {
+        LogisticAnnealingConfig::default()
+    }
+
     /// Constructor of LogisticAnnealingOptimizer
     ///
     /// - `patience` : the optimizer will give up
@@ -45,43 +91,61 @@ impl LogisticAnnealingOptimizer {
             patience,
             n_trials,
             return_iter,
-            w,
+            w: Schedule::constant(w),
         }
     }
+
+    /// Replace the fixed `w` with a schedule, e.g. an increasing weight that makes worsening transitions rarer over time
+    pub fn with_w_schedule(mut self, schedule: Schedule) -> Self {
+        self.w = schedule;
+        self
+    }
 }
 
-impl<M: OptModel<ScoreType = NotNan<f64>>> LocalSearchOptimizer<M> for LogisticAnnealingOptimizer {
-    /// Start optimization
+impl<M: OptModel<ScoreType: Energy>> LocalSearchOptimizer<M> for LogisticAnnealingOptimizer {
+    /// Start an optimization to be run step by step
     ///
     /// - `model` : the model to optimize
     /// - `initial_solution` : the initial solution to start optimization
     /// - `initial_score` : the initial score of the initial solution
     /// - `n_iter`: maximum iterations
     /// - `time_limit`: maximum iteration time
-    /// - `callback` : callback function that will be invoked at the end of each iteration
-    fn optimize(
+    /// - `stop_condition`: additional condition to stop the optimization, checked at the end of each iteration
+    /// - `control`: handle to cancel, pause or inject solutions from other threads, if any
+    /// - `rng`: random number generator from which all randomness is drawn
+    fn start_optimization<'a>(
         &self,
-        model: &M,
+        model: &'a M,
         initial_solution: M::SolutionType,
         initial_score: M::ScoreType,
         n_iter: usize,
         time_limit: Duration,
-        callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
-    ) -> (M::SolutionType, M::ScoreType) {
+        stop_condition: Box<dyn StopCondition<M::ScoreType> + 'a>,
+        control: Option<&'a OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: Box<dyn Rng + 'a>,
+    ) -> Result<OptSession<'a, M>, LocalsearchError>
+    where
+        Self: 'a,
+    {
         let optimizer = GenericLocalSearchOptimizer::new(
             self.patience,
             self.n_trials,
             self.return_iter,
-            |current, trial| transition_prob(current, trial, self.w),
+            LogisticAcceptance {
+                w: self.w.value(0.0),
+                schedule: self.w.clone(),
+            },
         );
 
-        optimizer.optimize(
+        optimizer.start_optimization(
             model,
             initial_solution,
             initial_score,
             n_iter,
             time_limit,
-            callback,
+            stop_condition,
+            control,
+            rng,
         )
     }
 }
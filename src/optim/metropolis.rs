@@ -1,16 +1,55 @@
-use ordered_float::NotNan;
+use rand::Rng;
 use rayon::prelude::*;
 
-use super::{GenericLocalSearchOptimizer, LocalSearchOptimizer, generic::StepResult};
-use crate::{Duration, OptModel, callback::OptCallbackFn};
+use super::{
+    AcceptanceCriterion, AcceptanceState, GenericLocalSearchOptimizer, LocalSearchOptimizer,
+    MetropolisConfig, Schedule, generic::StepResult,
+};
+use crate::{
+    AlgorithmState, Duration, Energy, LocalsearchError, OptControl, OptModel, OptObserver,
+    OptSession,
+    callback::OptCallbackFn,
+    stop_condition::StopCondition,
+    utils::{catch_panic, split_rng},
+};
 
-pub fn metropolis_transition(beta: f64) -> impl Fn(NotNan<f64>, NotNan<f64>) -> f64 {
-    move |current: NotNan<f64>, trial: NotNan<f64>| {
-        let ds = trial - current;
-        if ds <= NotNan::new(0.0).unwrap() {
-            1.0
-        } else {
-            (-beta * ds.into_inner()).exp()
+pub fn metropolis_transition<ST: Energy>(beta: f64) -> impl Fn(ST, ST) -> f64 {
+    move |current: ST, trial: ST| {
+        let ds = current.energy_delta(&trial);
+        if ds <= 0.0 { 1.0 } else { (-beta * ds).exp() }
+    }
+}
+
+/// Metropolis criterion at a fixed or scheduled inverse temperature
+#[derive(Clone)]
+struct MetropolisAcceptance {
+    beta: f64,
+    schedule: Schedule,
+}
+
+impl<ST: Energy> AcceptanceCriterion<ST> for MetropolisAcceptance {
+    fn acceptance_probability(&self, current_score: ST, trial_score: ST) -> f64 {
+        metropolis_transition(self.beta)(current_score, trial_score)
+    }
+
+    fn initialize(&mut self, _initial_score: ST) {
+        self.beta = self.schedule.value(0.0);
+    }
+
+    fn update(&mut self, state: &AcceptanceState<ST>) {
+        self.beta = self.schedule.value_at(state);
+    }
+
+    fn algorithm_state(&self) -> AlgorithmState {
+        AlgorithmState {
+            beta: Some(self.beta),
+            ..Default::default()
+        }
+    }
+
+    fn restore(&mut self, state: &AlgorithmState) {
+        if let Some(beta) = state.beta {
+            self.beta = beta;
         }
     }
 }
@@ -27,50 +66,63 @@ pub(crate) fn calculate_temperature_from_acceptance_prob(
     -ln_prob / average_energy_diff.clamp(0.01, 100.0)
 }
 
-pub(crate) fn gather_energy_diffs<M: OptModel<ScoreType = NotNan<f64>>>(
+pub(crate) fn gather_energy_diffs<M: OptModel<ScoreType: Energy>, R: Rng>(
     model: &M,
     initial_solution_and_score: Option<(M::SolutionType, M::ScoreType)>,
     n_warmup: usize,
-) -> Vec<f64> {
-    let mut rng = rand::rng();
-    let (current_solution, current_score) =
-        initial_solution_and_score.unwrap_or(model.generate_random_solution(&mut rng).unwrap());
+    rng: &mut R,
+) -> Result<Vec<f64>, LocalsearchError> {
+    let (current_solution, current_score) = match initial_solution_and_score {
+        Some(solution_and_score) => solution_and_score,
+        None => catch_panic(|| model.generate_random_solution(rng))?,
+    };
 
-    let energy_diffs: Vec<f64> = (0..n_warmup)
+    let energy_diffs = split_rng(rng, n_warmup)
         .into_par_iter()
-        .filter_map(|_| {
-            let mut rng = rand::rng();
-            let (_, _, trial_score) =
-                model.generate_trial_solution(current_solution.clone(), current_score, &mut rng);
-            let ds = trial_score - current_score;
-            if ds > NotNan::new(0.0).unwrap() {
-                Some(ds.into_inner())
-            } else {
-                None
-            }
+        .map(|mut trial_rng| {
+            let trial = catch_panic(|| {
+                model.generate_trial_solution(
+                    current_solution.clone(),
+                    current_score,
+                    &mut trial_rng,
+                )
+            });
+            let trial_score = match trial {
+                Ok((_, _, trial_score)) => trial_score,
+                // trials without a valid move are left out of the warmup
+                Err(LocalsearchError::NoValidMove) => return Ok(None),
+                Err(e) => return Err(e),
+            };
+            let ds = current_score.energy_delta(&trial_score);
+            Ok(if ds > 0.0 { Some(ds) } else { None })
         })
-        .collect();
+        .collect::<Result<Vec<_>, _>>()?;
 
-    energy_diffs
+    Ok(energy_diffs.into_iter().flatten().collect())
 }
 
-/// Tune inverse temperature beta based on initial random trials
-pub fn tune_temperature<M: OptModel<ScoreType = NotNan<f64>>>(
+/// Tune inverse temperature beta based on initial random trials,
+/// returning the first error of the model other than [`LocalsearchError::NoValidMove`]
+pub fn tune_temperature<M: OptModel<ScoreType: Energy>, R: Rng>(
     model: &M,
     initial_solution_and_score: Option<(M::SolutionType, M::ScoreType)>,
     n_warmup: usize,
     target_prob: f64,
-) -> f64 {
-    let energy_diffs = gather_energy_diffs(model, initial_solution_and_score, n_warmup);
+    rng: &mut R,
+) -> Result<f64, LocalsearchError> {
+    let energy_diffs = gather_energy_diffs(model, initial_solution_and_score, n_warmup, rng)?;
     if energy_diffs.is_empty() {
-        1.0
+        Ok(1.0)
     } else {
-        calculate_temperature_from_acceptance_prob(&energy_diffs, target_prob)
+        Ok(calculate_temperature_from_acceptance_prob(
+            &energy_diffs,
+            target_prob,
+        ))
     }
 }
 
-/// Optimizer that implements the Metropolis algorithm with constant beta
-#[derive(Clone, Copy)]
+/// Optimizer that implements the Metropolis algorithm with constant or scheduled beta
+#[derive(Clone)]
 pub struct MetropolisOptimizer {
     /// The optimizer will give up if there is no improvement of the score after this number of iterations
     patience: usize,
@@ -79,10 +131,16 @@ pub struct MetropolisOptimizer {
     /// Returns to the best solution if there is no improvement after this number of iterations
     return_iter: usize,
     /// Inverse temperature (beta)
-    beta: f64,
+    beta: Schedule,
 }
 
 impl MetropolisOptimizer {
+    /// Builder of the optimizer, starting from the default parameters of [`MetropolisConfig`]
+    /// and validating them in [`MetropolisConfig::build`]
+    pub fn builder() -> MetropolisConfig {
+        MetropolisConfig::default()
+    }
+
     /// Constructor of MetropolisOptimizer
     ///
     /// - `patience` : the optimizer will give up
@@ -95,66 +153,120 @@ impl MetropolisOptimizer {
             patience,
             n_trials,
             return_iter,
-            beta,
+            beta: Schedule::constant(beta),
         }
     }
 
+    /// Replace the constant beta with a schedule, e.g. to anneal
+    pub fn with_beta_schedule(mut self, schedule: Schedule) -> Self {
+        self.beta = schedule;
+        self
+    }
+
+    /// Generic optimizer running the Metropolis criterion with the beta schedule
+    fn generic_optimizer<ST: Energy>(
+        &self,
+    ) -> GenericLocalSearchOptimizer<ST, MetropolisAcceptance> {
+        GenericLocalSearchOptimizer::new(
+            self.patience,
+            self.n_trials,
+            self.return_iter,
+            MetropolisAcceptance {
+                beta: self.beta.value(0.0),
+                schedule: self.beta.clone(),
+            },
+        )
+    }
+
     /// Perform one optimization step
-    pub fn step<M: OptModel<ScoreType = NotNan<f64>>>(
+    #[allow(clippy::too_many_arguments)]
+    pub fn step<M: OptModel<ScoreType: Energy>>(
         &self,
         model: &M,
         initial_solution: M::SolutionType,
         initial_score: M::ScoreType,
         n_iter: usize,
         time_limit: Duration,
-        callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
-    ) -> StepResult<M::SolutionType, M::ScoreType> {
-        let transition = |current: NotNan<f64>, trial: NotNan<f64>| {
-            metropolis_transition(self.beta)(current, trial)
-        };
-        let generic_optimizer = GenericLocalSearchOptimizer::new(
-            self.patience,
-            self.n_trials,
-            self.return_iter,
-            transition,
-        );
-        generic_optimizer.step(
+        stop_condition: &mut dyn StopCondition<M::ScoreType>,
+        control: Option<&OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: &mut dyn Rng,
+        mut callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
+    ) -> Result<StepResult<M::SolutionType, M::ScoreType>, LocalsearchError> {
+        self.step_with_observer(
+            model,
+            initial_solution,
+            initial_score,
+            n_iter,
+            time_limit,
+            stop_condition,
+            control,
+            rng,
+            &mut callback,
+        )
+    }
+
+    /// Same as [`MetropolisOptimizer::step`], reporting events to `observer`
+    #[allow(clippy::too_many_arguments)]
+    pub(crate) fn step_with_observer<M: OptModel<ScoreType: Energy>>(
+        &self,
+        model: &M,
+        initial_solution: M::SolutionType,
+        initial_score: M::ScoreType,
+        n_iter: usize,
+        time_limit: Duration,
+        stop_condition: &mut dyn StopCondition<M::ScoreType>,
+        control: Option<&OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: &mut dyn Rng,
+        observer: &mut dyn OptObserver<M::SolutionType, M::ScoreType, M::TransitionType>,
+    ) -> Result<StepResult<M::SolutionType, M::ScoreType>, LocalsearchError> {
+        self.generic_optimizer().step_with_observer(
             model,
             initial_solution,
             initial_score,
             n_iter,
             time_limit,
-            callback,
+            stop_condition,
+            control,
+            rng,
+            observer,
         )
     }
 }
 
-impl<M: OptModel<ScoreType = NotNan<f64>>> LocalSearchOptimizer<M> for MetropolisOptimizer {
-    /// Start optimization
+impl<M: OptModel<ScoreType: Energy>> LocalSearchOptimizer<M> for MetropolisOptimizer {
+    /// Start an optimization to be run step by step
     ///
     /// - `model` : the model to optimize
     /// - `initial_solution` : the initial solution to start optimization
     /// - `initial_score` : the initial score of the initial solution
     /// - `n_iter`: maximum iterations
     /// - `time_limit`: maximum iteration time
-    /// - `callback` : callback function that will be invoked at the end of each iteration
-    fn optimize(
+    /// - `stop_condition`: additional condition to stop the optimization, checked at the end of each iteration
+    /// - `control`: handle to cancel, pause or inject solutions from other threads, if any
+    /// - `rng`: random number generator from which all randomness is drawn
+    fn start_optimization<'a>(
         &self,
-        model: &M,
-        initial_solution: <M as OptModel>::SolutionType,
-        initial_score: <M as OptModel>::ScoreType,
+        model: &'a M,
+        initial_solution: M::SolutionType,
+        initial_score: M::ScoreType,
         n_iter: usize,
         time_limit: Duration,
-        callback: &mut dyn OptCallbackFn<<M as OptModel>::SolutionType, <M as OptModel>::ScoreType>,
-    ) -> (<M as OptModel>::SolutionType, <M as OptModel>::ScoreType) {
-        let step_result = self.step(
+        stop_condition: Box<dyn StopCondition<M::ScoreType> + 'a>,
+        control: Option<&'a OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: Box<dyn Rng + 'a>,
+    ) -> Result<OptSession<'a, M>, LocalsearchError>
+    where
+        Self: 'a,
+    {
+        self.generic_optimizer().start_optimization(
             model,
             initial_solution,
             initial_score,
             n_iter,
             time_limit,
-            callback,
-        );
-        (step_result.best_solution, step_result.best_score)
+            stop_condition,
+            control,
+            rng,
+        )
     }
 }
@@ -1,18 +1,21 @@
 use std::{cell::RefCell, num::NonZero, rc::Rc};
 
-use ordered_float::NotNan;
-use rand::RngExt as _;
+use rand::{Rng, RngExt as _};
 use rayon::prelude::*;
 
 use super::{
-    LocalSearchOptimizer,
+    EnergySample, LocalSearchOptimizer, ParallelTemperingConfig, TemperatureEstimator,
     generic::StepResult,
     metropolis::{calculate_temperature_from_acceptance_prob, gather_energy_diffs},
 };
 use crate::{
-    Duration, Instant, OptModel,
-    callback::{OptCallbackFn, OptProgress},
+    AlgorithmState, Checkpoint, Duration, Energy, Instant, LocalsearchError, OptControl, OptModel,
+    OptObserver, OptResult, OptSession, OptStepper, StopReason,
+    callback::{OptMetrics, OptProgress},
+    counter::AcceptanceCounter,
     optim::metropolis::MetropolisOptimizer,
+    stop_condition::{StopCondition, StopState},
+    utils::split_rng,
 };
 
 /// Parallel Tempering (Replica Exchange) optimizer
@@ -31,7 +34,15 @@ pub struct ParallelTemperingOptimizer {
 }
 
 impl ParallelTemperingOptimizer {
+    /// Builder of the optimizer, starting from the default parameters of [`ParallelTemperingConfig`]
+    /// and validating them in [`ParallelTemperingConfig::build`]
+    pub fn builder() -> ParallelTemperingConfig {
+        ParallelTemperingConfig::default()
+    }
+
     /// Create a ParallelTemperingOptimizer with explicit beta ladder
+    ///
+    /// Panics if `betas` is empty, [`Self::builder`] returns an error instead.
     pub fn new(
         patience: usize,
         n_trials: usize,
@@ -54,6 +65,7 @@ impl ParallelTemperingOptimizer {
     /// Helper to create geometric spaced betas
     ///
     /// Creates `n_replicas` betas geometrically spaced between `beta_min` and `beta_max`.
+    /// Panics if `n_replicas` is 0, [`Self::builder`] returns an error instead.
     pub fn with_geometric_betas(
         patience: usize,
         n_trials: usize,
@@ -91,22 +103,53 @@ impl ParallelTemperingOptimizer {
     /// - `n_warmup`: number of warmup iterations to gather energy differences
     /// - `target_max_prob`: target acceptance probability for the highest beta (coldest replica)
     /// - `target_min_prob`: target acceptance probability for the lowest beta (hottest replica)
-    pub fn tune_temperature<M: OptModel<ScoreType = NotNan<f64>>>(
+    /// - `rng`: random number generator used to draw the warmup trials
+    ///
+    /// Errors of the model other than [`LocalsearchError::NoValidMove`] are returned.
+    pub fn tune_temperature<M: OptModel<ScoreType: Energy>, R: Rng>(
         self,
         model: &M,
         initial_solution: Option<(M::SolutionType, M::ScoreType)>,
         n_warmup: usize,
         target_max_prob: f64,
         target_min_prob: f64,
-    ) -> Self {
-        let energy_diffs = gather_energy_diffs(model, initial_solution, n_warmup);
+        rng: &mut R,
+    ) -> Result<Self, LocalsearchError> {
+        let energy_diffs = gather_energy_diffs(model, initial_solution, n_warmup, rng)?;
         if energy_diffs.is_empty() {
-            return self;
+            return Ok(self);
         }
         let beta_max = calculate_temperature_from_acceptance_prob(&energy_diffs, target_max_prob);
         let beta_min = calculate_temperature_from_acceptance_prob(&energy_diffs, target_min_prob);
         let n_replicas = self.betas.len();
-        Self::with_geometric_betas(
+        Ok(Self::with_geometric_betas(
+            self.patience,
+            self.n_trials,
+            self.return_iter,
+            n_replicas,
+            beta_min,
+            beta_max,
+            self.update_frequency,
+        ))
+    }
+
+    /// Geometric ladder between the inverse temperatures estimated from `sample` by `hottest` and `coldest`,
+    /// keeping the number of replicas. Unchanged if the sample is empty.
+    ///
+    /// Returns [`LocalsearchError::InvalidParameter`] if the target acceptance of an estimator is not in (0, 1).
+    pub fn estimate_betas(
+        self,
+        sample: &EnergySample,
+        hottest: TemperatureEstimator,
+        coldest: TemperatureEstimator,
+    ) -> Result<Self, LocalsearchError> {
+        let (Some(beta_min), Some(beta_max)) =
+            (hottest.estimate(sample)?, coldest.estimate(sample)?)
+        else {
+            return Ok(self);
+        };
+        let n_replicas = self.betas.len();
+        Ok(Self::with_geometric_betas(
             self.patience,
             self.n_trials,
             self.return_iter,
@@ -114,146 +157,378 @@ impl ParallelTemperingOptimizer {
             beta_min,
             beta_max,
             self.update_frequency,
-        )
+        ))
     }
 }
 
-impl<M: OptModel<ScoreType = NotNan<f64>>> LocalSearchOptimizer<M> for ParallelTemperingOptimizer {
-    /// Start optimization
+impl<M: OptModel<ScoreType: Energy>> LocalSearchOptimizer<M> for ParallelTemperingOptimizer {
+    /// Start an optimization to be run step by step, one exchange round per step
     ///
     /// - `model`: the model to optimize
     /// - `initial_solution`: the initial solution to start optimization
     /// - `initial_score`: the initial score of the initial solution
     /// - `n_iter`: maximum iterations
     /// - `time_limit`: maximum iteration time
-    /// - `callback`: callback function that will be invoked at the end of each iteration
-    fn optimize(
+    /// - `stop_condition`: additional condition to stop the optimization, checked at the end of each iteration
+    /// - `control`: handle to cancel, pause or inject solutions from other threads, if any
+    /// - `rng`: random number generator from which all randomness is drawn
+    fn start_optimization<'a>(
         &self,
-        model: &M,
+        model: &'a M,
         initial_solution: M::SolutionType,
         initial_score: M::ScoreType,
         n_iter: usize,
         time_limit: Duration,
-        callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
-    ) -> (M::SolutionType, M::ScoreType) {
-        let start_time = Instant::now();
-        let mut rng = rand::rng();
+        stop_condition: Box<dyn StopCondition<M::ScoreType> + 'a>,
+        control: Option<&'a OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: Box<dyn Rng + 'a>,
+    ) -> Result<OptSession<'a, M>, LocalsearchError>
+    where
+        Self: 'a,
+    {
+        let betas = self.betas.clone();
+        let n_replicas = betas.len();
+        let mut stepper = ParallelTemperingStepper {
+            patience: self.patience,
+            n_trials: self.n_trials,
+            return_iter: self.return_iter,
+            update_frequency: self.update_frequency,
+            model,
+            n_iter,
+            time_limit,
+            stop_condition,
+            control,
+            rng,
+            start_time: Instant::now(),
+            // Initialize replicas: every replica starts from the provided initial solution
+            replicas: vec![(initial_solution.clone(), initial_score); n_replicas],
+            best_solution: Rc::new(RefCell::new(initial_solution)),
+            best_score: initial_score,
+            betas,
+            coldest: 0,
+            n_swaps_accepted: Vec::new(),
+            n_swap_rounds: 0,
+            iter: 0,
+            return_stagnation_counter: 0,
+            patience_stagnation_counter: 0,
+            n_evaluations: 0,
+            acceptance_ratio: 0.0,
+            injected: None,
+        };
+        stepper.reset_ladder_statistics();
 
-        let n_replicas = self.betas.len();
+        if let Some(control) = control {
+            // Continue from a checkpoint, if any, with its replicas and ladder
+            if let Some(checkpoint) = control.take_restored() {
+                stepper.restore(checkpoint);
+            }
+            control.publish_best(&stepper.best_solution.borrow(), stepper.best_score);
+        }
+        Ok(OptSession::new(model, stepper))
+    }
+}
+
+/// State of the loop of [`ParallelTemperingOptimizer`], advanced one exchange round at a time
+struct ParallelTemperingStepper<'a, M: OptModel> {
+    patience: usize,
+    n_trials: usize,
+    return_iter: usize,
+    update_frequency: NonZero<usize>,
+    model: &'a M,
+    n_iter: usize,
+    time_limit: Duration,
+    stop_condition: Box<dyn StopCondition<M::ScoreType> + 'a>,
+    control: Option<&'a OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+    rng: Box<dyn Rng + 'a>,
+    start_time: Instant,
+    replicas: Vec<(M::SolutionType, M::ScoreType)>,
+    best_solution: Rc<RefCell<M::SolutionType>>,
+    best_score: M::ScoreType,
+    betas: Vec<f64>,
+    coldest: usize,
+    // exchanges attempted and accepted between replicas i and i + 1 in this run
+    n_swaps_accepted: Vec<usize>,
+    n_swap_rounds: usize,
+    iter: usize,
+    return_stagnation_counter: usize,
+    patience_stagnation_counter: usize,
+    n_evaluations: usize,
+    acceptance_ratio: f64,
+    injected: Option<(M::SolutionType, M::ScoreType)>,
+}
 
-        // Initialize replicas: first replica uses provided initial solution
-        let mut replicas: Vec<(M::SolutionType, M::ScoreType)> =
-            vec![(initial_solution.clone(), initial_score,); n_replicas];
+impl<M: OptModel<ScoreType: Energy>> ParallelTemperingStepper<'_, M> {
+    /// Find the coldest replica and clear the exchange statistics of the ladder
+    fn reset_ladder_statistics(&mut self) {
+        self.coldest = (0..self.betas.len())
+            .max_by(|&i, &j| self.betas[i].total_cmp(&self.betas[j]))
+            .unwrap_or(0);
+        self.n_swaps_accepted = vec![0; self.betas.len() - 1];
+        self.n_swap_rounds = 0;
+    }
+}
+
+impl<M: OptModel<ScoreType: Energy>> OptStepper<M::SolutionType, M::ScoreType, M::TransitionType>
+    for ParallelTemperingStepper<'_, M>
+{
+    fn iterate(
+        &mut self,
+        observer: &mut dyn OptObserver<M::SolutionType, M::ScoreType, M::TransitionType>,
+    ) -> Result<Option<StopReason>, LocalsearchError> {
+        if self.iter >= self.n_iter {
+            return Ok(Some(StopReason::MaxIterations));
+        }
+        let n_replicas = self.betas.len();
+        let control = self.control;
 
-        let best_solution = Rc::new(RefCell::new(initial_solution.clone()));
-        let mut best_score = initial_score;
-        for (s, sc) in &replicas {
-            if *sc < best_score {
-                best_solution.replace(s.clone());
-                best_score = *sc;
+        // 0. Handle external control: pause, checkpoint and cancellation, then injected solution
+        if let Some(control) = control {
+            // time spent paused does not count toward the time limit
+            self.start_time += control.wait_while_paused();
+            if control.take_checkpoint_request() {
+                control.store_checkpoint(self.checkpoint());
+            }
+            if control.is_cancelled() {
+                return Ok(Some(StopReason::Cancelled));
             }
         }
+        // the injected solution replaces the coldest replica
+        let injected = self
+            .injected
+            .take()
+            .or_else(|| control.and_then(OptControl::take_injected));
+        if let Some((solution, score)) = injected {
+            if score < self.best_score {
+                self.best_score = score;
+                self.best_solution.replace(solution.clone());
+                if let Some(control) = control {
+                    control.publish_best(&solution, score);
+                }
+                observer.on_improvement(self.iter, &solution, score);
+                self.return_stagnation_counter = 0;
+                self.patience_stagnation_counter = 0;
+            }
+            self.replicas[self.coldest] = (solution, score);
+        }
 
-        let mut iter: usize = 0;
-        let mut return_stagnation_counter: usize = 0;
-        let mut patience_stagnation_counter: usize = 0;
+        let elapsed = Instant::now().duration_since(self.start_time);
+        if elapsed > self.time_limit {
+            return Ok(Some(StopReason::TimeLimit));
+        }
 
-        while iter < n_iter {
-            let elapsed = Instant::now().duration_since(start_time);
-            if elapsed > time_limit {
-                break;
-            }
+        // Run Metropolis on each replica in parallel
+        let n_trials = self.n_trials;
+        let update_freq = self.update_frequency.get();
+        // Replicas must not run past the deadline of the stop condition, if any
+        let mut time_remaining = self.time_limit.saturating_sub(elapsed);
+        if let Some(deadline) = self.stop_condition.deadline() {
+            time_remaining = time_remaining.min(deadline.saturating_duration_since(Instant::now()));
+        }
 
-            // Run Metropolis on each replica in parallel
-            let n_trials = self.n_trials;
-            let update_freq = self.update_frequency.get();
-            let time_remaining = time_limit.saturating_sub(elapsed);
-
-            // Keep a clone of current replicas for parallel processing
-            let step_results: Vec<StepResult<M::SolutionType, M::ScoreType>> = replicas
-                .par_iter()
-                .enumerate()
-                .map(|(idx, (sol, score))| {
-                    let m = MetropolisOptimizer::new(
-                        self.patience,
-                        n_trials,
-                        self.return_iter,
-                        self.betas[idx],
-                    );
-                    let mut cb = &mut |_p: OptProgress<M::SolutionType, M::ScoreType>| {};
-                    m.step(
-                        model,
-                        sol.clone(),
-                        *score,
-                        update_freq,
-                        time_remaining,
-                        &mut cb,
-                    )
-                })
-                .collect();
-
-            // 1. Update time and iteration counters
-            iter = iter.saturating_add(update_freq);
-
-            // 2. Update best solution and score based on step_results
-            let best_step_result = step_results.iter().min_by_key(|r| r.best_score).unwrap();
-            if best_step_result.best_score < best_score {
-                best_score = best_step_result.best_score;
-                best_solution.replace(best_step_result.best_solution.clone());
-                return_stagnation_counter = 0;
-                patience_stagnation_counter = 0;
-            } else {
-                return_stagnation_counter =
-                    return_stagnation_counter.saturating_add(update_freq);
-                patience_stagnation_counter =
-                    patience_stagnation_counter.saturating_add(update_freq);
-            }
+        // Keep a clone of current replicas for parallel processing
+        let (model, patience, return_iter, betas) =
+            (self.model, self.patience, self.return_iter, &self.betas);
+        let step_results: Vec<StepResult<M::SolutionType, M::ScoreType>> = self
+            .replicas
+            .par_iter()
+            .zip(split_rng(&mut *self.rng, n_replicas))
+            .enumerate()
+            .map(|(idx, ((sol, score), mut replica_rng))| {
+                let m = MetropolisOptimizer::new(patience, n_trials, return_iter, betas[idx]);
+                let mut cb = &mut |_p: OptProgress<M::SolutionType, M::ScoreType>| {};
+                m.step(
+                    model,
+                    sol.clone(),
+                    *score,
+                    update_freq,
+                    time_remaining,
+                    // cancellation also interrupts the running round
+                    &mut |_: &StopState<M::ScoreType>| control.is_some_and(|c| c.is_cancelled()),
+                    None,
+                    &mut replica_rng,
+                    &mut cb,
+                )
+            })
+            .collect::<Result<_, _>>()?;
 
-            // 3. Compute acceptance ratio
-            let acceptance_ratio = {
-                let mut sum = 0.0;
-                for r in step_results.iter() {
-                    sum += r.acceptance_counter.acceptance_ratio();
-                }
-                sum / n_replicas as f64
-            };
+        // 1. Update time and iteration counters
+        self.iter = self.iter.saturating_add(update_freq);
+        self.n_evaluations += step_results.iter().map(|r| r.n_evaluations).sum::<usize>();
 
-            // 4. Update current solution and score from step results
-            for (i, r) in step_results.into_iter().enumerate() {
-                replicas[i] = (r.last_solution, r.last_score);
+        // 2. Update best solution and score based on step_results
+        let best_step_result = step_results.iter().min_by_key(|r| r.best_score).unwrap();
+        if best_step_result.best_score < self.best_score {
+            self.best_score = best_step_result.best_score;
+            self.best_solution
+                .replace(best_step_result.best_solution.clone());
+            if let Some(control) = control {
+                control.publish_best(&best_step_result.best_solution, self.best_score);
             }
+            observer.on_improvement(self.iter, &best_step_result.best_solution, self.best_score);
+            self.return_stagnation_counter = 0;
+            self.patience_stagnation_counter = 0;
+        } else {
+            self.return_stagnation_counter =
+                self.return_stagnation_counter.saturating_add(update_freq);
+            self.patience_stagnation_counter =
+                self.patience_stagnation_counter.saturating_add(update_freq);
+        }
 
-            // 5. Check and handle return to best
-            if return_stagnation_counter >= self.return_iter {
-                let idx = rng.random_range(0..n_replicas);
-                replicas[idx] = ((*best_solution.borrow()).clone(), best_score);
-                return_stagnation_counter = 0;
+        // 3. Compute acceptance ratio
+        self.acceptance_ratio = {
+            let mut sum = 0.0;
+            for r in step_results.iter() {
+                sum += r.acceptance_counter.acceptance_ratio();
             }
+            sum / n_replicas as f64
+        };
 
-            // 6. Check patience
-            if patience_stagnation_counter >= self.patience {
-                break;
-            }
+        // 4. Update current solution and score from step results
+        for (i, r) in step_results.into_iter().enumerate() {
+            self.replicas[i] = (r.last_solution, r.last_score);
+        }
 
-            // 7. Algorithm-specific updates: attempt exchanges between adjacent replicas
-            for i in 0..(n_replicas - 1) {
-                let sc_i = replicas[i].1;
-                let sc_j = replicas[i + 1].1;
-                // p_swap = exp((beta_j - beta_i) * (E_j - E_i))
-                let exponent = (self.betas[i + 1] - self.betas[i]) * (sc_j - sc_i).into_inner();
-                let p_swap = exponent.exp();
-                let accept = p_swap >= 1.0 || rng.random::<f64>() < p_swap;
-                if accept {
-                    replicas.swap(i, i + 1);
-                }
+        // 5. Check and handle return to best
+        if self.return_stagnation_counter >= self.return_iter {
+            let idx = self.rng.random_range(0..n_replicas);
+            self.replicas[idx] = ((*self.best_solution.borrow()).clone(), self.best_score);
+            self.return_stagnation_counter = 0;
+            observer.on_return_to_best(self.iter, self.best_score);
+        }
+
+        // 6. Check patience and stop condition
+        if self.patience_stagnation_counter >= self.patience {
+            return Ok(Some(StopReason::Patience));
+        }
+        let stop_state = StopState {
+            iter: self.iter,
+            elapsed: Instant::now().duration_since(self.start_time),
+            n_evaluations: self.n_evaluations,
+            current_score: self
+                .replicas
+                .iter()
+                .map(|(_, sc)| *sc)
+                .min()
+                .unwrap_or(self.best_score),
+            best_score: self.best_score,
+        };
+        if self.stop_condition.should_stop(&stop_state) {
+            return Ok(Some(StopReason::StopCondition));
+        }
+
+        // 7. Algorithm-specific updates: attempt exchanges between adjacent replicas
+        self.n_swap_rounds += 1;
+        for i in 0..(n_replicas - 1) {
+            let sc_i = self.replicas[i].1;
+            let sc_j = self.replicas[i + 1].1;
+            // p_swap = exp((beta_j - beta_i) * (E_j - E_i))
+            let exponent = (self.betas[i + 1] - self.betas[i]) * sc_i.energy_delta(&sc_j);
+            let p_swap = exponent.exp();
+            let accept = p_swap >= 1.0 || self.rng.random::<f64>() < p_swap;
+            if accept {
+                self.replicas.swap(i, i + 1);
+                self.n_swaps_accepted[i] += 1;
             }
+            observer.on_replica_exchange(self.iter, i, accept);
+        }
 
-            // 8. Invoke callback
-            let progress =
-                OptProgress::new(iter, acceptance_ratio, best_solution.clone(), best_score);
-            callback(progress);
+        // 8. Notify observer
+        let replica_scores: Vec<f64> = self.replicas.iter().map(|(_, sc)| sc.energy()).collect();
+        let progress = OptProgress {
+            iter: self.iter,
+            acceptance_ratio: self.acceptance_ratio,
+            solution: self.best_solution.clone(),
+            score: self.best_score,
+            current_score: stop_state.current_score,
+            elapsed: stop_state.elapsed,
+            n_evaluations: self.n_evaluations,
+            metrics: OptMetrics {
+                betas: Some(self.betas.clone()),
+                replica_scores: Some(replica_scores),
+                swap_rates: Some(
+                    self.n_swaps_accepted
+                        .iter()
+                        .map(|&n| n as f64 / self.n_swap_rounds as f64)
+                        .collect(),
+                ),
+                ..Default::default()
+            },
+        };
+        observer.on_iteration(progress);
+        Ok(None)
+    }
+
+    fn n_iterations(&self) -> usize {
+        self.iter
+    }
+
+    fn best_score(&self) -> M::ScoreType {
+        self.best_score
+    }
+
+    fn checkpoint(&self) -> Checkpoint<M::SolutionType, M::ScoreType, M::TransitionType> {
+        Checkpoint {
+            iter: self.iter,
+            elapsed: Instant::now().duration_since(self.start_time),
+            n_evaluations: self.n_evaluations,
+            best_solution: self.best_solution.borrow().clone(),
+            best_score: self.best_score,
+            current: self.replicas.clone(),
+            // acceptances are only counted within a round
+            acceptance_counter: AcceptanceCounter::default(),
+            return_stagnation_counter: self.return_stagnation_counter,
+            patience_stagnation_counter: self.patience_stagnation_counter,
+            algorithm_state: AlgorithmState {
+                betas: Some(self.betas.clone()),
+                ..Default::default()
+            },
+            tabu_list: Vec::new(),
         }
+    }
 
-        (best_solution.borrow().clone(), best_score)
+    fn restore(
+        &mut self,
+        checkpoint: Checkpoint<M::SolutionType, M::ScoreType, M::TransitionType>,
+    ) {
+        let now = Instant::now();
+        self.start_time = now.checked_sub(checkpoint.elapsed).unwrap_or(now);
+        self.iter = checkpoint.iter;
+        self.n_evaluations = checkpoint.n_evaluations;
+        self.best_solution.replace(checkpoint.best_solution);
+        self.best_score = checkpoint.best_score;
+        if let Some(betas) = checkpoint.algorithm_state.betas
+            && !betas.is_empty()
+        {
+            self.betas = betas;
+        }
+        let n_replicas = self.betas.len();
+        self.replicas = if checkpoint.current.len() == n_replicas {
+            checkpoint.current
+        } else {
+            vec![(self.best_solution.borrow().clone(), self.best_score); n_replicas]
+        };
+        self.return_stagnation_counter = checkpoint.return_stagnation_counter;
+        self.patience_stagnation_counter = checkpoint.patience_stagnation_counter;
+        self.reset_ladder_statistics();
+    }
+
+    fn inject(&mut self, solution: M::SolutionType, score: M::ScoreType) {
+        self.injected = Some((solution, score));
+    }
+
+    fn result(&self, stop_reason: StopReason) -> OptResult<M::SolutionType, M::ScoreType> {
+        OptResult {
+            solution: self.best_solution.borrow().clone(),
+            score: self.best_score,
+            n_iterations: self.iter,
+            n_evaluations: self.n_evaluations,
+            elapsed: Instant::now().duration_since(self.start_time),
+            acceptance_ratio: self.acceptance_ratio,
+            stop_reason,
+            algorithm_state: AlgorithmState {
+                betas: Some(self.betas.clone()),
+                ..Default::default()
+            },
+        }
     }
 }
@@ -0,0 +1,118 @@
+use crate::ObjectiveVector;
+
+/// Set of mutually non-dominated solutions, bounded by pruning the most crowded ones.
+///
+/// The archive may grow up to `soft_limit` members, and is then pruned down to `hard_limit`
+/// by repeatedly removing the member with the smallest crowding distance,
+/// which keeps the extremes of the front and spreads the remaining members along it.
+#[derive(Debug, Clone)]
+pub struct ParetoArchive<S, SC> {
+    members: Vec<(S, SC)>,
+    hard_limit: usize,
+    soft_limit: usize,
+}
+
+impl<S, SC: ObjectiveVector> ParetoArchive<S, SC> {
+    /// Constructor of ParetoArchive
+    ///
+    /// - `hard_limit` : size of the archive after pruning
+    /// - `soft_limit` : size above which the archive is pruned, at least `hard_limit`
+    pub fn new(hard_limit: usize, soft_limit: usize) -> Self {
+        Self {
+            members: Vec::new(),
+            hard_limit,
+            soft_limit: soft_limit.max(hard_limit),
+        }
+    }
+
+    /// Archive that is never pruned
+    pub fn unbounded() -> Self {
+        Self::new(usize::MAX, usize::MAX)
+    }
+
+    /// Number of members
+    pub fn len(&self) -> usize {
+        self.members.len()
+    }
+
+    /// Whether the archive has no member
+    pub fn is_empty(&self) -> bool {
+        self.members.is_empty()
+    }
+
+    /// Members as `(solution, score)` pairs, in no particular order
+    pub fn members(&self) -> &[(S, SC)] {
+        &self.members
+    }
+
+    /// Members as `(solution, score)` pairs
+    pub fn into_members(self) -> Vec<(S, SC)> {
+        self.members
+    }
+
+    /// Members whose score dominates `score`
+    pub fn dominating<'a>(&'a self, score: &'a SC) -> impl Iterator<Item = &'a (S, SC)> + 'a {
+        self.members
+            .iter()
+            .filter(move |(_, member)| member.dominates(score))
+    }
+
+    /// Whether a member dominates `score` or has the same score
+    pub fn covers(&self, score: &SC) -> bool {
+        self.members
+            .iter()
+            .any(|(_, member)| member == score || member.dominates(score))
+    }
+
+    /// Add a solution unless it is covered by a member, removing the members it dominates
+    /// and pruning the archive if it exceeds the soft limit. Returns whether the solution was added.
+    pub fn insert(&mut self, solution: S, score: SC) -> bool {
+        if self.covers(&score) {
+            return false;
+        }
+        self.members.retain(|(_, member)| !score.dominates(member));
+        self.members.push((solution, score));
+        if self.members.len() > self.soft_limit {
+            self.prune(self.hard_limit);
+        }
+        true
+    }
+
+    /// Remove the most crowded members until at most `size` remain
+    pub fn prune(&mut self, size: usize) {
+        while self.members.len() > size {
+            let crowding = crowding_distances(&self.members);
+            let most_crowded = crowding
+                .iter()
+                .enumerate()
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(i, _)| i)
+                .unwrap();
+            self.members.swap_remove(most_crowded);
+        }
+    }
+}
+
+/// Crowding distance of each member: the sum over the objectives of the normalized gap
+/// between its neighbors, infinite for the extremes
+fn crowding_distances<S, SC: ObjectiveVector>(members: &[(S, SC)]) -> Vec<f64> {
+    let mut distances = vec![0.0; members.len()];
+    let Some((_, first)) = members.first() else {
+        return distances;
+    };
+    let mut order: Vec<usize> = (0..members.len()).collect();
+    for k in 0..first.n_objectives() {
+        let objective = |i: usize| members[i].1.objective(k);
+        order.sort_by(|&a, &b| objective(a).total_cmp(&objective(b)));
+        let (low, high) = (objective(order[0]), objective(order[order.len() - 1]));
+        distances[order[0]] = f64::INFINITY;
+        distances[order[order.len() - 1]] = f64::INFINITY;
+        if high <= low {
+            continue;
+        }
+        for w in order.windows(3) {
+            distances[w[1]] += (objective(w[2]) - objective(w[0])) / (high - low);
+        }
+    }
+    distances
+}
@@ -1,16 +1,20 @@
 use std::{cell::RefCell, num::NonZero, rc::Rc};
 
-use ordered_float::NotNan;
-use rand::{RngExt as _, distr::weighted::WeightedIndex, prelude::Distribution};
+use rand::{Rng, RngExt as _, distr::weighted::WeightedIndex, prelude::Distribution};
 use rayon::prelude::*;
 
 use super::{
-    LocalSearchOptimizer, metropolis, metropolis::tune_temperature,
+    EnergySample, LocalSearchOptimizer, PopulationAnnealingConfig, Schedule, ScheduleClock,
+    TemperatureEstimator, acceptance::elapsed_fraction, metropolis, metropolis::tune_temperature,
     simulated_annealing::tune_cooling_rate,
 };
 use crate::{
-    Duration, Instant, OptModel,
-    callback::{OptCallbackFn, OptProgress},
+    AlgorithmState, Checkpoint, Duration, Energy, Instant, LocalsearchError, OptControl, OptModel,
+    OptObserver, OptResult, OptSession, OptStepper, StopReason,
+    callback::{OptMetrics, OptProgress},
+    counter::AcceptanceCounter,
+    stop_condition::{StopCondition, StopState},
+    utils::{catch_panic, split_rng},
 };
 
 /// Optimizer that implements the population annealing algorithm
@@ -27,6 +31,8 @@ pub struct PopulationAnnealingOptimizer {
     initial_beta: f64,
     /// Cooling rate
     cooling_rate: f64,
+    /// Schedule of the inverse temperature replacing the cooling rate, if any
+    beta_schedule: Option<Schedule>,
     /// Non-zero number of steps to run each simulated annealing before updating the population
     update_frequency: NonZero<usize>,
     /// Number of simulated annealing processes to run in parallel
@@ -34,6 +40,12 @@ pub struct PopulationAnnealingOptimizer {
 }
 
 impl PopulationAnnealingOptimizer {
+    /// Builder of the optimizer, starting from the default parameters of [`PopulationAnnealingConfig`]
+    /// and validating them in [`PopulationAnnealingConfig::build`]
+    pub fn builder() -> PopulationAnnealingConfig {
+        PopulationAnnealingConfig::default()
+    }
+
     /// Constructor of PopulationAnnealingOptimizer
     ///
     /// - `patience` : the optimizer will give up
@@ -59,25 +71,36 @@ impl PopulationAnnealingOptimizer {
             return_iter,
             initial_beta,
             cooling_rate,
+            beta_schedule: None,
             update_frequency,
             population_size,
         }
     }
 
-    /// Tune initial inverse temperature by drawing random trials
-    pub fn tune_initial_temperature<M: OptModel<ScoreType = NotNan<f64>>>(
+    /// Set the inverse temperature from `schedule` after each population update, instead of multiplying it by the cooling rate
+    pub fn with_beta_schedule(mut self, schedule: Schedule) -> Self {
+        self.beta_schedule = Some(schedule);
+        self
+    }
+
+    /// Tune initial inverse temperature by drawing random trials from `rng`
+    ///
+    /// Errors of the model other than [`LocalsearchError::NoValidMove`] are returned.
+    pub fn tune_initial_temperature<M: OptModel<ScoreType: Energy>, R: Rng>(
         self,
         model: &M,
         initial_solution: Option<(M::SolutionType, M::ScoreType)>,
         n_warmup: usize,
         target_initial_prob: f64,
-    ) -> Self {
-        let tuned_beta = tune_temperature(model, initial_solution, n_warmup, target_initial_prob);
+        rng: &mut R,
+    ) -> Result<Self, LocalsearchError> {
+        let tuned_beta =
+            tune_temperature(model, initial_solution, n_warmup, target_initial_prob, rng)?;
 
-        Self {
+        Ok(Self {
             initial_beta: tuned_beta,
             ..self
-        }
+        })
     }
 
     /// Tune cooling rate to reach high inverse temperature (beta ~ 1e2) at the end of optimization
@@ -90,168 +113,484 @@ impl PopulationAnnealingOptimizer {
             ..self
         }
     }
+
+    /// Cool geometrically from self.initial_beta to a final beta of 1e2 at the end of the optimization measured by `clock`.
+    /// With [`ScheduleClock::ElapsedTime`], annealing completes when the time limit is reached.
+    pub fn tune_cooling_schedule(self, clock: ScheduleClock) -> Self {
+        let schedule = Schedule::geometric(self.initial_beta, 1e2).with_clock(clock);
+        self.with_beta_schedule(schedule)
+    }
+
+    /// Set the initial inverse temperature estimated from `sample` by `estimator`, unchanged if the sample is empty
+    ///
+    /// Returns [`LocalsearchError::InvalidParameter`] if the target acceptance of `estimator` is not in (0, 1).
+    pub fn estimate_initial_temperature(
+        self,
+        sample: &EnergySample,
+        estimator: TemperatureEstimator,
+    ) -> Result<Self, LocalsearchError> {
+        Ok(match estimator.estimate(sample)? {
+            Some(initial_beta) => Self {
+                initial_beta,
+                ..self
+            },
+            None => self,
+        })
+    }
+
+    /// Tune cooling rate to reach the final inverse temperature estimated from `sample` by `estimator`
+    /// after `n_iter` iterations, unchanged if the sample is empty
+    ///
+    /// Returns [`LocalsearchError::InvalidParameter`] if the target acceptance of `estimator` is not in (0, 1).
+    pub fn estimate_cooling_rate(
+        self,
+        sample: &EnergySample,
+        estimator: TemperatureEstimator,
+        n_iter: usize,
+    ) -> Result<Self, LocalsearchError> {
+        Ok(match estimator.estimate(sample)? {
+            Some(final_beta) => {
+                let cooling_rate = tune_cooling_rate(
+                    self.initial_beta,
+                    final_beta,
+                    n_iter / self.update_frequency.get(),
+                );
+                Self {
+                    cooling_rate,
+                    ..self
+                }
+            }
+            None => self,
+        })
+    }
+
+    /// Cool geometrically from self.initial_beta to the final inverse temperature estimated from `sample` by `estimator`
+    /// at the end of the optimization measured by `clock`, unchanged if the sample is empty
+    ///
+    /// Returns [`LocalsearchError::InvalidParameter`] if the target acceptance of `estimator` is not in (0, 1).
+    pub fn estimate_cooling_schedule(
+        self,
+        sample: &EnergySample,
+        estimator: TemperatureEstimator,
+        clock: ScheduleClock,
+    ) -> Result<Self, LocalsearchError> {
+        Ok(match estimator.estimate(sample)? {
+            Some(final_beta) => {
+                let schedule = Schedule::geometric(self.initial_beta, final_beta).with_clock(clock);
+                self.with_beta_schedule(schedule)
+            }
+            None => self,
+        })
+    }
 }
 
-impl<M: OptModel<ScoreType = NotNan<f64>>> LocalSearchOptimizer<M>
-    for PopulationAnnealingOptimizer
-{
-    /// Start optimization
+impl<M: OptModel<ScoreType: Energy>> LocalSearchOptimizer<M> for PopulationAnnealingOptimizer {
+    /// Start an optimization to be run step by step, one population update per step
     ///
     /// - `model`: the model to optimize
     /// - `initial_solution`: the initial solution to start optimization
     /// - `initial_score`: the initial score of the initial solution
     /// - `n_iter`: maximum iterations
     /// - `time_limit`: maximum iteration time
-    /// - `callback`: callback function that will be invoked at the end of each iteration
-    fn optimize(
+    /// - `stop_condition`: additional condition to stop the optimization, checked at the end of each iteration
+    /// - `control`: handle to cancel, pause or inject solutions from other threads, if any
+    /// - `rng`: random number generator from which all randomness is drawn
+    fn start_optimization<'a>(
         &self,
-        model: &M,
+        model: &'a M,
         initial_solution: M::SolutionType,
         initial_score: M::ScoreType,
         n_iter: usize,
         time_limit: Duration,
-        callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
-    ) -> (M::SolutionType, M::ScoreType) {
-        let start_time = Instant::now();
-        let mut rng = rand::rng();
-
-        // Initialize population with random solutions or copies of the initial solution
-        let mut population: Vec<(M::SolutionType, M::ScoreType)> =
-            Vec::with_capacity(self.population_size);
-
-        for _ in 0..self.population_size {
-            // Generate a random solution for other members
-            let (solution, _, score) =
-                model.generate_trial_solution(initial_solution.clone(), initial_score, &mut rng);
-            population.push((solution, score));
+        stop_condition: Box<dyn StopCondition<M::ScoreType> + 'a>,
+        control: Option<&'a OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        mut rng: Box<dyn Rng + 'a>,
+    ) -> Result<OptSession<'a, M>, LocalsearchError>
+    where
+        Self: 'a,
+    {
+        // Continue from a checkpoint, if any, with its population and beta
+        let checkpoint = control.and_then(|c| c.take_restored());
+
+        // Otherwise initialize population with neighbors or copies of the initial solution
+        let mut population = Vec::with_capacity(self.population_size);
+        while checkpoint.is_none() && population.len() < self.population_size {
+            // Generate a neighbor of the initial solution for other members
+            match catch_panic(|| {
+                model.generate_trial_solution(initial_solution.clone(), initial_score, &mut rng)
+            }) {
+                Ok((solution, _, score)) => population.push((solution, score)),
+                Err(LocalsearchError::NoValidMove) => {
+                    population.push((initial_solution.clone(), initial_score))
+                }
+                Err(e) => return Err(e),
+            }
         }
 
-        // Track the best solution found
-        let best_solution = Rc::new(RefCell::new(initial_solution.clone()));
-        let mut best_score = initial_score;
+        let mut stepper = PopulationAnnealingStepper {
+            patience: self.patience,
+            n_trials: self.n_trials,
+            return_iter: self.return_iter,
+            cooling_rate: self.cooling_rate,
+            beta_schedule: self.beta_schedule.clone(),
+            update_frequency: self.update_frequency,
+            population_size: self.population_size,
+            model,
+            n_iter,
+            time_limit,
+            stop_condition,
+            control,
+            rng,
+            start_time: Instant::now(),
+            best_solution: Rc::new(RefCell::new(initial_solution)),
+            best_score: initial_score,
+            population,
+            current_beta: self.initial_beta,
+            iter: 0,
+            // Separate counters for return-to-best and patience
+            return_stagnation_counter: 0,
+            patience_stagnation_counter: 0,
+            n_evaluations: 0,
+            acceptance_ratio: 0.0,
+            injected: None,
+        };
+        if let Some(checkpoint) = checkpoint {
+            stepper.restore(checkpoint);
+        }
 
         // Update the best solution if we found a better one in the initial population
-        for (solution, score) in &population {
-            if *score < best_score {
-                best_solution.replace(solution.clone());
-                best_score = *score;
+        for (solution, score) in &stepper.population {
+            if *score < stepper.best_score {
+                stepper.best_solution.replace(solution.clone());
+                stepper.best_score = *score;
             }
         }
 
-        let mut current_beta = self.initial_beta;
-        let mut iter = 0;
-        // Separate counters for return-to-best and patience
-        let mut return_stagnation_counter: usize = 0;
-        let mut patience_stagnation_counter: usize = 0;
-
-        // Main optimization loop
-        while iter < n_iter {
-            let duration = Instant::now().duration_since(start_time);
-            if duration > time_limit {
-                break;
+        if let Some(control) = control {
+            control.publish_best(&stepper.best_solution.borrow(), stepper.best_score);
+        }
+        Ok(OptSession::new(model, stepper))
+    }
+}
+
+/// State of the loop of [`PopulationAnnealingOptimizer`], advanced one population update at a time
+struct PopulationAnnealingStepper<'a, M: OptModel> {
+    patience: usize,
+    n_trials: usize,
+    return_iter: usize,
+    cooling_rate: f64,
+    beta_schedule: Option<Schedule>,
+    update_frequency: NonZero<usize>,
+    population_size: usize,
+    model: &'a M,
+    n_iter: usize,
+    time_limit: Duration,
+    stop_condition: Box<dyn StopCondition<M::ScoreType> + 'a>,
+    control: Option<&'a OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+    rng: Box<dyn Rng + 'a>,
+    start_time: Instant,
+    best_solution: Rc<RefCell<M::SolutionType>>,
+    best_score: M::ScoreType,
+    population: Vec<(M::SolutionType, M::ScoreType)>,
+    current_beta: f64,
+    iter: usize,
+    return_stagnation_counter: usize,
+    patience_stagnation_counter: usize,
+    n_evaluations: usize,
+    acceptance_ratio: f64,
+    injected: Option<(M::SolutionType, M::ScoreType)>,
+}
+
+impl<M: OptModel<ScoreType: Energy>> OptStepper<M::SolutionType, M::ScoreType, M::TransitionType>
+    for PopulationAnnealingStepper<'_, M>
+{
+    fn iterate(
+        &mut self,
+        observer: &mut dyn OptObserver<M::SolutionType, M::ScoreType, M::TransitionType>,
+    ) -> Result<Option<StopReason>, LocalsearchError> {
+        if self.iter >= self.n_iter {
+            return Ok(Some(StopReason::MaxIterations));
+        }
+        let control = self.control;
+
+        // 0. Handle external control: pause, checkpoint and cancellation, then injected solution
+        if let Some(control) = control {
+            // time spent paused does not count toward the time limit
+            self.start_time += control.wait_while_paused();
+            if control.take_checkpoint_request() {
+                control.store_checkpoint(self.checkpoint());
+            }
+            if control.is_cancelled() {
+                return Ok(Some(StopReason::Cancelled));
+            }
+        }
+        // the injected solution replaces the worst member
+        let injected = self
+            .injected
+            .take()
+            .or_else(|| control.and_then(OptControl::take_injected));
+        if let Some((solution, score)) = injected {
+            if score < self.best_score {
+                self.best_score = score;
+                self.best_solution.replace(solution.clone());
+                if let Some(control) = control {
+                    control.publish_best(&solution, score);
+                }
+                observer.on_improvement(self.iter, &solution, score);
+                self.return_stagnation_counter = 0;
+                self.patience_stagnation_counter = 0;
             }
+            let worst = self
+                .population
+                .iter()
+                .enumerate()
+                .max_by_key(|(_, (_, score))| *score)
+                .map(|(idx, _)| idx)
+                .unwrap_or(0);
+            self.population[worst] = (solution, score);
+        }
 
-            let metropolis = metropolis::MetropolisOptimizer::new(
-                self.patience,
-                self.n_trials,
-                self.return_iter,
-                current_beta,
-            );
-            let update_freq = self.update_frequency.get();
-
-            // Process each member of the population
-            let step_results = population
-                .par_iter()
-                .map(|(solution, score)| {
-                    // Run SA for n_population_update steps
-                    let temp_callback =
-                        &mut |_progress: OptProgress<M::SolutionType, M::ScoreType>| {};
-
-                    metropolis.step(
-                        model,
-                        solution.clone(),
-                        *score,
-                        update_freq,
-                        time_limit.saturating_sub(duration),
-                        temp_callback,
-                    )
-                })
-                .collect::<Vec<_>>();
-
-            // 1. Update time and iteration counters
-            iter = iter.saturating_add(update_freq);
-
-            // 2. Update best solution and score
-            let best_step_result = step_results.iter().min_by_key(|r| r.best_score).unwrap();
-            if best_step_result.best_score < best_score {
-                best_score = best_step_result.best_score;
-                best_solution.replace(best_step_result.best_solution.clone());
-                return_stagnation_counter = 0;
-                patience_stagnation_counter = 0;
-            } else {
-                return_stagnation_counter = return_stagnation_counter.saturating_add(update_freq);
-                patience_stagnation_counter =
-                    patience_stagnation_counter.saturating_add(update_freq);
+        let duration = Instant::now().duration_since(self.start_time);
+        if duration > self.time_limit {
+            return Ok(Some(StopReason::TimeLimit));
+        }
+
+        let metropolis = metropolis::MetropolisOptimizer::new(
+            self.patience,
+            self.n_trials,
+            self.return_iter,
+            self.current_beta,
+        );
+        let update_freq = self.update_frequency.get();
+        // Members must not run past the deadline of the stop condition, if any
+        let mut time_remaining = self.time_limit.saturating_sub(duration);
+        if let Some(deadline) = self.stop_condition.deadline() {
+            time_remaining = time_remaining.min(deadline.saturating_duration_since(Instant::now()));
+        }
+
+        // Process each member of the population
+        let model = self.model;
+        let step_results = self
+            .population
+            .par_iter()
+            .zip(split_rng(&mut *self.rng, self.population_size))
+            .map(|((solution, score), mut member_rng)| {
+                // Run SA for n_population_update steps
+                let temp_callback = &mut |_progress: OptProgress<M::SolutionType, M::ScoreType>| {};
+
+                metropolis.step(
+                    model,
+                    solution.clone(),
+                    *score,
+                    update_freq,
+                    time_remaining,
+                    // cancellation also interrupts the running round
+                    &mut |_: &StopState<M::ScoreType>| control.is_some_and(|c| c.is_cancelled()),
+                    None,
+                    &mut member_rng,
+                    temp_callback,
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        // 1. Update time and iteration counters
+        self.iter = self.iter.saturating_add(update_freq);
+        self.n_evaluations += step_results.iter().map(|r| r.n_evaluations).sum::<usize>();
+
+        // 2. Update best solution and score
+        let best_step_result = step_results.iter().min_by_key(|r| r.best_score).unwrap();
+        if best_step_result.best_score < self.best_score {
+            self.best_score = best_step_result.best_score;
+            self.best_solution
+                .replace(best_step_result.best_solution.clone());
+            if let Some(control) = control {
+                control.publish_best(&best_step_result.best_solution, self.best_score);
             }
+            observer.on_improvement(self.iter, &best_step_result.best_solution, self.best_score);
+            self.return_stagnation_counter = 0;
+            self.patience_stagnation_counter = 0;
+        } else {
+            self.return_stagnation_counter =
+                self.return_stagnation_counter.saturating_add(update_freq);
+            self.patience_stagnation_counter =
+                self.patience_stagnation_counter.saturating_add(update_freq);
+        }
 
-            // 3. Update accepted counter
-            let acceptance_ratio = step_results
+        // 3. Update accepted counter
+        self.acceptance_ratio = step_results
+            .iter()
+            .map(|r| r.acceptance_counter.acceptance_ratio())
+            .sum::<f64>()
+            / self.population_size as f64;
+
+        // 4. Update current solution and score (population updated in algo specific)
+
+        // 5. Check and handle return to best
+        if self.return_stagnation_counter >= self.return_iter {
+            // randomly select a member to revert
+            let idx = self.rng.random_range(0..self.population_size);
+            self.population[idx] = ((*self.best_solution.borrow()).clone(), self.best_score);
+            self.return_stagnation_counter = 0;
+            observer.on_return_to_best(self.iter, self.best_score);
+        }
+
+        // 6. Check patience and stop condition
+        if self.patience_stagnation_counter >= self.patience {
+            return Ok(Some(StopReason::Patience));
+        }
+        let stop_state = StopState {
+            iter: self.iter,
+            elapsed: Instant::now().duration_since(self.start_time),
+            n_evaluations: self.n_evaluations,
+            current_score: step_results
                 .iter()
-                .map(|r| r.acceptance_counter.acceptance_ratio())
-                .sum::<f64>()
-                / self.population_size as f64;
-
-            // 4. Update current solution and score (population updated in algo specific)
-
-            // 5. Check and handle return to best
-            if return_stagnation_counter >= self.return_iter {
-                // randomly select a member to revert
-                let idx = rng.random_range(0..self.population_size);
-                population[idx] = ((*best_solution.borrow()).clone(), best_score);
-                return_stagnation_counter = 0;
-            }
+                .map(|r| r.last_score)
+                .min()
+                .unwrap_or(self.best_score),
+            best_score: self.best_score,
+        };
+        if self.stop_condition.should_stop(&stop_state) {
+            return Ok(Some(StopReason::StopCondition));
+        }
 
-            // 6. Check patience
-            if patience_stagnation_counter >= self.patience {
-                break;
+        // 7. Update algorithm-specific state
+        self.current_beta = match &self.beta_schedule {
+            Some(schedule) => {
+                let elapsed_fraction = elapsed_fraction(stop_state.elapsed, self.time_limit);
+                schedule.value(schedule.clock().progress_of(
+                    self.iter,
+                    self.n_iter,
+                    elapsed_fraction,
+                ))
             }
+            None => self.current_beta * self.cooling_rate,
+        };
+        observer.on_temperature_update(self.iter, self.current_beta);
+        let new_population: Vec<(M::SolutionType, M::ScoreType)> = step_results
+            .into_iter()
+            .map(|r| (r.last_solution, r.last_score))
+            .collect();
 
-            // 7. Update algorithm-specific state
-            current_beta *= self.cooling_rate;
-            let new_population: Vec<(M::SolutionType, M::ScoreType)> = step_results
-                .into_iter()
-                .map(|r| (r.last_solution, r.last_score))
-                .collect();
-
-            // Population update: resample based on Boltzmann distribution weights
-            // Calculate weights for each solution based on the current temperature
-            let mut weights = Vec::new();
-            for &(_, score) in &new_population {
-                // Boltzmann factor: exp(-beta * score)
-                let boltzmann_factor = (-current_beta * score.into_inner()).exp().max(1e-8);
-                weights.push(boltzmann_factor);
-            }
+        // Population update: resample based on Boltzmann distribution weights
+        // Calculate weights for each solution based on the current temperature
+        // relative to the lowest energy, so that the factors neither overflow nor vanish
+        // whatever the scale and sign of the scores
+        let min_energy = new_population
+            .iter()
+            .map(|(_, score)| score.energy())
+            .fold(f64::INFINITY, f64::min);
+        let mut weights = Vec::new();
+        for &(_, score) in &new_population {
+            // Boltzmann factor: exp(-beta * (score - min_score))
+            let boltzmann_factor = (-self.current_beta * (score.energy() - min_energy))
+                .exp()
+                .max(1e-8);
+            weights.push(boltzmann_factor);
+        }
 
-            // normalize weights
-            let weight_sum: f64 = weights.iter().sum();
-            for w in &mut weights {
-                *w /= weight_sum;
-            }
+        // normalize weights
+        let weight_sum: f64 = weights.iter().sum();
+        for w in &mut weights {
+            *w /= weight_sum;
+        }
 
-            // Use stochastic universal sampling or roulette wheel sampling
-            let slice_sampler = WeightedIndex::new(&weights).unwrap();
-            (0..self.population_size).for_each(|i| {
-                let idx = slice_sampler.sample(&mut rng);
-                population[i] = new_population[idx].clone();
-            });
-
-            // 8. Invoke callback
-            let progress =
-                OptProgress::new(iter, acceptance_ratio, best_solution.clone(), best_score);
-            callback(progress);
+        // Use stochastic universal sampling or roulette wheel sampling
+        // the weights are invalid if the scores make them overflow, e.g. with infinite energies
+        let slice_sampler = WeightedIndex::new(&weights).map_err(LocalsearchError::model)?;
+        for i in 0..self.population_size {
+            let idx = slice_sampler.sample(&mut self.rng);
+            self.population[i] = new_population[idx].clone();
         }
+        observer.on_population_resample(self.iter, &weights);
 
-        let final_best_solution = (*best_solution.borrow()).clone();
-        (final_best_solution, best_score)
+        // 8. Notify observer
+        let progress = OptProgress {
+            iter: self.iter,
+            acceptance_ratio: self.acceptance_ratio,
+            solution: self.best_solution.clone(),
+            score: self.best_score,
+            current_score: stop_state.current_score,
+            elapsed: stop_state.elapsed,
+            n_evaluations: self.n_evaluations,
+            metrics: OptMetrics {
+                beta: Some(self.current_beta),
+                population_weights: Some(weights),
+                ..Default::default()
+            },
+        };
+        observer.on_iteration(progress);
+        Ok(None)
+    }
+
+    fn n_iterations(&self) -> usize {
+        self.iter
+    }
+
+    fn best_score(&self) -> M::ScoreType {
+        self.best_score
+    }
+
+    fn checkpoint(&self) -> Checkpoint<M::SolutionType, M::ScoreType, M::TransitionType> {
+        Checkpoint {
+            iter: self.iter,
+            elapsed: Instant::now().duration_since(self.start_time),
+            n_evaluations: self.n_evaluations,
+            best_solution: self.best_solution.borrow().clone(),
+            best_score: self.best_score,
+            current: self.population.clone(),
+            // acceptances are only counted within a round
+            acceptance_counter: AcceptanceCounter::default(),
+            return_stagnation_counter: self.return_stagnation_counter,
+            patience_stagnation_counter: self.patience_stagnation_counter,
+            algorithm_state: AlgorithmState {
+                beta: Some(self.current_beta),
+                ..Default::default()
+            },
+            tabu_list: Vec::new(),
+        }
+    }
+
+    /// Missing members are filled with copies of the best solution
+    fn restore(
+        &mut self,
+        checkpoint: Checkpoint<M::SolutionType, M::ScoreType, M::TransitionType>,
+    ) {
+        let now = Instant::now();
+        self.start_time = now.checked_sub(checkpoint.elapsed).unwrap_or(now);
+        self.iter = checkpoint.iter;
+        self.n_evaluations = checkpoint.n_evaluations;
+        self.best_solution.replace(checkpoint.best_solution);
+        self.best_score = checkpoint.best_score;
+        self.population = checkpoint.current;
+        self.population.truncate(self.population_size);
+        self.population.resize(
+            self.population_size,
+            (self.best_solution.borrow().clone(), self.best_score),
+        );
+        if let Some(beta) = checkpoint.algorithm_state.beta {
+            self.current_beta = beta;
+        }
+        self.return_stagnation_counter = checkpoint.return_stagnation_counter;
+        self.patience_stagnation_counter = checkpoint.patience_stagnation_counter;
+    }
+
+    fn inject(&mut self, solution: M::SolutionType, score: M::ScoreType) {
+        self.injected = Some((solution, score));
+    }
+
+    fn result(&self, stop_reason: StopReason) -> OptResult<M::SolutionType, M::ScoreType> {
+        OptResult {
+            solution: self.best_solution.borrow().clone(),
+            score: self.best_score,
+            n_iterations: self.iter,
+            n_evaluations: self.n_evaluations,
+            elapsed: Instant::now().duration_since(self.start_time),
+            acceptance_ratio: self.acceptance_ratio,
+            stop_reason,
+            algorithm_state: AlgorithmState {
+                beta: Some(self.current_beta),
+                ..Default::default()
+            },
+        }
     }
 }
@@ -0,0 +1,418 @@
+use ordered_float::NotNan;
+use rand::Rng;
+use rayon::prelude::*;
+
+use super::{LocalSearchOptimizer, ParetoArchive};
+use crate::{
+    Duration, Energy, LocalsearchError, ObjectiveVector, OptModel, OptResult,
+    utils::{catch_panic, split_rng},
+};
+
+/// Reduction of an [`ObjectiveVector`] to a single value to minimize
+#[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Scalarization {
+    /// `sum_k weights[k] * f_k`
+    WeightedSum(Vec<f64>),
+    /// `max_k weights[k] * |f_k - reference[k]|`, with `reference` typically the ideal point.
+    /// Unlike the weighted sum, it reaches the non-convex parts of the front.
+    Chebyshev {
+        /// weight of each objective
+        weights: Vec<f64>,
+        /// reference point the distances are measured from
+        reference: Vec<f64>,
+    },
+    /// `f_objective + penalty * sum_k max(0, f_k - bounds[k])`:
+    /// the objective subject to the other objectives staying below their bound, violations being penalized.
+    /// The bound of the optimized objective itself is ignored.
+    EpsilonConstraint {
+        /// index of the optimized objective
+        objective: usize,
+        /// upper bound of each objective
+        bounds: Vec<f64>,
+        /// multiplier of the bound violations
+        penalty: f64,
+    },
+}
+
+impl Scalarization {
+    /// Scalar value of `objectives`
+    pub fn value<SC: ObjectiveVector>(&self, objectives: &SC) -> f64 {
+        match self {
+            Self::WeightedSum(weights) => weights
+                .iter()
+                .enumerate()
+                .map(|(k, w)| w * objectives.objective(k))
+                .sum(),
+            Self::Chebyshev { weights, reference } => weights
+                .iter()
+                .zip(reference)
+                .enumerate()
+                .map(|(k, (w, z))| w * (objectives.objective(k) - z).abs())
+                .fold(f64::NEG_INFINITY, f64::max),
+            Self::EpsilonConstraint {
+                objective,
+                bounds,
+                penalty,
+            } => {
+                let violation: f64 = bounds
+                    .iter()
+                    .enumerate()
+                    .filter(|(k, _)| k != objective)
+                    .map(|(k, bound)| (objectives.objective(k) - bound).max(0.0))
+                    .sum();
+                objectives.objective(*objective) + penalty * violation
+            }
+        }
+    }
+
+    /// Score of `objectives` under this scalarization
+    pub fn score<SC: ObjectiveVector>(&self, objectives: SC) -> ScalarizedScore<SC> {
+        ScalarizedScore {
+            value: NotNan::new(self.value(&objectives)).expect("scalarized score is NaN"),
+            objectives,
+        }
+    }
+}
+
+/// Score of a [`ScalarizedModel`]: the scalar value of the objectives, which are kept alongside.
+///
+/// Scores are compared by the value, then by the objectives.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScalarizedScore<SC> {
+    value: NotNan<f64>,
+    objectives: SC,
+}
+
+impl<SC: ObjectiveVector> ScalarizedScore<SC> {
+    /// Scalar value of the objectives
+    pub fn value(&self) -> f64 {
+        self.value.into_inner()
+    }
+
+    /// Objectives of the model
+    pub fn objectives(&self) -> SC {
+        self.objectives
+    }
+}
+
+impl<SC: ObjectiveVector> Energy for ScalarizedScore<SC> {
+    fn energy(&self) -> f64 {
+        self.value()
+    }
+}
+
+/// Adapter optimizing a [`Scalarization`] of a model whose score is an [`ObjectiveVector`],
+/// so that single-objective optimizers can be applied to it
+#[derive(Debug)]
+pub struct ScalarizedModel<'a, M> {
+    model: &'a M,
+    scalarization: Scalarization,
+}
+
+impl<'a, M: OptModel<ScoreType: ObjectiveVector>> ScalarizedModel<'a, M> {
+    /// Minimize `scalarization` of the objectives of `model`
+    pub fn new(model: &'a M, scalarization: Scalarization) -> Self {
+        Self {
+            model,
+            scalarization,
+        }
+    }
+
+    /// Wrapped model
+    pub fn inner(&self) -> &M {
+        self.model
+    }
+
+    /// Scalarization of the objectives
+    pub fn scalarization(&self) -> &Scalarization {
+        &self.scalarization
+    }
+}
+
+impl<M: OptModel<ScoreType: ObjectiveVector>> OptModel for ScalarizedModel<'_, M> {
+    type ScoreType = ScalarizedScore<M::ScoreType>;
+    type SolutionType = M::SolutionType;
+    type TransitionType = M::TransitionType;
+
+    fn generate_random_solution<R: rand::Rng>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        let (solution, objectives) = self.model.generate_random_solution(rng)?;
+        Ok((solution, self.scalarization.score(objectives)))
+    }
+
+    fn generate_trial_solution<R: rand::Rng>(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+        rng: &mut R,
+    ) -> Result<(Self::SolutionType, Self::TransitionType, Self::ScoreType), LocalsearchError> {
+        let (solution, transition, objectives) =
+            self.model
+                .generate_trial_solution(current_solution, current_score.objectives, rng)?;
+        Ok((solution, transition, self.scalarization.score(objectives)))
+    }
+
+    fn preprocess_solution(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        let (solution, objectives) = self
+            .model
+            .preprocess_solution(current_solution, current_score.objectives)?;
+        Ok((solution, self.scalarization.score(objectives)))
+    }
+
+    fn postprocess_solution(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        let (solution, objectives) = self
+            .model
+            .postprocess_solution(current_solution, current_score.objectives)?;
+        Ok((solution, self.scalarization.score(objectives)))
+    }
+}
+
+/// Outcome of a [`ScalarizationSweep`]
+#[derive(Debug, Clone)]
+pub struct SweepResult<S, SC> {
+    /// mutually non-dominated `(solution, objectives)` pairs among the results of the runs, sorted by objectives
+    pub front: Vec<(S, SC)>,
+    /// result of the run of each subproblem, in the order of the subproblems
+    pub runs: Vec<OptResult<S, ScalarizedScore<SC>>>,
+}
+
+/// Result of the run of a subproblem of a sweep over `M`
+type SweepRun<M> =
+    OptResult<<M as OptModel>::SolutionType, ScalarizedScore<<M as OptModel>::ScoreType>>;
+
+/// Approximation of the Pareto front of a model whose score is an [`ObjectiveVector`]
+/// by running a single-objective [`LocalSearchOptimizer`] on a sequence of [`Scalarization`] subproblems.
+///
+/// Neighboring subproblems in the sequence have close optima, so each run is warm-started
+/// from the solution of a neighbor. The subproblems are solved in waves that run in parallel:
+/// the first and last ones from random solutions, then the middle of every interval between solved subproblems,
+/// starting from the solution at the lower end of the interval.
+/// The waves depend only on the number of subproblems, so a seeded sweep is reproducible.
+///
+/// Example
+///
+/// ```rust
+/// let sweep = ScalarizationSweep::chebyshev(vec![0.0, 0.0], 20);
+/// let opt = SimulatedAnnealingOptimizer::new(5000, 8, 200, 1.0, 0.99, NonZero::new(10).unwrap());
+/// let result = sweep.run(&model, &opt, 2000, Duration::from_secs(10))?;
+/// ```
+#[derive(Debug, Clone)]
+pub struct ScalarizationSweep {
+    subproblems: Vec<Scalarization>,
+}
+
+impl ScalarizationSweep {
+    /// Sweep over `subproblems`, in which consecutive subproblems are neighbors
+    pub fn new(subproblems: Vec<Scalarization>) -> Self {
+        Self { subproblems }
+    }
+
+    /// Weighted sums over the grid of weight vectors of [`Self::weight_grid`]
+    pub fn weighted_sum(n_objectives: usize, n_divisions: usize) -> Self {
+        Self::new(
+            Self::weight_grid(n_objectives, n_divisions)
+                .into_iter()
+                .map(Scalarization::WeightedSum)
+                .collect(),
+        )
+    }
+
+    /// Chebyshev distances to `reference` over the grid of weight vectors of [`Self::weight_grid`]
+    pub fn chebyshev(reference: Vec<f64>, n_divisions: usize) -> Self {
+        Self::new(
+            Self::weight_grid(reference.len(), n_divisions)
+                .into_iter()
+                .map(|weights| Scalarization::Chebyshev {
+                    weights,
+                    reference: reference.clone(),
+                })
+                .collect(),
+        )
+    }
+
+    /// Minimization of `objective` under each vector of upper bounds of `bounds`
+    pub fn epsilon_constraint(objective: usize, bounds: Vec<Vec<f64>>, penalty: f64) -> Self {
+        Self::new(
+            bounds
+                .into_iter()
+                .map(|bounds| Scalarization::EpsilonConstraint {
+                    objective,
+                    bounds,
+                    penalty,
+                })
+                .collect(),
+        )
+    }
+
+    /// Weight vectors whose components are multiples of `1 / n_divisions` summing to 1 (simplex lattice),
+    /// in lexicographic order of the weights from the last objective to the first,
+    /// e.g. `[1, 0], [0.5, 0.5], [0, 1]` for 2 objectives and 2 divisions
+    pub fn weight_grid(n_objectives: usize, n_divisions: usize) -> Vec<Vec<f64>> {
+        fn compositions(n_parts: usize, total: usize) -> Vec<Vec<usize>> {
+            if n_parts <= 1 {
+                return vec![vec![total]];
+            }
+            (0..=total)
+                .flat_map(|last| {
+                    compositions(n_parts - 1, total - last)
+                        .into_iter()
+                        .map(move |mut parts| {
+                            parts.push(last);
+                            parts
+                        })
+                })
+                .collect()
+        }
+        if n_objectives == 0 {
+            return Vec::new();
+        }
+        let n_divisions = n_divisions.max(1);
+        compositions(n_objectives, n_divisions)
+            .into_iter()
+            .map(|parts| {
+                parts
+                    .into_iter()
+                    .map(|part| part as f64 / n_divisions as f64)
+                    .collect()
+            })
+            .collect()
+    }
+
+    /// Subproblems of the sweep
+    pub fn subproblems(&self) -> &[Scalarization] {
+        &self.subproblems
+    }
+
+    /// Solve every subproblem with `optimizer`
+    ///
+    /// - `model` : the model to optimize
+    /// - `optimizer` : optimizer of the scalarized model
+    /// - `n_iter`: maximum iterations of each run
+    /// - `time_limit`: maximum iteration time of each run
+    pub fn run<'a, M, O>(
+        &self,
+        model: &'a M,
+        optimizer: &O,
+        n_iter: usize,
+        time_limit: Duration,
+    ) -> Result<SweepResult<M::SolutionType, M::ScoreType>, LocalsearchError>
+    where
+        M: OptModel<ScoreType: ObjectiveVector>,
+        O: LocalSearchOptimizer<ScalarizedModel<'a, M>> + Sync + ?Sized,
+    {
+        self.run_with_rng(model, optimizer, n_iter, time_limit, &mut rand::rng())
+    }
+
+    /// Solve every subproblem with `optimizer`, drawing every random number from `rng`
+    ///
+    /// - `model` : the model to optimize
+    /// - `optimizer` : optimizer of the scalarized model
+    /// - `n_iter`: maximum iterations of each run
+    /// - `time_limit`: maximum iteration time of each run
+    /// - `rng`: random number generator, split into one generator per subproblem
+    pub fn run_with_rng<'a, M, O>(
+        &self,
+        model: &'a M,
+        optimizer: &O,
+        n_iter: usize,
+        time_limit: Duration,
+        rng: &mut dyn Rng,
+    ) -> Result<SweepResult<M::SolutionType, M::ScoreType>, LocalsearchError>
+    where
+        M: OptModel<ScoreType: ObjectiveVector>,
+        O: LocalSearchOptimizer<ScalarizedModel<'a, M>> + Sync + ?Sized,
+    {
+        let mut rngs: Vec<_> = split_rng(rng, self.subproblems.len())
+            .into_iter()
+            .map(Some)
+            .collect();
+        let mut runs: Vec<Option<SweepRun<M>>> = vec![None; self.subproblems.len()];
+
+        for wave in self.waves() {
+            let tasks: Vec<_> = wave
+                .into_iter()
+                .map(|(i, neighbor)| {
+                    let warm_start = neighbor
+                        .and_then(|j| runs[j].as_ref())
+                        .map(|result| (result.solution.clone(), result.score.objectives()));
+                    (i, warm_start, rngs[i].take().unwrap())
+                })
+                .collect();
+            let results = tasks
+                .into_par_iter()
+                .map(|(i, warm_start, mut rng)| {
+                    let scalarized = ScalarizedModel::new(model, self.subproblems[i].clone());
+                    let initial_solution = warm_start.map(|(solution, objectives)| {
+                        (solution, scalarized.scalarization.score(objectives))
+                    });
+                    let result = catch_panic(|| {
+                        optimizer.run_with_rng(
+                            &scalarized,
+                            initial_solution,
+                            n_iter,
+                            time_limit,
+                            &mut rng,
+                            &mut |_| {},
+                        )
+                    })?;
+                    Ok((i, result))
+                })
+                .collect::<Result<Vec<_>, LocalsearchError>>()?;
+            for (i, result) in results {
+                runs[i] = Some(result);
+            }
+        }
+
+        let runs: Vec<_> = runs.into_iter().flatten().collect();
+        let mut archive = ParetoArchive::unbounded();
+        for result in &runs {
+            archive.insert(result.solution.clone(), result.score.objectives());
+        }
+        let mut front = archive.into_members();
+        front.sort_by_key(|(_, objectives)| *objectives);
+        Ok(SweepResult { front, runs })
+    }
+
+    /// Order of the runs: waves of `(subproblem, neighbor to warm-start from)` that can run in parallel
+    fn waves(&self) -> Vec<Vec<(usize, Option<usize>)>> {
+        let n = self.subproblems.len();
+        if n == 0 {
+            return Vec::new();
+        }
+        let mut waves = vec![vec![(0, None)]];
+        if n > 1 {
+            waves[0].push((n - 1, None));
+        }
+        let mut intervals = vec![(0, n - 1)];
+        while !intervals.is_empty() {
+            let mut wave = Vec::new();
+            let mut next = Vec::new();
+            for (low, high) in intervals {
+                if high - low < 2 {
+                    continue;
+                }
+                let middle = (low + high) / 2;
+                wave.push((middle, Some(low)));
+                next.push((low, middle));
+                next.push((middle, high));
+            }
+            if !wave.is_empty() {
+                waves.push(wave);
+            }
+            intervals = next;
+        }
+        waves
+    }
+}
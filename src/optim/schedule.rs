@@ -0,0 +1,196 @@
+use std::{f64::consts::PI, fmt, sync::Arc};
+
+use super::AcceptanceState;
+
+/// Measure of the progress of an optimization that drives a [`Schedule`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum ScheduleClock {
+    /// `iter / n_iter`
+    #[default]
+    Iterations,
+    /// elapsed fraction of the time limit, so that the schedule completes when the deadline hits
+    ElapsedTime,
+}
+
+impl ScheduleClock {
+    /// Progress of the optimization in [0, 1] at the state of an iteration
+    pub fn progress<ST>(self, state: &AcceptanceState<ST>) -> f64 {
+        self.progress_of(state.iter, state.n_iter, state.elapsed_fraction)
+    }
+
+    /// Progress at iteration `iter` of `n_iter`, with `elapsed_fraction` of the time limit elapsed
+    pub(crate) fn progress_of(self, iter: usize, n_iter: usize, elapsed_fraction: f64) -> f64 {
+        match self {
+            Self::Iterations if n_iter == 0 => 1.0,
+            Self::Iterations => (iter as f64 / n_iter as f64).min(1.0),
+            Self::ElapsedTime => elapsed_fraction,
+        }
+    }
+}
+
+#[derive(Clone)]
+struct CustomFn(Arc<dyn Fn(f64) -> f64 + Send + Sync>);
+
+impl fmt::Debug for CustomFn {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("CustomFn")
+    }
+}
+
+#[derive(Debug, Clone)]
+enum Curve {
+    Constant(f64),
+    Linear {
+        start: f64,
+        end: f64,
+    },
+    Geometric {
+        start: f64,
+        end: f64,
+    },
+    Logarithmic {
+        start: f64,
+        end: f64,
+        steepness: f64,
+    },
+    LundyMees {
+        start: f64,
+        end: f64,
+    },
+    Cosine {
+        start: f64,
+        end: f64,
+    },
+    Piecewise(Vec<(f64, f64)>),
+    Custom(CustomFn),
+}
+
+/// Schedule of a parameter (inverse temperature, epsilon, ...) over the progress of an optimization.
+///
+/// A schedule maps the progress `t` in [0, 1], measured by its [`ScheduleClock`], to the value of the parameter.
+/// Except for [`Schedule::constant`] and [`Schedule::from_fn`], it goes from `start` at `t = 0` to `end` at `t = 1`.
+///
+/// Example
+///
+/// ```rust
+/// // epsilon decaying from 0.3 to 0.01 until the time limit
+/// let schedule = Schedule::geometric(0.3, 0.01).with_clock(ScheduleClock::ElapsedTime);
+/// let opt = EpsilonGreedyOptimizer::new(patience, n_trials, return_iter, 0.3).with_epsilon_schedule(schedule);
+/// ```
+#[derive(Debug, Clone)]
+pub struct Schedule {
+    curve: Curve,
+    clock: ScheduleClock,
+}
+
+impl Schedule {
+    fn new(curve: Curve) -> Self {
+        Self {
+            curve,
+            clock: ScheduleClock::default(),
+        }
+    }
+
+    /// Constant `value`
+    pub fn constant(value: f64) -> Self {
+        Self::new(Curve::Constant(value))
+    }
+
+    /// `start + (end - start) * t`
+    pub fn linear(start: f64, end: f64) -> Self {
+        Self::new(Curve::Linear { start, end })
+    }
+
+    /// `start * (end / start)^t`, i.e. multiplied by a constant factor per step.
+    /// `start` and `end` must be nonzero and of the same sign.
+    pub fn geometric(start: f64, end: f64) -> Self {
+        Self::new(Curve::Geometric { start, end })
+    }
+
+    /// `start + (end - start) * ln(1 + steepness * t) / ln(1 + steepness)`.
+    /// Applied to an inverse temperature, this is the logarithmic cooling `T ∝ 1 / ln(1 + k)`,
+    /// where a larger `steepness` moves more of the change to the beginning.
+    pub fn logarithmic(start: f64, end: f64, steepness: f64) -> Self {
+        Self::new(Curve::Logarithmic {
+            start,
+            end,
+            steepness,
+        })
+    }
+
+    /// Lundy-Mees schedule `x <- x / (1 + gamma * x)`, with `gamma` chosen to reach `end`:
+    /// `1 / x` goes linearly from `1 / start` to `1 / end`.
+    /// Intended for a temperature, whose inverse then changes linearly.
+    pub fn lundy_mees(start: f64, end: f64) -> Self {
+        Self::new(Curve::LundyMees { start, end })
+    }
+
+    /// Half cosine from `start` to `end`, changing slowly at both ends
+    pub fn cosine(start: f64, end: f64) -> Self {
+        Self::new(Curve::Cosine { start, end })
+    }
+
+    /// Linear interpolation between `(t, value)` points, constant before the first and after the last point.
+    ///
+    /// Panics if `points` is empty.
+    pub fn piecewise(mut points: Vec<(f64, f64)>) -> Self {
+        assert!(!points.is_empty(), "piecewise schedule needs a point");
+        points.sort_by(|a, b| a.0.total_cmp(&b.0));
+        Self::new(Curve::Piecewise(points))
+    }
+
+    /// User-defined function of the progress `t`
+    pub fn from_fn<F>(f: F) -> Self
+    where
+        F: Fn(f64) -> f64 + Send + Sync + 'static,
+    {
+        Self::new(Curve::Custom(CustomFn(Arc::new(f))))
+    }
+
+    /// Set the clock measuring the progress, [`ScheduleClock::Iterations`] by default
+    pub fn with_clock(mut self, clock: ScheduleClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Clock measuring the progress
+    pub fn clock(&self) -> ScheduleClock {
+        self.clock
+    }
+
+    /// Value at progress `t`, clamped to [0, 1]
+    pub fn value(&self, t: f64) -> f64 {
+        let t = t.clamp(0.0, 1.0);
+        match &self.curve {
+            Curve::Constant(value) => *value,
+            Curve::Linear { start, end } => start + (end - start) * t,
+            Curve::Geometric { start, end } => start * (end / start).powf(t),
+            Curve::Logarithmic {
+                start,
+                end,
+                steepness,
+            } => start + (end - start) * (steepness * t).ln_1p() / steepness.ln_1p(),
+            Curve::LundyMees { start, end } => 1.0 / (1.0 / start + (1.0 / end - 1.0 / start) * t),
+            Curve::Cosine { start, end } => end + 0.5 * (start - end) * (1.0 + (PI * t).cos()),
+            Curve::Piecewise(points) => {
+                let i = points.partition_point(|&(x, _)| x <= t);
+                if i == 0 {
+                    return points[0].1;
+                }
+                if i == points.len() {
+                    return points[i - 1].1;
+                }
+                let (x0, y0) = points[i - 1];
+                let (x1, y1) = points[i];
+                y0 + (y1 - y0) * (t - x0) / (x1 - x0)
+            }
+            Curve::Custom(f) => (f.0)(t),
+        }
+    }
+
+    /// Value at the progress of `state`, measured by the clock of the schedule
+    pub fn value_at<ST>(&self, state: &AcceptanceState<ST>) -> f64 {
+        self.value(self.clock.progress(state))
+    }
+}
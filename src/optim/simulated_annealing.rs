@@ -1,14 +1,17 @@
-use std::{cell::RefCell, num::NonZero, rc::Rc};
+use std::num::NonZero;
 
-use ordered_float::NotNan;
+use rand::Rng;
 
 use super::{
-    GenericLocalSearchOptimizer, LocalSearchOptimizer,
+    AcceptanceCriterion, AcceptanceState, EnergySample, GenericLocalSearchOptimizer,
+    IncrementalOptimizer, LocalSearchOptimizer, Schedule, ScheduleClock, SimulatedAnnealingConfig,
+    TemperatureEstimator,
+    config::{positive_fraction, probability},
     metropolis::{metropolis_transition, tune_temperature},
 };
 use crate::{
-    Duration, OptModel,
-    callback::{OptCallbackFn, OptProgress},
+    AlgorithmState, Duration, Energy, IncrementalOptModel, LocalsearchError, OptControl, OptModel,
+    OptSession, stop_condition::StopCondition,
 };
 
 /// Tune cooling rate based on initial and final inverse temperatures and number of iterations
@@ -21,8 +24,134 @@ pub fn tune_cooling_rate(initial_beta: f64, final_beta: f64, n_iter: usize) -> f
     (final_beta / initial_beta).powf(1.0 / n_iter as f64)
 }
 
+/// Policy raising the temperature of [`SimulatedAnnealingOptimizer`] again when the search freezes.
+/// A reheat lowers the inverse temperature to `fraction * initial_beta`, from which cooling resumes,
+/// and only happens when the current inverse temperature is larger (colder) than that.
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum ReheatPolicy {
+    /// Reheat every `patience` iterations without improvement of the best score
+    Stagnation {
+        /// number of iterations without improvement before reheating
+        patience: NonZero<usize>,
+        /// reheated inverse temperature relative to the initial one
+        fraction: f64,
+    },
+    /// Cyclic annealing: restart from the initial inverse temperature every `period` iterations
+    Cyclic {
+        /// number of iterations of each annealing cycle
+        period: NonZero<usize>,
+    },
+    /// Reheat when the acceptance ratio falls below `threshold` at a temperature update
+    LowAcceptance {
+        /// acceptance ratio below which the temperature is raised
+        threshold: f64,
+        /// reheated inverse temperature relative to the initial one
+        fraction: f64,
+    },
+}
+
+impl ReheatPolicy {
+    /// Error if a parameter is out of its valid range
+    pub(super) fn validate(&self) -> Result<(), LocalsearchError> {
+        match *self {
+            Self::Stagnation { fraction, .. } => positive_fraction("fraction", fraction),
+            Self::Cyclic { .. } => Ok(()),
+            Self::LowAcceptance {
+                threshold,
+                fraction,
+            } => {
+                probability("threshold", threshold)?;
+                positive_fraction("fraction", fraction)
+            }
+        }
+    }
+
+    /// Reheated inverse temperature, if the policy reheats at the end of this iteration
+    fn reheated_beta<ST>(
+        &self,
+        state: &AcceptanceState<ST>,
+        initial_beta: f64,
+        cooled: bool,
+    ) -> Option<f64> {
+        let (reheat, fraction) = match *self {
+            Self::Stagnation { patience, fraction } => (
+                state.stagnation > 0 && state.stagnation.is_multiple_of(patience.get()),
+                fraction,
+            ),
+            Self::Cyclic { period } => ((state.iter + 1).is_multiple_of(period.get()), 1.0),
+            Self::LowAcceptance {
+                threshold,
+                fraction,
+            } => (cooled && state.acceptance_ratio < threshold, fraction),
+        };
+        reheat.then_some(fraction * initial_beta)
+    }
+}
+
+/// Metropolis criterion whose inverse temperature is multiplied by `cooling_rate`,
+/// or set from `schedule` if any, every `update_frequency` iterations,
+/// and raised again by the `reheat` policy, if any
+#[derive(Clone)]
+struct GeometricCooling {
+    beta: f64,
+    initial_beta: f64,
+    cooling_rate: f64,
+    schedule: Option<Schedule>,
+    update_frequency: NonZero<usize>,
+    reheat: Option<ReheatPolicy>,
+    n_reheats: usize,
+}
+
+impl<ST: Energy> AcceptanceCriterion<ST> for GeometricCooling {
+    fn acceptance_probability(&self, current_score: ST, trial_score: ST) -> f64 {
+        metropolis_transition(self.beta)(current_score, trial_score)
+    }
+
+    fn update(&mut self, state: &AcceptanceState<ST>) {
+        let cooled = state.iter.is_multiple_of(self.update_frequency.get()) && state.iter > 0;
+        if cooled {
+            self.beta = match &self.schedule {
+                Some(schedule) => schedule.value_at(state),
+                None => self.beta * self.cooling_rate,
+            };
+        }
+        // a schedule is followed as is, there is no cooling to resume after a reheat
+        if self.schedule.is_none()
+            && let Some(beta) = self
+                .reheat
+                .and_then(|policy| policy.reheated_beta(state, self.initial_beta, cooled))
+            && beta < self.beta
+        {
+            self.beta = beta;
+            self.n_reheats += 1;
+        }
+    }
+
+    fn algorithm_state(&self) -> AlgorithmState {
+        AlgorithmState {
+            beta: Some(self.beta),
+            n_reheats: self.reheat.map(|_| self.n_reheats),
+            ..Default::default()
+        }
+    }
+
+    fn restore(&mut self, state: &AlgorithmState) {
+        if let Some(beta) = state.beta {
+            self.beta = beta;
+        }
+        if let Some(n_reheats) = state.n_reheats {
+            self.n_reheats = n_reheats;
+        }
+    }
+}
+
 /// Optimizer that implements the simulated annealing algorithm
-#[derive(Clone, Copy)]
+#[derive(Clone)]
 pub struct SimulatedAnnealingOptimizer {
     /// The optimizer will give up if there is no improvement of the score after this number of iterations
     patience: usize,
@@ -34,11 +163,21 @@ pub struct SimulatedAnnealingOptimizer {
     initial_beta: f64,
     /// Cooling rate
     cooling_rate: f64,
+    /// Schedule of the inverse temperature replacing the cooling rate, if any
+    beta_schedule: Option<Schedule>,
     /// Non-zero number of steps after which temperature is updated
     update_frequency: NonZero<usize>,
+    /// Policy raising the temperature again when the search freezes, if any
+    reheat: Option<ReheatPolicy>,
 }
 
 impl SimulatedAnnealingOptimizer {
+    /// Builder of the optimizer, starting from the default parameters of [`SimulatedAnnealingConfig`]
+    /// and validating them in [`SimulatedAnnealingConfig::build`]
+    pub fn builder() -> SimulatedAnnealingConfig {
+        SimulatedAnnealingConfig::default()
+    }
+
     /// Constructor of SimulatedAnnealingOptimizer
     ///
     /// - `patience` : the optimizer will give up
@@ -62,28 +201,48 @@ impl SimulatedAnnealingOptimizer {
             return_iter,
             initial_beta,
             cooling_rate,
+            beta_schedule: None,
             update_frequency,
+            reheat: None,
         }
     }
 
+    /// Raise the temperature again according to `policy` when the search freezes.
+    /// Reheating applies to the cooling by the cooling rate; a schedule set by [`Self::with_beta_schedule`] is followed as is.
+    pub fn with_reheat(mut self, policy: ReheatPolicy) -> Self {
+        self.reheat = Some(policy);
+        self
+    }
+
+    /// Set the inverse temperature from `schedule` every `update_frequency` steps, instead of multiplying it by the cooling rate
+    pub fn with_beta_schedule(mut self, schedule: Schedule) -> Self {
+        self.beta_schedule = Some(schedule);
+        self
+    }
+
     /// Tune inverse temperature parameter beta based on initial random trials
     /// - `model` : the model to optimize
     /// - `initial_solution` : the initial solution to start optimization. If None, a random solution will be generated.
     /// - `n_warmup` : number of warmup iterations to run
     /// - `target_initial_prob` : target acceptance probability for uphill moves at the beginning
-    pub fn tune_initial_temperature<M: OptModel<ScoreType = NotNan<f64>>>(
+    /// - `rng` : random number generator used to draw the warmup trials
+    ///
+    /// Errors of the model other than [`LocalsearchError::NoValidMove`] are returned.
+    pub fn tune_initial_temperature<M: OptModel<ScoreType: Energy>, R: Rng>(
         self,
         model: &M,
         initial_solution: Option<(M::SolutionType, M::ScoreType)>,
         n_warmup: usize,
         target_initial_prob: f64,
-    ) -> Self {
-        let tuned_beta = tune_temperature(model, initial_solution, n_warmup, target_initial_prob);
+        rng: &mut R,
+    ) -> Result<Self, LocalsearchError> {
+        let tuned_beta =
+            tune_temperature(model, initial_solution, n_warmup, target_initial_prob, rng)?;
 
-        Self {
+        Ok(Self {
             initial_beta: tuned_beta,
             ..self
-        }
+        })
     }
 
     /// Tune cooling rate based on self.initial_beta, final beta of 1e2
@@ -96,54 +255,172 @@ impl SimulatedAnnealingOptimizer {
             ..self
         }
     }
+
+    /// Cool geometrically from self.initial_beta to a final beta of 1e2 at the end of the optimization measured by `clock`.
+    /// With [`ScheduleClock::ElapsedTime`], annealing completes when the time limit is reached,
+    /// so `n_iter` does not need to be known (e.g. `usize::MAX`).
+    pub fn tune_cooling_schedule(self, clock: ScheduleClock) -> Self {
+        let schedule = Schedule::geometric(self.initial_beta, 1e2).with_clock(clock);
+        self.with_beta_schedule(schedule)
+    }
+
+    /// Set the initial inverse temperature estimated from `sample` by `estimator`, unchanged if the sample is empty
+    ///
+    /// Returns [`LocalsearchError::InvalidParameter`] if the target acceptance of `estimator` is not in (0, 1).
+    pub fn estimate_initial_temperature(
+        self,
+        sample: &EnergySample,
+        estimator: TemperatureEstimator,
+    ) -> Result<Self, LocalsearchError> {
+        Ok(match estimator.estimate(sample)? {
+            Some(initial_beta) => Self {
+                initial_beta,
+                ..self
+            },
+            None => self,
+        })
+    }
+
+    /// Tune cooling rate to reach the final inverse temperature estimated from `sample` by `estimator`
+    /// after `n_iter` iterations, unchanged if the sample is empty
+    ///
+    /// Returns [`LocalsearchError::InvalidParameter`] if the target acceptance of `estimator` is not in (0, 1).
+    pub fn estimate_cooling_rate(
+        self,
+        sample: &EnergySample,
+        estimator: TemperatureEstimator,
+        n_iter: usize,
+    ) -> Result<Self, LocalsearchError> {
+        Ok(match estimator.estimate(sample)? {
+            Some(final_beta) => {
+                let cooling_rate = tune_cooling_rate(
+                    self.initial_beta,
+                    final_beta,
+                    n_iter / self.update_frequency.get(),
+                );
+                Self {
+                    cooling_rate,
+                    ..self
+                }
+            }
+            None => self,
+        })
+    }
+
+    /// Cool geometrically from self.initial_beta to the final inverse temperature estimated from `sample` by `estimator`
+    /// at the end of the optimization measured by `clock`, unchanged if the sample is empty
+    ///
+    /// Returns [`LocalsearchError::InvalidParameter`] if the target acceptance of `estimator` is not in (0, 1).
+    pub fn estimate_cooling_schedule(
+        self,
+        sample: &EnergySample,
+        estimator: TemperatureEstimator,
+        clock: ScheduleClock,
+    ) -> Result<Self, LocalsearchError> {
+        Ok(match estimator.estimate(sample)? {
+            Some(final_beta) => {
+                let schedule = Schedule::geometric(self.initial_beta, final_beta).with_clock(clock);
+                self.with_beta_schedule(schedule)
+            }
+            None => self,
+        })
+    }
 }
 
-impl<M: OptModel<ScoreType = NotNan<f64>>> LocalSearchOptimizer<M> for SimulatedAnnealingOptimizer {
-    /// Start optimization
+impl SimulatedAnnealingOptimizer {
+    /// Generic optimizer running the Metropolis criterion with the cooling schedule
+    fn generic_optimizer<ST: Energy>(&self) -> GenericLocalSearchOptimizer<ST, GeometricCooling> {
+        GenericLocalSearchOptimizer::new(
+            self.patience,
+            self.n_trials,
+            self.return_iter,
+            GeometricCooling {
+                beta: self.initial_beta,
+                initial_beta: self.initial_beta,
+                cooling_rate: self.cooling_rate,
+                schedule: self.beta_schedule.clone(),
+                update_frequency: self.update_frequency,
+                reheat: self.reheat,
+                n_reheats: 0,
+            },
+        )
+    }
+}
+
+impl<M: OptModel<ScoreType: Energy>> LocalSearchOptimizer<M> for SimulatedAnnealingOptimizer {
+    /// Start an optimization to be run step by step
     ///
     /// - `model` : the model to optimize
     /// - `initial_solution` : the initial solution to start optimization
     /// - `initial_score` : the initial score of the initial solution
     /// - `n_iter`: maximum iterations
     /// - `time_limit`: maximum iteration time
-    /// - `callback` : callback function that will be invoked at the end of each iteration
-    fn optimize(
+    /// - `stop_condition`: additional condition to stop the optimization, checked at the end of each iteration
+    /// - `control`: handle to cancel, pause or inject solutions from other threads, if any
+    /// - `rng`: random number generator from which all randomness is drawn
+    fn start_optimization<'a>(
         &self,
-        model: &M,
+        model: &'a M,
         initial_solution: M::SolutionType,
         initial_score: M::ScoreType,
         n_iter: usize,
         time_limit: Duration,
-        callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
-    ) -> (M::SolutionType, M::ScoreType) {
-        let current_beta = Rc::new(RefCell::new(self.initial_beta));
-        let transition = {
-            let current_beta = Rc::clone(&current_beta);
-            move |current: NotNan<f64>, trial: NotNan<f64>| {
-                metropolis_transition(*current_beta.borrow())(current, trial)
-            }
-        };
-        let mut callback_with_update = |progress: OptProgress<M::SolutionType, M::ScoreType>| {
-            if progress.iter % self.update_frequency.get() == 0 && progress.iter > 0 {
-                let new_beta = *current_beta.borrow() * self.cooling_rate;
-                current_beta.replace(new_beta);
-            }
-            callback(progress);
-        };
+        stop_condition: Box<dyn StopCondition<M::ScoreType> + 'a>,
+        control: Option<&'a OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: Box<dyn Rng + 'a>,
+    ) -> Result<OptSession<'a, M>, LocalsearchError>
+    where
+        Self: 'a,
+    {
+        self.generic_optimizer().start_optimization(
+            model,
+            initial_solution,
+            initial_score,
+            n_iter,
+            time_limit,
+            stop_condition,
+            control,
+            rng,
+        )
+    }
+}
 
-        let generic_optimizer = GenericLocalSearchOptimizer::new(
-            self.patience,
-            self.n_trials,
-            self.return_iter,
-            transition,
-        );
-        generic_optimizer.optimize(
+impl<M: IncrementalOptModel<ScoreType: Energy>> LocalSearchOptimizer<M>
+    for IncrementalOptimizer<SimulatedAnnealingOptimizer>
+{
+    /// Start an optimization to be run step by step, applying only accepted transitions to the current solution
+    ///
+    /// - `model` : the model to optimize
+    /// - `initial_solution` : the initial solution to start optimization
+    /// - `initial_score` : the initial score of the initial solution
+    /// - `n_iter`: maximum iterations
+    /// - `time_limit`: maximum iteration time
+    /// - `stop_condition`: additional condition to stop the optimization, checked at the end of each iteration
+    /// - `control`: handle to cancel, pause or inject solutions from other threads, if any
+    /// - `rng`: random number generator from which all randomness is drawn
+    fn start_optimization<'a>(
+        &self,
+        model: &'a M,
+        initial_solution: M::SolutionType,
+        initial_score: M::ScoreType,
+        n_iter: usize,
+        time_limit: Duration,
+        stop_condition: Box<dyn StopCondition<M::ScoreType> + 'a>,
+        control: Option<&'a OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: Box<dyn Rng + 'a>,
+    ) -> Result<OptSession<'a, M>, LocalsearchError>
+    where
+        Self: 'a,
+    {
+        IncrementalOptimizer::new(self.optimizer.generic_optimizer()).start_optimization(
             model,
             initial_solution,
             initial_score,
             n_iter,
             time_limit,
-            &mut callback_with_update,
+            stop_condition,
+            control,
+            rng,
         )
     }
 }
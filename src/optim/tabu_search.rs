@@ -1,12 +1,17 @@
 use std::{cell::RefCell, marker::PhantomData, rc::Rc};
 
+use rand::{Rng, rngs::SmallRng};
 use rayon::prelude::*;
 
-use super::LocalSearchOptimizer;
+use super::{IncrementalOptimizer, LocalSearchOptimizer, TabuSearchConfig, generic::valid_trials};
 use crate::{
-    Duration, Instant, OptModel,
-    callback::{OptCallbackFn, OptProgress},
+    AlgorithmState, Checkpoint, Duration, IncrementalOptModel, Instant, LocalsearchError,
+    OptControl, OptModel, OptObserver, OptResult, OptSession, OptStepper, StopReason,
+    TrialSolution,
+    callback::{OptMetrics, OptProgress},
     counter::AcceptanceCounter,
+    stop_condition::{StopCondition, StopState},
+    utils::{catch_panic, split_rng},
 };
 
 /// Trait that a tabu list must satisfies
@@ -22,6 +27,10 @@ pub trait TabuList: Default {
 
     /// Append the item to the list
     fn append(&mut self, transition: Self::Item);
+
+    /// Items of the list, oldest first, saved in [`Checkpoint`]s.
+    /// Appending them to an empty list of the same size must rebuild the list.
+    fn items(&self) -> Vec<Self::Item>;
 }
 
 /// Optimizer that implements the tabu search algorithm
@@ -33,14 +42,14 @@ pub struct TabuSearchOptimizer<T: TabuList> {
     phantom: PhantomData<T>,
 }
 
-fn find_accepted_solution<M, L>(
-    samples: Vec<(M::SolutionType, M::TransitionType, M::ScoreType)>,
+fn find_accepted_solution<C, SC, L>(
+    samples: Vec<(C, L::Item, SC)>,
     tabu_list: &L,
-    best_score: M::ScoreType,
-) -> Option<(M::SolutionType, M::TransitionType, M::ScoreType)>
+    best_score: SC,
+) -> Option<(C, L::Item, SC)>
 where
-    M: OptModel,
-    L: TabuList<Item = M::TransitionType>,
+    SC: Ord,
+    L: TabuList,
 {
     for (solution, transition, score) in samples.into_iter() {
         #[allow(unused_parens)]
@@ -58,6 +67,12 @@ where
 }
 
 impl<T: TabuList> TabuSearchOptimizer<T> {
+    /// Builder of the optimizer, starting from the default parameters of [`TabuSearchConfig`]
+    /// and validating them in [`TabuSearchConfig::build`]
+    pub fn builder() -> TabuSearchConfig {
+        TabuSearchConfig::default()
+    }
+
     /// Constructor of TabuSearchOptimizer
     ///
     /// - `patience` : the optimizer will give up
@@ -80,153 +95,421 @@ impl<T: TabuList> TabuSearchOptimizer<T> {
     }
 }
 
-impl<T> TabuSearchOptimizer<T>
+/// Generates a trial solution, its transition and its score from the current solution
+fn propose_solution<M: OptModel>(
+    model: &M,
+) -> impl Fn(&M::SolutionType, M::ScoreType, &mut SmallRng) -> TrialSolution<M> + Sync + '_ {
+    |current_solution, current_score, trial_rng| {
+        model.generate_trial_solution(current_solution.clone(), current_score, trial_rng)
+    }
+}
+
+/// Transition proposed from the borrowed current solution, with an empty candidate
+type TrialTransition<M> = Result<
+    (
+        (),
+        <M as OptModel>::TransitionType,
+        <M as OptModel>::ScoreType,
+    ),
+    LocalsearchError,
+>;
+
+/// Proposes a transition and its score from the borrowed current solution
+fn propose_transition<M: IncrementalOptModel>(
+    model: &M,
+) -> impl Fn(&M::SolutionType, M::ScoreType, &mut SmallRng) -> TrialTransition<M> + Sync + '_ {
+    |current_solution, current_score, trial_rng| {
+        let (transition, score) =
+            model.propose_transition(current_solution, current_score, trial_rng)?;
+        Ok(((), transition, score))
+    }
+}
+
+/// State of the loop of [`TabuSearchOptimizer`], shared by the cloning and the incremental variants,
+/// advanced one iteration at a time.
+///
+/// - `propose` : generates a trial candidate, its transition and its score from the current solution,
+///   [`LocalsearchError::NoValidMove`] leaving the trial out and other errors stopping the loop
+/// - `materialize` : turns the current solution into the accepted candidate
+struct TabuStepper<'a, S, SC, L: TabuList, C, P, A> {
+    patience: usize,
+    n_trials: usize,
+    return_iter: usize,
+    default_tabu_size: usize,
+    n_iter: usize,
+    time_limit: Duration,
+    stop_condition: Box<dyn StopCondition<SC> + 'a>,
+    control: Option<&'a OptControl<S, SC, L::Item>>,
+    rng: Box<dyn Rng + 'a>,
+    propose: P,
+    materialize: A,
+    start_time: Instant,
+    current_solution: S,
+    current_score: SC,
+    best_solution: Rc<RefCell<S>>,
+    best_score: SC,
+    tabu_list: L,
+    acceptance_counter: AcceptanceCounter,
+    return_stagnation_counter: usize,
+    patience_stagnation_counter: usize,
+    n_evaluations: usize,
+    n_iterations: usize,
+    injected: Option<(S, SC)>,
+    phantom: PhantomData<fn(C)>,
+}
+
+impl<'a, S, SC, L, C, P, A> TabuStepper<'a, S, SC, L, C, P, A>
 where
-    T: TabuList,
+    S: Clone + Sync + Send,
+    SC: Ord + Copy + Sync + Send,
+    L: TabuList,
+    C: Send,
+    P: Fn(&S, SC, &mut SmallRng) -> Result<(C, L::Item, SC), LocalsearchError> + Sync,
+    A: FnMut(&mut S, C, &L::Item),
 {
+    /// Start from the initial solution with an empty tabu list,
+    /// or from the checkpoint restored through `control`, if any
     #[allow(clippy::too_many_arguments)]
-    /// Start optimization
-    ///
-    /// - `model` : the model to optimize
-    /// - `initial_solution` : the initial solution to start optimization
-    /// - `initial_score` : the initial score of the initial solution
-    /// - `n_iter`: maximum iterations
-    /// - `time_limit`: maximum iteration time
-    /// - `callback` : callback function that will be invoked at the end of each iteration
-    /// - `tabu_list` : initial tabu list
-    fn optimize_with_tabu_list<M: OptModel<TransitionType = T::Item>>(
-        &self,
-        model: &M,
-        initial_solution: M::SolutionType,
-        initial_score: M::ScoreType,
+    fn new(
+        optimizer: &TabuSearchOptimizer<L>,
+        initial_solution: S,
+        initial_score: SC,
         n_iter: usize,
         time_limit: Duration,
-        callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
-        mut tabu_list: T,
-    ) -> (M::SolutionType, M::ScoreType, T) {
-        let start_time = Instant::now();
-        let mut current_solution = initial_solution;
-        let mut current_score = initial_score;
-        let best_solution = Rc::new(RefCell::new(current_solution.clone()));
-        let mut best_score = current_score;
-        let mut return_stagnation_counter = 0;
-        let mut patience_stagnation_counter = 0;
-        let mut acceptance_counter = AcceptanceCounter::new(100);
-
-        for it in 0..n_iter {
-            let duration = Instant::now().duration_since(start_time);
-            if duration > time_limit {
-                break;
+        stop_condition: Box<dyn StopCondition<SC> + 'a>,
+        control: Option<&'a OptControl<S, SC, L::Item>>,
+        rng: Box<dyn Rng + 'a>,
+        propose: P,
+        materialize: A,
+    ) -> Self {
+        let mut tabu_list = L::default();
+        tabu_list.set_size(optimizer.default_tabu_size);
+        let mut stepper = Self {
+            patience: optimizer.patience,
+            n_trials: optimizer.n_trials,
+            return_iter: optimizer.return_iter,
+            default_tabu_size: optimizer.default_tabu_size,
+            n_iter,
+            time_limit,
+            stop_condition,
+            control,
+            rng,
+            propose,
+            materialize,
+            start_time: Instant::now(),
+            best_solution: Rc::new(RefCell::new(initial_solution.clone())),
+            current_solution: initial_solution,
+            current_score: initial_score,
+            best_score: initial_score,
+            tabu_list,
+            acceptance_counter: AcceptanceCounter::new(100),
+            return_stagnation_counter: 0,
+            patience_stagnation_counter: 0,
+            n_evaluations: 0,
+            n_iterations: 0,
+            injected: None,
+            phantom: PhantomData,
+        };
+
+        if let Some(control) = control {
+            // Continue from a checkpoint, if any
+            if let Some(checkpoint) = control.take_restored() {
+                stepper.restore(checkpoint);
+            }
+            control.publish_best(&stepper.best_solution.borrow(), stepper.best_score);
+        }
+        stepper
+    }
+}
+
+impl<S, SC, L, C, P, A> OptStepper<S, SC, L::Item> for TabuStepper<'_, S, SC, L, C, P, A>
+where
+    S: Clone + Sync + Send,
+    SC: Ord + Copy + Sync + Send,
+    L: TabuList,
+    C: Send,
+    P: Fn(&S, SC, &mut SmallRng) -> Result<(C, L::Item, SC), LocalsearchError> + Sync,
+    A: FnMut(&mut S, C, &L::Item),
+{
+    fn iterate(
+        &mut self,
+        observer: &mut dyn OptObserver<S, SC, L::Item>,
+    ) -> Result<Option<StopReason>, LocalsearchError> {
+        let it = self.n_iterations;
+        if it >= self.n_iter {
+            return Ok(Some(StopReason::MaxIterations));
+        }
+
+        // Handle external control: pause, checkpoint and cancellation, then injected solution
+        if let Some(control) = self.control {
+            // time spent paused does not count toward the time limit
+            self.start_time += control.wait_while_paused();
+            if control.take_checkpoint_request() {
+                control.store_checkpoint(self.checkpoint());
+            }
+            if control.is_cancelled() {
+                return Ok(Some(StopReason::Cancelled));
             }
-            let mut samples = vec![];
-            (0..self.n_trials)
-                .into_par_iter()
-                .map(|_| {
-                    let mut rng = rand::rng();
-                    let (solution, transitions, score) = model.generate_trial_solution(
-                        current_solution.clone(),
-                        current_score,
-                        &mut rng,
-                    );
-                    (solution, transitions, score)
-                })
-                .collect_into_vec(&mut samples);
-
-            samples.sort_unstable_by_key(|(_, _, score)| *score);
-
-            let res = find_accepted_solution::<M, T>(samples, &tabu_list, best_score);
-
-            let accepted = res.is_some();
-            acceptance_counter.enqueue(accepted);
-
-            if accepted {
-                let (solution, trans, score) = res.unwrap();
-                // Accepted
-                // 2. Update best solution and score
-                if score < best_score {
-                    best_score = score;
-                    best_solution.replace(solution.clone());
-                    return_stagnation_counter = 0;
-                    patience_stagnation_counter = 0;
-                } else {
-                    return_stagnation_counter += 1;
-                    patience_stagnation_counter += 1;
+        }
+        let injected = self
+            .injected
+            .take()
+            .or_else(|| self.control.and_then(OptControl::take_injected));
+        if let Some((solution, score)) = injected {
+            self.current_solution = solution;
+            self.current_score = score;
+            if score < self.best_score {
+                self.best_score = score;
+                self.best_solution.replace(self.current_solution.clone());
+                if let Some(control) = self.control {
+                    control.publish_best(&self.current_solution, score);
                 }
+                observer.on_improvement(it, &self.current_solution, score);
+                self.return_stagnation_counter = 0;
+                self.patience_stagnation_counter = 0;
+            }
+        }
+
+        let duration = Instant::now().duration_since(self.start_time);
+        if duration > self.time_limit {
+            return Ok(Some(StopReason::TimeLimit));
+        }
+        self.n_iterations = it + 1;
+        let propose = &self.propose;
+        let current_solution = &self.current_solution;
+        let current_score = self.current_score;
+        let trials = split_rng(&mut *self.rng, self.n_trials)
+            .into_par_iter()
+            .map(|mut trial_rng| {
+                catch_panic(|| propose(current_solution, current_score, &mut trial_rng))
+            })
+            .collect();
+        let mut samples = valid_trials(trials)?;
+        self.n_evaluations += self.n_trials;
+
+        samples.sort_unstable_by_key(|(_, _, score)| *score);
 
-                // 3. Update accepted counter and transitions (no transitions here)
+        let res = find_accepted_solution(samples, &self.tabu_list, self.best_score);
 
-                // 4. Update current solution and score
-                current_score = score;
-                current_solution = solution;
+        let accepted = res.is_some();
+        self.acceptance_counter.enqueue(accepted);
 
-                // 7. Update algorithm-specific state
-                tabu_list.append(trans);
+        if let Some((candidate, trans, score)) = res {
+            // Accepted
+            // 4. Update current solution and score
+            (self.materialize)(&mut self.current_solution, candidate, &trans);
+            self.current_score = score;
+
+            // 2. Update best solution and score
+            if score < self.best_score {
+                self.best_score = score;
+                self.best_solution.replace(self.current_solution.clone());
+                if let Some(control) = self.control {
+                    control.publish_best(&self.current_solution, score);
+                }
+                observer.on_improvement(it, &self.current_solution, score);
+                self.return_stagnation_counter = 0;
+                self.patience_stagnation_counter = 0;
             } else {
-                // rejected
-                // If no accepted, increment stagnation
-                return_stagnation_counter += 1;
-                patience_stagnation_counter += 1;
+                self.return_stagnation_counter += 1;
+                self.patience_stagnation_counter += 1;
             }
 
-            // 5. Check and handle return to best
-            if return_stagnation_counter == self.return_iter {
-                current_solution = best_solution.borrow().clone();
-                current_score = best_score;
-                return_stagnation_counter = 0;
-            }
+            // 3. Update accepted counter and transitions (no transitions here)
 
-            // 6. Check patience
-            if patience_stagnation_counter == self.patience {
-                break;
-            }
+            // 7. Update algorithm-specific state
+            observer.on_tabu_append(it, &trans);
+            self.tabu_list.append(trans);
+        } else {
+            // rejected
+            // If no accepted, increment stagnation
+            self.return_stagnation_counter += 1;
+            self.patience_stagnation_counter += 1;
+        }
+
+        // 5. Check and handle return to best
+        if self.return_stagnation_counter == self.return_iter {
+            self.current_solution = self.best_solution.borrow().clone();
+            self.current_score = self.best_score;
+            self.return_stagnation_counter = 0;
+            observer.on_return_to_best(it, self.best_score);
+        }
+
+        // 6. Check patience and stop condition
+        if self.patience_stagnation_counter == self.patience {
+            return Ok(Some(StopReason::Patience));
+        }
+        let stop_state = StopState {
+            iter: self.n_iterations,
+            elapsed: Instant::now().duration_since(self.start_time),
+            n_evaluations: self.n_evaluations,
+            current_score: self.current_score,
+            best_score: self.best_score,
+        };
+        if self.stop_condition.should_stop(&stop_state) {
+            return Ok(Some(StopReason::StopCondition));
+        }
+
+        // 8. Notify observer
+        let progress = OptProgress {
+            iter: it,
+            acceptance_ratio: self.acceptance_counter.acceptance_ratio(),
+            solution: self.best_solution.clone(),
+            score: self.best_score,
+            current_score: self.current_score,
+            elapsed: stop_state.elapsed,
+            n_evaluations: self.n_evaluations,
+            metrics: OptMetrics::default(),
+        };
+        observer.on_iteration(progress);
+        Ok(None)
+    }
+
+    fn n_iterations(&self) -> usize {
+        self.n_iterations
+    }
 
-            // 8. Invoke callback
-            let progress = OptProgress::new(
-                it,
-                acceptance_counter.acceptance_ratio(),
-                best_solution.clone(),
-                best_score,
-            );
-            callback(progress);
+    fn best_score(&self) -> SC {
+        self.best_score
+    }
+
+    fn checkpoint(&self) -> Checkpoint<S, SC, L::Item> {
+        Checkpoint {
+            iter: self.n_iterations,
+            elapsed: Instant::now().duration_since(self.start_time),
+            n_evaluations: self.n_evaluations,
+            best_solution: self.best_solution.borrow().clone(),
+            best_score: self.best_score,
+            current: vec![(self.current_solution.clone(), self.current_score)],
+            acceptance_counter: self.acceptance_counter.clone(),
+            return_stagnation_counter: self.return_stagnation_counter,
+            patience_stagnation_counter: self.patience_stagnation_counter,
+            algorithm_state: AlgorithmState::default(),
+            tabu_list: self.tabu_list.items(),
         }
+    }
 
-        let best_solution = (*best_solution.borrow()).clone();
+    fn restore(&mut self, checkpoint: Checkpoint<S, SC, L::Item>) {
+        let now = Instant::now();
+        self.start_time = now.checked_sub(checkpoint.elapsed).unwrap_or(now);
+        self.n_iterations = checkpoint.iter;
+        self.n_evaluations = checkpoint.n_evaluations;
+        self.best_solution.replace(checkpoint.best_solution);
+        self.best_score = checkpoint.best_score;
+        if let Some((solution, score)) = checkpoint.current.into_iter().next() {
+            self.current_solution = solution;
+            self.current_score = score;
+        }
+        self.acceptance_counter = checkpoint.acceptance_counter;
+        self.return_stagnation_counter = checkpoint.return_stagnation_counter;
+        self.patience_stagnation_counter = checkpoint.patience_stagnation_counter;
+        self.tabu_list = L::default();
+        self.tabu_list.set_size(self.default_tabu_size);
+        for item in checkpoint.tabu_list {
+            self.tabu_list.append(item);
+        }
+    }
+
+    fn inject(&mut self, solution: S, score: SC) {
+        self.injected = Some((solution, score));
+    }
 
-        (best_solution, best_score, tabu_list)
+    fn result(&self, stop_reason: StopReason) -> OptResult<S, SC> {
+        OptResult {
+            solution: self.best_solution.borrow().clone(),
+            score: self.best_score,
+            n_iterations: self.n_iterations,
+            n_evaluations: self.n_evaluations,
+            elapsed: Instant::now().duration_since(self.start_time),
+            acceptance_ratio: self.acceptance_counter.acceptance_ratio(),
+            stop_reason,
+            algorithm_state: AlgorithmState::default(),
+        }
     }
 }
 
 impl<T: TabuList, M: OptModel<TransitionType = T::Item>> LocalSearchOptimizer<M>
     for TabuSearchOptimizer<T>
 {
-    /// Start optimization
+    /// Start an optimization to be run step by step
     ///
     /// - `model`: the model to optimize
     /// - `initial_solution`: the initial solution to start optimization
     /// - `initial_score`: the initial score of the initial solution
     /// - `n_iter`: maximum iterations
     /// - `time_limit`: maximum iteration time
-    /// - `callback`: callback function that will be invoked at the end of each iteration
-    fn optimize(
+    /// - `stop_condition`: additional condition to stop the optimization, checked at the end of each iteration
+    /// - `control`: handle to cancel, pause or inject solutions from other threads, if any
+    /// - `rng`: random number generator from which all randomness is drawn
+    fn start_optimization<'a>(
         &self,
-        model: &M,
+        model: &'a M,
         initial_solution: M::SolutionType,
         initial_score: M::ScoreType,
         n_iter: usize,
         time_limit: Duration,
-        callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
-    ) -> (M::SolutionType, M::ScoreType) {
-        let mut tabu_list = T::default();
-        tabu_list.set_size(self.default_tabu_size);
-        let (solution, score, _) = self.optimize_with_tabu_list(
-            model,
+        stop_condition: Box<dyn StopCondition<M::ScoreType> + 'a>,
+        control: Option<&'a OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: Box<dyn Rng + 'a>,
+    ) -> Result<OptSession<'a, M>, LocalsearchError>
+    where
+        Self: 'a,
+    {
+        let stepper = TabuStepper::new(
+            self,
             initial_solution,
             initial_score,
             n_iter,
             time_limit,
-            callback,
-            tabu_list,
+            stop_condition,
+            control,
+            rng,
+            propose_solution(model),
+            |current_solution, trial_solution, _| *current_solution = trial_solution,
+        );
+        Ok(OptSession::new(model, stepper))
+    }
+}
+
+impl<T: TabuList, M: IncrementalOptModel<TransitionType = T::Item>> LocalSearchOptimizer<M>
+    for IncrementalOptimizer<TabuSearchOptimizer<T>>
+{
+    /// Start an optimization to be run step by step, applying only accepted transitions to the current solution
+    ///
+    /// - `model`: the model to optimize
+    /// - `initial_solution`: the initial solution to start optimization
+    /// - `initial_score`: the initial score of the initial solution
+    /// - `n_iter`: maximum iterations
+    /// - `time_limit`: maximum iteration time
+    /// - `stop_condition`: additional condition to stop the optimization, checked at the end of each iteration
+    /// - `control`: handle to cancel, pause or inject solutions from other threads, if any
+    /// - `rng`: random number generator from which all randomness is drawn
+    fn start_optimization<'a>(
+        &self,
+        model: &'a M,
+        initial_solution: M::SolutionType,
+        initial_score: M::ScoreType,
+        n_iter: usize,
+        time_limit: Duration,
+        stop_condition: Box<dyn StopCondition<M::ScoreType> + 'a>,
+        control: Option<&'a OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: Box<dyn Rng + 'a>,
+    ) -> Result<OptSession<'a, M>, LocalsearchError>
+    where
+        Self: 'a,
+    {
+        let stepper = TabuStepper::new(
+            &self.optimizer,
+            initial_solution,
+            initial_score,
+            n_iter,
+            time_limit,
+            stop_condition,
+            control,
+            rng,
+            propose_transition(model),
+            |current_solution, _, transition| model.apply_transition(current_solution, transition),
         );
-        (solution, score)
+        Ok(OptSession::new(model, stepper))
     }
 }
@@ -0,0 +1,186 @@
+use rand::Rng;
+use rayon::prelude::*;
+
+use super::config::check;
+use crate::{
+    Energy, LocalsearchError, OptModel,
+    utils::{catch_panic, split_rng},
+};
+
+/// Uphill transitions `(current_score, trial_score)` sampled from a model,
+/// from which [`TemperatureEstimator`] estimates inverse temperatures
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EnergySample {
+    transitions: Vec<(f64, f64)>,
+}
+
+impl EnergySample {
+    /// Sample from `(current_score, trial_score)` pairs, keeping only uphill transitions (`trial_score > current_score`)
+    pub fn new(transitions: Vec<(f64, f64)>) -> Self {
+        let transitions = transitions
+            .into_iter()
+            .filter(|(current, trial)| trial > current)
+            .collect();
+        Self { transitions }
+    }
+
+    /// Sample uphill transitions along `n_walks` random walks of `walk_length` steps,
+    /// each starting from a random solution and accepting every trial solution.
+    /// Walks run in parallel, each with a generator split from `rng`, and end early where the model has no valid move.
+    pub fn collect<M: OptModel<ScoreType: Energy>, R: Rng>(
+        model: &M,
+        n_walks: usize,
+        walk_length: usize,
+        rng: &mut R,
+    ) -> Result<Self, LocalsearchError> {
+        let walks = split_rng(rng, n_walks)
+            .into_par_iter()
+            .map(|mut walk_rng| {
+                catch_panic(|| {
+                    let (mut solution, mut score) =
+                        model.generate_random_solution(&mut walk_rng)?;
+                    let mut transitions = Vec::with_capacity(walk_length);
+                    for _ in 0..walk_length {
+                        let (trial_solution, _, trial_score) =
+                            match model.generate_trial_solution(solution, score, &mut walk_rng) {
+                                Ok(trial) => trial,
+                                // the walk ends where there is no valid move
+                                Err(LocalsearchError::NoValidMove) => break,
+                                Err(e) => return Err(e),
+                            };
+                        transitions.push((score.energy(), trial_score.energy()));
+                        solution = trial_solution;
+                        score = trial_score;
+                    }
+                    Ok(transitions)
+                })
+            })
+            .collect::<Result<Vec<_>, LocalsearchError>>()?;
+        Ok(Self::new(walks.into_iter().flatten().collect()))
+    }
+
+    /// Number of uphill transitions
+    pub fn len(&self) -> usize {
+        self.transitions.len()
+    }
+
+    /// Whether no uphill transition was sampled
+    pub fn is_empty(&self) -> bool {
+        self.transitions.is_empty()
+    }
+
+    /// Score increases of the uphill transitions, all positive
+    pub fn deltas(&self) -> impl Iterator<Item = f64> + '_ {
+        self.transitions
+            .iter()
+            .map(|(current, trial)| trial - current)
+    }
+
+    /// Acceptance ratio of the uphill transitions at inverse temperature `beta`,
+    /// `sum(exp(-beta * trial)) / sum(exp(-beta * current))`
+    fn acceptance_ratio(&self, beta: f64) -> f64 {
+        // shift the scores by the lowest one so that the exponentials do not overflow
+        let offset = self
+            .transitions
+            .iter()
+            .map(|(current, _)| *current)
+            .fold(f64::INFINITY, f64::min);
+        let (trial_sum, current_sum) = self.transitions.iter().fold(
+            (0.0, 0.0),
+            |(trial_sum, current_sum), (current, trial)| {
+                (
+                    trial_sum + (-beta * (trial - offset)).exp(),
+                    current_sum + (-beta * (current - offset)).exp(),
+                )
+            },
+        );
+        trial_sum / current_sum
+    }
+}
+
+/// Method to estimate an inverse temperature from an [`EnergySample`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(rename_all = "snake_case")
+)]
+pub enum TemperatureEstimator {
+    /// Ben-Ameur's iterative method: the inverse temperature at which
+    /// the sampled uphill transitions are accepted with probability `target_acceptance`,
+    /// weighting each transition by the Boltzmann probability of its origin
+    BenAmeur {
+        /// target acceptance probability of uphill transitions, in (0, 1)
+        target_acceptance: f64,
+    },
+    /// The `percentile` of the uphill deltas is accepted with probability `target_acceptance`.
+    /// A high percentile is robust to a few huge deltas when the score scale is large.
+    Percentile {
+        /// target acceptance probability, in (0, 1)
+        target_acceptance: f64,
+        /// percentile of the deltas, in [0, 1]
+        percentile: f64,
+    },
+    /// The smallest nonzero delta is accepted with probability `target_acceptance`,
+    /// suited to the final temperature of an annealing schedule
+    SmallestDelta {
+        /// target acceptance probability, in (0, 1)
+        target_acceptance: f64,
+    },
+}
+
+const BEN_AMEUR_MAX_ITER: usize = 100;
+const BEN_AMEUR_TOLERANCE: f64 = 1e-4;
+
+impl TemperatureEstimator {
+    /// Estimated inverse temperature, `None` if the sample has no uphill transition
+    ///
+    /// Returns [`LocalsearchError::InvalidParameter`] unless `target_acceptance` is in (0, 1).
+    pub fn estimate(&self, sample: &EnergySample) -> Result<Option<f64>, LocalsearchError> {
+        let (Self::BenAmeur { target_acceptance }
+        | Self::Percentile {
+            target_acceptance, ..
+        }
+        | Self::SmallestDelta { target_acceptance }) = *self;
+        check(
+            target_acceptance > 0.0 && target_acceptance < 1.0,
+            "target_acceptance",
+            "in (0, 1)",
+        )?;
+        if sample.is_empty() {
+            return Ok(None);
+        }
+        let beta = match *self {
+            Self::BenAmeur { target_acceptance } => {
+                let ln_target = target_acceptance.ln();
+                // start from the mean delta
+                let mean = sample.deltas().sum::<f64>() / sample.len() as f64;
+                let mut beta = -ln_target / mean;
+                for _ in 0..BEN_AMEUR_MAX_ITER {
+                    let acceptance = sample
+                        .acceptance_ratio(beta)
+                        .clamp(f64::MIN_POSITIVE, 1.0 - f64::EPSILON);
+                    if (acceptance - target_acceptance).abs() < BEN_AMEUR_TOLERANCE {
+                        break;
+                    }
+                    beta *= ln_target / acceptance.ln();
+                }
+                beta
+            }
+            Self::Percentile {
+                target_acceptance,
+                percentile,
+            } => {
+                let mut deltas: Vec<f64> = sample.deltas().collect();
+                deltas.sort_by(f64::total_cmp);
+                let rank = (percentile.clamp(0.0, 1.0) * (deltas.len() - 1) as f64).round();
+                -target_acceptance.ln() / deltas[rank as usize]
+            }
+            Self::SmallestDelta { target_acceptance } => {
+                let smallest = sample.deltas().fold(f64::INFINITY, f64::min);
+                -target_acceptance.ln() / smallest
+            }
+        };
+        Ok(Some(beta))
+    }
+}
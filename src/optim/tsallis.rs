@@ -1,11 +1,14 @@
-use std::{cell::RefCell, num::NonZero, rc::Rc};
+use std::num::NonZero;
 
-use ordered_float::NotNan;
+use rand::Rng;
 
-use super::{AdaptiveScheduler, GenericLocalSearchOptimizer, LocalSearchOptimizer};
+use super::{
+    AcceptanceCriterion, AcceptanceState, AdaptiveScheduler, GenericLocalSearchOptimizer,
+    LocalSearchOptimizer, TsallisRelativeAnnealingConfig,
+};
 use crate::{
-    Duration, OptModel,
-    callback::{OptCallbackFn, OptProgress},
+    AlgorithmState, Duration, Energy, LocalsearchError, OptControl, OptModel, OptSession,
+    stop_condition::StopCondition,
 };
 
 fn tsallis_transition_prob(
@@ -27,6 +30,56 @@ fn tsallis_transition_prob(
     }
 }
 
+/// Tsallis criterion relative to the best score (offset),
+/// whose beta is adapted by `scheduler` every `update_frequency` iterations
+#[derive(Clone, Copy)]
+struct TsallisAcceptance {
+    beta: f64,
+    offset: f64,
+    scheduler: AdaptiveScheduler,
+    update_frequency: NonZero<usize>,
+    q: f64,
+    xi: f64,
+}
+
+impl<ST: Energy> AcceptanceCriterion<ST> for TsallisAcceptance {
+    fn acceptance_probability(&self, current_score: ST, trial_score: ST) -> f64 {
+        tsallis_transition_prob(
+            current_score.energy(),
+            trial_score.energy(),
+            self.offset,
+            self.beta,
+            self.q,
+            self.xi,
+        )
+    }
+
+    // When resuming, the initial score is the best score of the checkpoint, hence its offset.
+    fn initialize(&mut self, initial_score: ST) {
+        self.offset = initial_score.energy();
+    }
+
+    fn update(&mut self, state: &AcceptanceState<ST>) {
+        self.offset = state.best_score.energy();
+        if state.iter.is_multiple_of(self.update_frequency.get()) {
+            self.beta = self.scheduler.update_temperature(self.beta, state);
+        }
+    }
+
+    fn algorithm_state(&self) -> AlgorithmState {
+        AlgorithmState {
+            beta: Some(self.beta),
+            ..Default::default()
+        }
+    }
+
+    fn restore(&mut self, state: &AlgorithmState) {
+        if let Some(beta) = state.beta {
+            self.beta = beta;
+        }
+    }
+}
+
 /// Optimizer that implements Tsallis relative annealing algorithm
 /// This is a generalization of relative annealing using Tsallis statistics.
 /// The acceptance probability for worse solutions is [1 - (1-q) * beta * ΔE / (E - E_best + ξ)]^{1/(1-q)},
@@ -45,6 +98,12 @@ pub struct TsallisRelativeAnnealingOptimizer {
 }
 
 impl TsallisRelativeAnnealingOptimizer {
+    /// Builder of the optimizer, starting from the default parameters of [`TsallisRelativeAnnealingConfig`]
+    /// and validating them in [`TsallisRelativeAnnealingConfig::build`]
+    pub fn builder() -> TsallisRelativeAnnealingConfig {
+        TsallisRelativeAnnealingConfig::default()
+    }
+
     /// Constructor of TsallisRelativeAnnealingOptimizer
     ///
     /// - `patience` : the optimizer will give up
@@ -87,82 +146,53 @@ impl TsallisRelativeAnnealingOptimizer {
     }
 }
 
-impl<M: OptModel<ScoreType = NotNan<f64>>> LocalSearchOptimizer<M>
-    for TsallisRelativeAnnealingOptimizer
-{
-    /// Start optimization
+impl<M: OptModel<ScoreType: Energy>> LocalSearchOptimizer<M> for TsallisRelativeAnnealingOptimizer {
+    /// Start an optimization to be run step by step
     ///
     /// - `model` : the model to optimize
     /// - `initial_solution` : the initial solution to start optimization
     /// - `initial_score` : the initial score of the initial solution
     /// - `n_iter`: maximum iterations
     /// - `time_limit`: maximum iteration time
-    /// - `callback` : callback function that will be invoked at the end of each iteration
-    fn optimize(
+    /// - `stop_condition`: additional condition to stop the optimization, checked at the end of each iteration
+    /// - `control`: handle to cancel, pause or inject solutions from other threads, if any
+    /// - `rng`: random number generator from which all randomness is drawn
+    fn start_optimization<'a>(
         &self,
-        model: &M,
+        model: &'a M,
         initial_solution: M::SolutionType,
         initial_score: M::ScoreType,
         n_iter: usize,
         time_limit: Duration,
-        callback: &mut dyn OptCallbackFn<M::SolutionType, M::ScoreType>,
-    ) -> (M::SolutionType, M::ScoreType) {
-        // wrap current best score (offset) and beta (inverse temperature) in Rc<RefCell> to allow mutation in closure
-        let current_offset = Rc::new(RefCell::new(initial_score.into_inner()));
-        let current_beta = Rc::new(RefCell::new(self.initial_beta));
-
-        // create transition probability function
-        let transition_prob = {
-            let offset = current_offset.clone();
-            let beta = current_beta.clone();
-
-            move |current: NotNan<f64>, trial: NotNan<f64>| {
-                tsallis_transition_prob(
-                    current.into_inner(),
-                    trial.into_inner(),
-                    *offset.borrow(),
-                    *beta.borrow(),
-                    self.q,
-                    self.xi,
-                )
-            }
-        };
-
-        // wrap callback to update offset and beta based on update_frequency
-        let mut callback_with_updates = |progress: OptProgress<M::SolutionType, M::ScoreType>| {
-            // update offset
-            current_offset.replace(progress.score.into_inner());
-
-            // potentially update beta with scheduler
-            if progress.iter % self.update_frequency.get() == 0 {
-                let new_beta = self.scheduler.update_temperature(
-                    *current_beta.borrow(),
-                    progress.iter,
-                    n_iter,
-                    progress.acceptance_ratio,
-                );
-                current_beta.replace(new_beta);
-            }
-
-            // invoke original callback
-            callback(progress);
-        };
-
-        // create generic optimizer and run optimization
+        stop_condition: Box<dyn StopCondition<M::ScoreType> + 'a>,
+        control: Option<&'a OptControl<M::SolutionType, M::ScoreType, M::TransitionType>>,
+        rng: Box<dyn Rng + 'a>,
+    ) -> Result<OptSession<'a, M>, LocalsearchError>
+    where
+        Self: 'a,
+    {
         let optimizer = GenericLocalSearchOptimizer::new(
             self.patience,
             self.n_trials,
             self.return_iter,
-            transition_prob,
+            TsallisAcceptance {
+                beta: self.initial_beta,
+                offset: initial_score.energy(),
+                scheduler: self.scheduler,
+                update_frequency: self.update_frequency,
+                q: self.q,
+                xi: self.xi,
+            },
         );
-
-        optimizer.optimize(
+        optimizer.start_optimization(
             model,
             initial_solution,
             initial_score,
             n_iter,
             time_limit,
-            &mut callback_with_updates,
+            stop_condition,
+            control,
+            rng,
         )
     }
 }
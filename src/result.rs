@@ -0,0 +1,79 @@
+//! Result of an Optimization Run
+
+use crate::Duration;
+
+/// Reason why an optimization stopped
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum StopReason {
+    /// `n_iter` iterations were done
+    MaxIterations,
+    /// `time_limit` was exceeded
+    TimeLimit,
+    /// no improvement of the best score within `patience` iterations
+    Patience,
+    /// the [`StopCondition`](crate::stop_condition::StopCondition) was met
+    StopCondition,
+    /// the optimization was cancelled through its [`OptControl`](crate::OptControl)
+    Cancelled,
+    /// the model failed while stepping an [`OptSession`](crate::OptSession), whose step returned the error.
+    /// Optimizers run to the end return the error instead.
+    Failed,
+}
+
+/// Algorithm-specific state at the end of an optimization or at a [`Checkpoint`](crate::Checkpoint).
+/// Fields that do not apply to the optimizer are `None`.
+#[derive(Debug, Clone, Default, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
+pub struct AlgorithmState {
+    /// final inverse temperature (annealing, metropolis and tsallis based optimizers; soft level of lexicographic annealing)
+    pub beta: Option<f64>,
+    /// final probability to accept a worsening transition (epsilon greedy)
+    pub epsilon: Option<f64>,
+    /// final weight of the relative score difference (logistic annealing)
+    pub w: Option<f64>,
+    /// final water level (great deluge)
+    pub water_level: Option<f64>,
+    /// water level at the start of the optimization, from which it is lowered (great deluge)
+    pub initial_water_level: Option<f64>,
+    /// inverse temperature ladder (parallel tempering) or `[hard, medium, soft]` inverse temperatures (lexicographic annealing)
+    pub betas: Option<Vec<f64>>,
+    /// number of reheats (simulated annealing with a reheat policy)
+    pub n_reheats: Option<usize>,
+}
+
+/// OptResult is the outcome of an optimization run: the best solution and score
+/// together with statistics of the run
+#[derive(Debug, Clone)]
+pub struct OptResult<S, SC> {
+    /// best solution
+    pub solution: S,
+    /// score of the best solution
+    pub score: SC,
+    /// number of iterations done
+    pub n_iterations: usize,
+    /// number of trial solutions evaluated
+    pub n_evaluations: usize,
+    /// elapsed time of the optimization
+    pub elapsed: Duration,
+    /// acceptance ratio at the end of the optimization
+    pub acceptance_ratio: f64,
+    /// reason why the optimization stopped
+    pub stop_reason: StopReason,
+    /// algorithm-specific final state
+    pub algorithm_state: AlgorithmState,
+}
+
+impl<S, SC> OptResult<S, SC> {
+    /// Split the result into the best solution and its score, dropping the statistics
+    pub fn into_solution_and_score(self) -> (S, SC) {
+        (self.solution, self.score)
+    }
+}
+
+impl<S, SC> From<OptResult<S, SC>> for (S, SC) {
+    fn from(result: OptResult<S, SC>) -> Self {
+        result.into_solution_and_score()
+    }
+}
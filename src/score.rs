@@ -0,0 +1,249 @@
+//! Score Types and their Energies
+
+use std::{
+    cmp::Reverse,
+    fmt,
+    iter::Sum,
+    ops::{Add, AddAssign, Neg, Sub, SubAssign},
+};
+
+use ordered_float::{FloatCore, NotNan, OrderedFloat};
+
+/// Score type that converts to an energy, as required by the temperature-based optimizers
+/// (Metropolis, simulated, adaptive, logistic, relative, tsallis and population annealing,
+/// great deluge and parallel tempering) and the temperature tuning helpers.
+///
+/// Implemented for the primitive integers and for `NotNan` and `OrderedFloat` of `f32` and `f64`,
+/// and for [`Reverse`] of any of them with the negated energy, to maximize the score.
+/// Implement it for a custom score to run these optimizers on it.
+///
+/// Example
+///
+/// ```rust
+/// #[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+/// struct Cost(u32);
+///
+/// impl Energy for Cost {
+///     fn energy(&self) -> f64 {
+///         self.0 as f64
+///     }
+/// }
+/// ```
+pub trait Energy: Ord + Copy + Sync + Send {
+    /// Energy of the score, lower is better
+    fn energy(&self) -> f64;
+
+    /// Energy difference `other - self` from this score to `other`, positive when `other` is worse
+    fn energy_delta(&self, other: &Self) -> f64 {
+        other.energy() - self.energy()
+    }
+}
+
+macro_rules! impl_energy_for_integer {
+    ($($t:ty),*) => {
+        $(
+            impl Energy for $t {
+                fn energy(&self) -> f64 {
+                    *self as f64
+                }
+            }
+        )*
+    };
+}
+
+impl_energy_for_integer!(
+    i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize
+);
+
+impl<T: FloatCore + Into<f64> + Sync + Send> Energy for NotNan<T> {
+    fn energy(&self) -> f64 {
+        self.into_inner().into()
+    }
+}
+
+impl<T: FloatCore + Into<f64> + Sync + Send> Energy for OrderedFloat<T> {
+    fn energy(&self) -> f64 {
+        self.into_inner().into()
+    }
+}
+
+impl<T: Energy> Energy for Reverse<T> {
+    fn energy(&self) -> f64 {
+        -self.0.energy()
+    }
+
+    fn energy_delta(&self, other: &Self) -> f64 {
+        -self.0.energy_delta(&other.0)
+    }
+}
+
+/// Score made of several objectives to minimize, for multi-objective optimizers
+/// such as [`AmosaOptimizer`](crate::optim::AmosaOptimizer).
+///
+/// Implemented for arrays of [`Energy`], e.g. `[NotNan<f64>; 2]` for cost and delay,
+/// whose `Ord` (lexicographic) only breaks ties where a single order is needed.
+pub trait ObjectiveVector: Ord + Copy + Sync + Send {
+    /// Number of objectives
+    fn n_objectives(&self) -> usize;
+
+    /// Value of the `i`-th objective
+    fn objective(&self, i: usize) -> f64;
+
+    /// Whether this score Pareto-dominates `other`: no worse in every objective and better in at least one
+    fn dominates(&self, other: &Self) -> bool {
+        let mut better = false;
+        for i in 0..self.n_objectives() {
+            let (a, b) = (self.objective(i), other.objective(i));
+            if a > b {
+                return false;
+            }
+            better |= a < b;
+        }
+        better
+    }
+}
+
+impl<T: Energy, const N: usize> ObjectiveVector for [T; N] {
+    fn n_objectives(&self) -> usize {
+        N
+    }
+
+    fn objective(&self, i: usize) -> f64 {
+        self[i].energy()
+    }
+}
+
+/// Lexicographic score of a constrained problem with hard, medium and soft levels, lower is better.
+///
+/// Scores are compared level by level: any decrease of `hard` beats any change of `medium` and `soft`,
+/// and `medium` likewise dominates `soft`. Typically `hard` counts the violated constraints,
+/// so that a solution is feasible when it is 0, and `soft` is the objective.
+/// Problems without a medium level leave it at 0.
+///
+/// Hill climbing, tabu search and the other optimizers relying only on `Ord` take it as is;
+/// annealing-style acceptance over it is provided by
+/// [`LexicographicAnnealing`](crate::optim::LexicographicAnnealing).
+///
+/// Example
+///
+/// ```rust
+/// impl OptModel for ShiftModel {
+///     type ScoreType = HardMediumSoftScore;
+///     ...
+/// }
+///
+/// let score = HardMediumSoftScore::new(n_conflicts, 0, total_overtime);
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct HardMediumSoftScore {
+    /// hard level, e.g. number or amount of violated constraints
+    pub hard: i64,
+    /// medium level, compared when the hard levels are equal
+    pub medium: i64,
+    /// soft level, compared when the hard and medium levels are equal
+    pub soft: i64,
+}
+
+impl HardMediumSoftScore {
+    /// Score with the given levels
+    pub fn new(hard: i64, medium: i64, soft: i64) -> Self {
+        Self { hard, medium, soft }
+    }
+
+    /// Score with only a hard level
+    pub fn of_hard(hard: i64) -> Self {
+        Self::new(hard, 0, 0)
+    }
+
+    /// Score with only a medium level
+    pub fn of_medium(medium: i64) -> Self {
+        Self::new(0, medium, 0)
+    }
+
+    /// Score with only a soft level
+    pub fn of_soft(soft: i64) -> Self {
+        Self::new(0, 0, soft)
+    }
+
+    /// Whether no hard penalty remains, i.e. `hard <= 0`
+    pub fn is_feasible(&self) -> bool {
+        self.hard <= 0
+    }
+
+    /// Levels as `[hard, medium, soft]`
+    pub fn levels(&self) -> [i64; 3] {
+        [self.hard, self.medium, self.soft]
+    }
+
+    /// First level at which `other` differs from `self`, as its index in [`HardMediumSoftScore::levels`]
+    /// and the difference `other - self` at that level. `None` if the scores are equal.
+    pub fn deciding_level(&self, other: &Self) -> Option<(usize, i64)> {
+        self.levels()
+            .into_iter()
+            .zip(other.levels())
+            .enumerate()
+            .find(|(_, (a, b))| a != b)
+            .map(|(level, (a, b))| (level, b - a))
+    }
+}
+
+impl fmt::Display for HardMediumSoftScore {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}hard/{}medium/{}soft",
+            self.hard, self.medium, self.soft
+        )
+    }
+}
+
+impl Add for HardMediumSoftScore {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self {
+        Self::new(
+            self.hard + rhs.hard,
+            self.medium + rhs.medium,
+            self.soft + rhs.soft,
+        )
+    }
+}
+
+impl Sub for HardMediumSoftScore {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self {
+        Self::new(
+            self.hard - rhs.hard,
+            self.medium - rhs.medium,
+            self.soft - rhs.soft,
+        )
+    }
+}
+
+impl Neg for HardMediumSoftScore {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Self::new(-self.hard, -self.medium, -self.soft)
+    }
+}
+
+impl AddAssign for HardMediumSoftScore {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for HardMediumSoftScore {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl Sum for HardMediumSoftScore {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), Add::add)
+    }
+}
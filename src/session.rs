@@ -0,0 +1,195 @@
+//! Step-wise Optimization
+
+use crate::{
+    Checkpoint, LocalsearchError, OptModel, OptObserver, OptResult, StopReason,
+    callback::OptProgress,
+};
+
+/// OptStepper is the state machine of a running optimization, advanced one iteration at a time.
+///
+/// Optimizers implement it for the state of their loop, from which
+/// [`LocalSearchOptimizer::start_optimization`](crate::optim::LocalSearchOptimizer::start_optimization)
+/// returns an [`OptSession`]. Solutions are in the preprocessed representation of the model.
+pub trait OptStepper<S, SC, T> {
+    /// Run one iteration (one round of replica and population based optimizers), reporting its events to `observer`.
+    /// Returns the reason why the optimization stops instead when it is over,
+    /// without notifying [`OptObserver::on_stop`], which is left to the session.
+    fn iterate(
+        &mut self,
+        observer: &mut dyn OptObserver<S, SC, T>,
+    ) -> Result<Option<StopReason>, LocalsearchError>;
+
+    /// Number of iterations done, counted like [`OptResult::n_iterations`]
+    fn n_iterations(&self) -> usize;
+
+    /// Score of the best solution found so far
+    fn best_score(&self) -> SC;
+
+    /// Snapshot of the full state, the same as the one taken on
+    /// [`OptControl::request_checkpoint`](crate::OptControl::request_checkpoint)
+    fn checkpoint(&self) -> Checkpoint<S, SC, T>;
+
+    /// Replace the state with `checkpoint`, as when continuing from it
+    fn restore(&mut self, checkpoint: Checkpoint<S, SC, T>);
+
+    /// Replace the current solution at the start of the next iteration,
+    /// like [`OptControl::inject`](crate::OptControl::inject)
+    fn inject(&mut self, solution: S, score: SC);
+
+    /// Result holding the best solution found so far
+    fn result(&self, stop_reason: StopReason) -> OptResult<S, SC>;
+}
+
+/// OptSession is a resumable optimization, run a few iterations at a time,
+/// e.g. to interleave it with a game loop, a GUI event loop or other cooperative tasks.
+///
+/// It is returned by [`LocalSearchOptimizer::start`](crate::optim::LocalSearchOptimizer::start)
+/// and [`LocalSearchOptimizer::start_optimization`](crate::optim::LocalSearchOptimizer::start_optimization).
+/// Between steps, the state can be read with [`OptSession::checkpoint`] and modified with
+/// [`OptSession::inject`] or [`OptSession::restore`].
+/// As an [`Iterator`], it runs one iteration per item and yields its progress until the optimization stops.
+/// Time between steps counts toward `time_limit`.
+///
+/// Example
+///
+/// ```rust
+/// let mut session = opt.start(&model, None, n_iter, time_limit)?;
+/// while session.step(10)?.is_none() {
+///     println!("best score: {}", session.best_score());
+///     // ... other work ...
+/// }
+/// let result = session.finish()?;
+/// ```
+pub struct OptSession<'a, M: OptModel> {
+    model: &'a M,
+    stepper: Box<dyn OptStepper<M::SolutionType, M::ScoreType, M::TransitionType> + 'a>,
+    stop_reason: Option<StopReason>,
+}
+
+impl<'a, M: OptModel> OptSession<'a, M> {
+    /// Constructor of OptSession, for implementations of
+    /// [`LocalSearchOptimizer::start_optimization`](crate::optim::LocalSearchOptimizer::start_optimization)
+    pub fn new(
+        model: &'a M,
+        stepper: impl OptStepper<M::SolutionType, M::ScoreType, M::TransitionType> + 'a,
+    ) -> Self {
+        Self {
+            model,
+            stepper: Box::new(stepper),
+            stop_reason: None,
+        }
+    }
+
+    /// Run up to `n_steps` iterations, and return the reason why the optimization stopped, if it did.
+    /// An error of the model ends the optimization with [`StopReason::Failed`], since the state may be half updated:
+    /// later steps return it without iterating, unless the state is replaced with [`OptSession::restore`].
+    pub fn step(&mut self, n_steps: usize) -> Result<Option<StopReason>, LocalsearchError> {
+        self.step_with_observer(n_steps, &mut |_| {})
+    }
+
+    /// Same as [`OptSession::step`], reporting events to `observer`.
+    /// [`OptObserver::on_stop`] is notified once, by the step in which the optimization stops.
+    pub fn step_with_observer(
+        &mut self,
+        n_steps: usize,
+        observer: &mut dyn OptObserver<M::SolutionType, M::ScoreType, M::TransitionType>,
+    ) -> Result<Option<StopReason>, LocalsearchError> {
+        for _ in 0..n_steps {
+            if self.stop_reason.is_some() {
+                break;
+            }
+            match self.stepper.iterate(observer) {
+                Ok(Some(stop_reason)) => {
+                    self.stop_reason = Some(stop_reason);
+                    observer.on_stop(self.stepper.n_iterations(), stop_reason);
+                }
+                Ok(None) => {}
+                Err(e) => {
+                    self.stop_reason = Some(StopReason::Failed);
+                    return Err(e);
+                }
+            }
+        }
+        Ok(self.stop_reason)
+    }
+
+    /// Reason why the optimization stopped, `None` while it is running
+    pub fn stop_reason(&self) -> Option<StopReason> {
+        self.stop_reason
+    }
+
+    /// Whether the optimization stopped
+    pub fn is_finished(&self) -> bool {
+        self.stop_reason.is_some()
+    }
+
+    /// Number of iterations done
+    pub fn n_iterations(&self) -> usize {
+        self.stepper.n_iterations()
+    }
+
+    /// Score of the best solution found so far
+    pub fn best_score(&self) -> M::ScoreType {
+        self.stepper.best_score()
+    }
+
+    /// Snapshot of the full state: best solution, current solutions, counters and algorithm-specific state
+    pub fn checkpoint(&self) -> Checkpoint<M::SolutionType, M::ScoreType, M::TransitionType> {
+        self.stepper.checkpoint()
+    }
+
+    /// Replace the state with `checkpoint`, e.g. one taken by [`OptSession::checkpoint`] and modified.
+    /// A stopped optimization continues from the restored state.
+    pub fn restore(
+        &mut self,
+        checkpoint: Checkpoint<M::SolutionType, M::ScoreType, M::TransitionType>,
+    ) {
+        self.stepper.restore(checkpoint);
+        self.stop_reason = None;
+    }
+
+    /// Replace the current solution at the start of the next iteration,
+    /// like [`OptControl::inject`](crate::OptControl::inject).
+    /// The solution is in the preprocessed representation of the model.
+    pub fn inject(&mut self, solution: M::SolutionType, score: M::ScoreType) {
+        self.stepper.inject(solution, score);
+    }
+
+    /// Result holding the best solution, in the preprocessed representation of the model.
+    /// An optimization that is still running ends as [`StopReason::Cancelled`].
+    pub fn into_result(self) -> OptResult<M::SolutionType, M::ScoreType> {
+        self.stepper
+            .result(self.stop_reason.unwrap_or(StopReason::Cancelled))
+    }
+
+    /// Same as [`OptSession::into_result`], with the best solution postprocessed by the model,
+    /// to end a session returned by [`LocalSearchOptimizer::start`](crate::optim::LocalSearchOptimizer::start)
+    pub fn finish(self) -> Result<OptResult<M::SolutionType, M::ScoreType>, LocalsearchError> {
+        let model = self.model;
+        let result = self.into_result();
+        let (solution, score) = model.postprocess_solution(result.solution, result.score)?;
+        Ok(OptResult {
+            solution,
+            score,
+            ..result
+        })
+    }
+}
+
+/// Each item runs one iteration and holds its progress.
+/// The iterator ends when the optimization stops or after yielding an error.
+impl<M: OptModel> Iterator for OptSession<'_, M> {
+    type Item = Result<OptProgress<M::SolutionType, M::ScoreType>, LocalsearchError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut progress = None;
+        while progress.is_none() {
+            match self.step_with_observer(1, &mut |p| progress = Some(p)) {
+                Ok(Some(_)) => return None,
+                Ok(None) => {}
+                Err(e) => return Some(Err(e)),
+            }
+        }
+        progress.map(Ok)
+    }
+}
@@ -0,0 +1,308 @@
+//! Pluggable stop conditions
+//!
+//! A [`StopCondition`] is checked by every optimizer at the end of each iteration,
+//! in addition to `n_iter`, `time_limit` and the optimizer's own `patience`.
+
+use crate::{Duration, Energy, Instant};
+
+/// Snapshot of the optimization state passed to a [`StopCondition`]
+#[derive(Debug, Clone, Copy)]
+pub struct StopState<SC> {
+    /// number of iterations done so far
+    pub iter: usize,
+    /// elapsed time since the optimization started
+    pub elapsed: Duration,
+    /// number of trial solutions evaluated so far
+    pub n_evaluations: usize,
+    /// current score
+    pub current_score: SC,
+    /// best score found so far
+    pub best_score: SC,
+}
+
+/// Condition that decides when an optimization should stop
+pub trait StopCondition<SC> {
+    /// Return `true` to stop the optimization.
+    /// Called exactly once per iteration (per exchange round for replica based optimizers).
+    fn should_stop(&mut self, state: &StopState<SC>) -> bool;
+
+    /// Absolute deadline, if any.
+    /// Optimizers that run nested optimizers (e.g. replicas) pass it down so that all of them stop at the same instant.
+    fn deadline(&self) -> Option<Instant> {
+        None
+    }
+}
+
+impl<SC, F> StopCondition<SC> for F
+where
+    F: FnMut(&StopState<SC>) -> bool,
+{
+    fn should_stop(&mut self, state: &StopState<SC>) -> bool {
+        self(state)
+    }
+}
+
+/// A borrowed condition, e.g. to box the condition passed to
+/// [`LocalSearchOptimizer::optimize`](crate::optim::LocalSearchOptimizer::optimize) into a session
+impl<SC> StopCondition<SC> for &mut (dyn StopCondition<SC> + '_) {
+    fn should_stop(&mut self, state: &StopState<SC>) -> bool {
+        (**self).should_stop(state)
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        (**self).deadline()
+    }
+}
+
+/// Stop condition that never stops
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Never;
+
+impl<SC> StopCondition<SC> for Never {
+    fn should_stop(&mut self, _state: &StopState<SC>) -> bool {
+        false
+    }
+}
+
+/// Stop when the best score reaches the target score or better
+#[derive(Debug, Clone, Copy)]
+pub struct TargetScore<SC> {
+    target: SC,
+}
+
+impl<SC> TargetScore<SC> {
+    /// Constructor of TargetScore
+    pub fn new(target: SC) -> Self {
+        Self { target }
+    }
+}
+
+impl<SC: Ord> StopCondition<SC> for TargetScore<SC> {
+    fn should_stop(&mut self, state: &StopState<SC>) -> bool {
+        state.best_score <= self.target
+    }
+}
+
+/// Stop when the number of evaluated trial solutions reaches the budget
+#[derive(Debug, Clone, Copy)]
+pub struct EvaluationBudget {
+    max_evaluations: usize,
+}
+
+impl EvaluationBudget {
+    /// Constructor of EvaluationBudget
+    pub fn new(max_evaluations: usize) -> Self {
+        Self { max_evaluations }
+    }
+}
+
+impl<SC> StopCondition<SC> for EvaluationBudget {
+    fn should_stop(&mut self, state: &StopState<SC>) -> bool {
+        state.n_evaluations >= self.max_evaluations
+    }
+}
+
+/// Stop when the best score has not improved by more than `min_delta` for `patience` iterations
+///
+/// Unlike the optimizer's own `patience`, tiny improvements below `min_delta` do not reset the counter.
+#[derive(Debug, Clone, Copy)]
+pub struct Stagnation {
+    patience: usize,
+    min_delta: f64,
+    reference: Option<(f64, usize)>,
+}
+
+impl Stagnation {
+    /// Constructor of Stagnation
+    ///
+    /// - `patience` : number of iterations without a significant improvement before stopping
+    /// - `min_delta` : minimum decrease of the [`Energy`] of the best score that counts as an improvement
+    pub fn new(patience: usize, min_delta: f64) -> Self {
+        Self {
+            patience,
+            min_delta,
+            reference: None,
+        }
+    }
+}
+
+impl<SC: Energy> StopCondition<SC> for Stagnation {
+    fn should_stop(&mut self, state: &StopState<SC>) -> bool {
+        let best = state.best_score.energy();
+        match self.reference {
+            Some((reference_best, _)) if reference_best - best <= self.min_delta => {}
+            _ => self.reference = Some((best, state.iter)),
+        }
+        // reference is always set above
+        let (_, reference_iter) = self.reference.unwrap();
+        state.iter.saturating_sub(reference_iter) >= self.patience
+    }
+}
+
+/// Stop at an absolute point in time
+///
+/// The deadline is shared with nested optimizers, so all replicas stop at the same instant.
+#[derive(Debug, Clone, Copy)]
+pub struct Deadline {
+    instant: Instant,
+}
+
+impl Deadline {
+    /// Stop at `instant`
+    pub fn at(instant: Instant) -> Self {
+        Self { instant }
+    }
+
+    /// Stop once `duration` has passed from now
+    pub fn after(duration: Duration) -> Self {
+        Self::at(Instant::now() + duration)
+    }
+}
+
+impl<SC> StopCondition<SC> for Deadline {
+    fn should_stop(&mut self, _state: &StopState<SC>) -> bool {
+        Instant::now() >= self.instant
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        Some(self.instant)
+    }
+}
+
+/// Stop when any of the conditions is met
+///
+/// Every condition is evaluated at each check so that stateful conditions stay up to date.
+pub struct AnyOf<'a, SC> {
+    conditions: Vec<Box<dyn StopCondition<SC> + 'a>>,
+}
+
+impl<'a, SC> AnyOf<'a, SC> {
+    /// Constructor of AnyOf
+    pub fn new(conditions: Vec<Box<dyn StopCondition<SC> + 'a>>) -> Self {
+        Self { conditions }
+    }
+}
+
+impl<SC> StopCondition<SC> for AnyOf<'_, SC> {
+    fn should_stop(&mut self, state: &StopState<SC>) -> bool {
+        let mut stop = false;
+        for c in self.conditions.iter_mut() {
+            stop |= c.should_stop(state);
+        }
+        stop
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        self.conditions.iter().filter_map(|c| c.deadline()).min()
+    }
+}
+
+/// Stop when all of the conditions are met
+///
+/// Every condition is evaluated at each check so that stateful conditions stay up to date.
+pub struct AllOf<'a, SC> {
+    conditions: Vec<Box<dyn StopCondition<SC> + 'a>>,
+}
+
+impl<'a, SC> AllOf<'a, SC> {
+    /// Constructor of AllOf
+    pub fn new(conditions: Vec<Box<dyn StopCondition<SC> + 'a>>) -> Self {
+        Self { conditions }
+    }
+}
+
+impl<SC> StopCondition<SC> for AllOf<'_, SC> {
+    fn should_stop(&mut self, state: &StopState<SC>) -> bool {
+        let mut stop = !self.conditions.is_empty();
+        for c in self.conditions.iter_mut() {
+            stop &= c.should_stop(state);
+        }
+        stop
+    }
+
+    fn deadline(&self) -> Option<Instant> {
+        // the run may only be cut short when every condition is a deadline
+        self.conditions
+            .iter()
+            .map(|c| c.deadline())
+            .collect::<Option<Vec<_>>>()
+            .and_then(|deadlines| deadlines.into_iter().max())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::cmp::Reverse;
+
+    use ordered_float::NotNan;
+
+    use super::*;
+
+    fn state(iter: usize, best: f64) -> StopState<NotNan<f64>> {
+        let best_score = NotNan::new(best).unwrap();
+        StopState {
+            iter,
+            elapsed: Duration::ZERO,
+            n_evaluations: iter * 10,
+            current_score: best_score,
+            best_score,
+        }
+    }
+
+    #[test]
+    fn stagnation_ignores_small_improvements() {
+        let mut c = Stagnation::new(3, 0.5);
+        assert!(!c.should_stop(&state(0, 10.0)));
+        assert!(!c.should_stop(&state(1, 9.9)));
+        assert!(!c.should_stop(&state(2, 9.8)));
+        assert!(c.should_stop(&state(3, 9.7)));
+
+        let mut c = Stagnation::new(3, 0.5);
+        assert!(!c.should_stop(&state(0, 10.0)));
+        assert!(!c.should_stop(&state(2, 9.0)));
+        assert!(!c.should_stop(&state(4, 8.9)));
+        assert!(c.should_stop(&state(5, 8.9)));
+
+        // integer scores, maximized
+        let mut c = Stagnation::new(2, 0.5);
+        let state = |iter, best| StopState {
+            iter,
+            elapsed: Duration::ZERO,
+            n_evaluations: 0,
+            current_score: Reverse(best),
+            best_score: Reverse(best),
+        };
+        assert!(!c.should_stop(&state(0, 10_i64)));
+        assert!(!c.should_stop(&state(1, 11)));
+        assert!(!c.should_stop(&state(2, 11)));
+        assert!(c.should_stop(&state(3, 11)));
+    }
+
+    #[test]
+    fn combinators() {
+        let mut any = AnyOf::new(vec![
+            Box::new(TargetScore::new(NotNan::new(1.0).unwrap())),
+            Box::new(EvaluationBudget::new(100)),
+        ]);
+        assert!(!any.should_stop(&state(1, 5.0)));
+        assert!(any.should_stop(&state(1, 0.5)));
+        assert!(any.should_stop(&state(10, 5.0)));
+        assert!(any.deadline().is_none());
+
+        let mut all = AllOf::new(vec![
+            Box::new(TargetScore::new(NotNan::new(1.0).unwrap())),
+            Box::new(EvaluationBudget::new(100)),
+        ]);
+        assert!(!all.should_stop(&state(1, 0.5)));
+        assert!(!all.should_stop(&state(10, 5.0)));
+        assert!(all.should_stop(&state(10, 0.5)));
+
+        let deadline = Instant::now();
+        let any: AnyOf<NotNan<f64>> = AnyOf::new(vec![
+            Box::new(Deadline::at(deadline)),
+            Box::new(Deadline::at(deadline + Duration::from_secs(1))),
+            Box::new(Never),
+        ]);
+        assert_eq!(any.deadline(), Some(deadline));
+    }
+}
@@ -1,7 +1,7 @@
 use ordered_float::NotNan;
 use rand::{RngExt as _, distr::Uniform, prelude::Distribution};
 
-use crate::{LocalsearchError, OptModel};
+use crate::{IncrementalOptModel, LocalsearchError, OptModel};
 
 type SolutionType = Vec<f64>;
 type TransitionType = (usize, f64, f64);
@@ -48,26 +48,180 @@ impl OptModel for QuadraticModel {
         current_solution: Self::SolutionType,
         _current_score: Self::ScoreType,
         rng: &mut R,
-    ) -> (Self::SolutionType, Self::TransitionType, NotNan<f64>) {
+    ) -> Result<(Self::SolutionType, Self::TransitionType, NotNan<f64>), LocalsearchError> {
         let k = rng.random_range(0..self.k);
         let v = self.dist.sample(rng);
         let mut new_solution = current_solution.clone();
         new_solution[k] = v;
         let score = self.evaluate_solution(&new_solution);
-        (new_solution, (k, current_solution[k], v), score)
+        Ok((new_solution, (k, current_solution[k], v), score))
     }
 }
 
+impl IncrementalOptModel for QuadraticModel {
+    fn propose_transition<R: rand::Rng>(
+        &self,
+        current_solution: &Self::SolutionType,
+        current_score: Self::ScoreType,
+        rng: &mut R,
+    ) -> Result<(Self::TransitionType, Self::ScoreType), LocalsearchError> {
+        let k = rng.random_range(0..self.k);
+        let v = self.dist.sample(rng);
+        let old = current_solution[k];
+        let delta = (v - self.centers[k]).powf(2.0) - (old - self.centers[k]).powf(2.0);
+        let score = NotNan::new((current_score.into_inner() + delta).max(0.0)).unwrap();
+        Ok(((k, old, v), score))
+    }
+
+    fn apply_transition(
+        &self,
+        solution: &mut Self::SolutionType,
+        transition: &Self::TransitionType,
+    ) {
+        let (k, _, v) = *transition;
+        solution[k] = v;
+    }
+}
+
+type SchafferScore = [NotNan<f64>; 2];
+
+/// Schaffer's bi-objective problem `(x^2, (x - 2)^2)`, whose Pareto front is `0 <= x <= 2`
+struct SchafferModel;
+
+impl SchafferModel {
+    fn evaluate_solution(&self, x: f64) -> SchafferScore {
+        [
+            NotNan::new(x * x).unwrap(),
+            NotNan::new((x - 2.0).powi(2)).unwrap(),
+        ]
+    }
+}
+
+impl OptModel for SchafferModel {
+    type SolutionType = f64;
+    type TransitionType = ();
+    type ScoreType = SchafferScore;
+
+    fn generate_random_solution<R: rand::Rng>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        let x = rng.random_range(-10.0..10.0);
+        Ok((x, self.evaluate_solution(x)))
+    }
+
+    fn generate_trial_solution<R: rand::Rng>(
+        &self,
+        current_solution: Self::SolutionType,
+        _current_score: Self::ScoreType,
+        rng: &mut R,
+    ) -> Result<(Self::SolutionType, Self::TransitionType, Self::ScoreType), LocalsearchError> {
+        let x = current_solution + rng.random_range(-0.5..0.5);
+        Ok((x, (), self.evaluate_solution(x)))
+    }
+}
+
+/// Knapsack of 10 items and a capacity of 40, whose score maps the excess weight over the capacity
+/// and the total value of the selected items
+struct KnapsackModel<SC> {
+    weights: Vec<i64>,
+    values: Vec<i64>,
+    capacity: i64,
+    score: fn(i64, i64) -> SC,
+}
+
+impl<SC> KnapsackModel<SC> {
+    /// `score(excess_weight, value)` gives the score of a solution
+    fn new(score: fn(i64, i64) -> SC) -> Self {
+        Self {
+            weights: vec![12, 7, 11, 8, 9, 6, 14, 5, 10, 13],
+            values: vec![24, 13, 23, 15, 16, 11, 30, 9, 19, 26],
+            capacity: 40,
+            score,
+        }
+    }
+
+    fn weight(&self, solution: &[bool]) -> i64 {
+        self.weights
+            .iter()
+            .zip(solution)
+            .filter(|(_, selected)| **selected)
+            .map(|(w, _)| w)
+            .sum()
+    }
+
+    fn value(&self, solution: &[bool]) -> i64 {
+        self.values
+            .iter()
+            .zip(solution)
+            .filter(|(_, selected)| **selected)
+            .map(|(v, _)| v)
+            .sum()
+    }
+
+    fn evaluate_solution(&self, solution: &[bool]) -> SC {
+        let excess_weight = (self.weight(solution) - self.capacity).max(0);
+        (self.score)(excess_weight, self.value(solution))
+    }
+}
+
+impl<SC: Ord + Copy + Sync + Send> OptModel for KnapsackModel<SC> {
+    type SolutionType = Vec<bool>;
+    type TransitionType = usize;
+    type ScoreType = SC;
+
+    fn generate_random_solution<R: rand::Rng>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        let solution: Vec<bool> = (0..self.weights.len()).map(|_| rng.random()).collect();
+        let score = self.evaluate_solution(&solution);
+        Ok((solution, score))
+    }
+
+    fn generate_trial_solution<R: rand::Rng>(
+        &self,
+        mut current_solution: Self::SolutionType,
+        _current_score: Self::ScoreType,
+        rng: &mut R,
+    ) -> Result<(Self::SolutionType, Self::TransitionType, Self::ScoreType), LocalsearchError> {
+        let k = rng.random_range(0..current_solution.len());
+        current_solution[k] = !current_solution[k];
+        let score = self.evaluate_solution(&current_solution);
+        Ok((current_solution, k, score))
+    }
+}
+
+mod test_acceptance;
 mod test_adaptive_annealing;
+mod test_amosa;
+mod test_checkpoint;
+mod test_config;
+mod test_constraint;
+mod test_control;
+mod test_energy;
 mod test_epsilon_greedy;
+mod test_error;
 mod test_great_deluge;
 mod test_hill_climbing;
+mod test_incremental;
+mod test_lexicographic;
 mod test_logistic_annealing;
+mod test_maximize;
 mod test_metropolis;
+mod test_observer;
 mod test_parallel_tempering;
 mod test_population_annealing;
+mod test_progress;
 mod test_relative_annealing;
+mod test_reproducibility;
+mod test_result;
+mod test_scalarization;
+mod test_schedule;
+mod test_session;
 mod test_simulated_annealing;
+mod test_stop_condition;
 mod test_tabu_search;
+mod test_temperature;
 mod test_trait_object;
 mod test_tsallis;
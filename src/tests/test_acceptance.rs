@@ -0,0 +1,110 @@
+use std::{cell::RefCell, rc::Rc};
+
+use super::{QuadraticModel, ScoreType, SolutionType, TransitionType};
+use crate::{
+    AlgorithmState, Duration, OptObserver,
+    optim::{
+        AcceptanceCriterion, AcceptanceState, GenericLocalSearchOptimizer, LocalSearchOptimizer,
+    },
+    stop_condition::Never,
+};
+
+/// Barker acceptance, heating up by a constant factor at each iteration
+#[derive(Clone)]
+struct Barker {
+    beta: f64,
+    heating_rate: f64,
+}
+
+impl AcceptanceCriterion<ScoreType> for Barker {
+    fn acceptance_probability(&self, current_score: ScoreType, trial_score: ScoreType) -> f64 {
+        1.0 / (1.0 + (self.beta * (trial_score - current_score).into_inner()).exp())
+    }
+
+    fn update(&mut self, _state: &AcceptanceState<ScoreType>) {
+        self.beta *= self.heating_rate;
+    }
+
+    fn algorithm_state(&self) -> AlgorithmState {
+        AlgorithmState {
+            beta: Some(self.beta),
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Default)]
+struct Temperatures(Vec<(usize, f64)>);
+
+impl OptObserver<SolutionType, ScoreType, TransitionType> for Temperatures {
+    fn on_temperature_update(&mut self, iter: usize, beta: f64) {
+        self.0.push((iter, beta));
+    }
+}
+
+#[test]
+fn test_custom_criterion() {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let barker = Barker {
+        beta: 1.0,
+        heating_rate: 2.0,
+    };
+    let opt = GenericLocalSearchOptimizer::new(1000, 10, 200, barker);
+
+    for _ in 0..2 {
+        let mut temperatures = Temperatures::default();
+        let result = opt
+            .run_with_observer(
+                &model,
+                None,
+                20,
+                Duration::from_secs(10),
+                &mut Never,
+                None,
+                &mut rand::rng(),
+                &mut temperatures,
+            )
+            .unwrap();
+
+        // every run starts from the criterion given to the optimizer
+        assert_eq!(result.algorithm_state.beta, Some(2f64.powi(20)));
+        let expected: Vec<_> = (0..20).map(|it| (it, 2f64.powi(it as i32 + 1))).collect();
+        assert_eq!(temperatures.0, expected);
+    }
+}
+
+/// Greedy acceptance that records the states it is updated with
+#[derive(Clone, Default)]
+struct Recorder(Rc<RefCell<Vec<AcceptanceState<ScoreType>>>>);
+
+impl AcceptanceCriterion<ScoreType> for Recorder {
+    fn acceptance_probability(&self, _current_score: ScoreType, _trial_score: ScoreType) -> f64 {
+        0.0
+    }
+
+    fn update(&mut self, state: &AcceptanceState<ScoreType>) {
+        self.0.borrow_mut().push(*state);
+    }
+}
+
+#[test]
+fn test_acceptance_state() {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let recorder = Recorder::default();
+    let opt = GenericLocalSearchOptimizer::new(1000, 10, 200, recorder.clone());
+    opt.run(&model, None, 30, Duration::from_secs(10)).unwrap();
+
+    let states = recorder.0.borrow();
+    assert_eq!(states.len(), 30);
+    for (it, state) in states.iter().enumerate() {
+        assert_eq!(state.iter, it);
+        assert_eq!(state.n_iter, 30);
+        assert!((0.0..=1.0).contains(&state.elapsed_fraction));
+        assert!((0.0..=1.0).contains(&state.acceptance_ratio));
+        // only improvements are accepted, so the current solution is the best one
+        assert_eq!(state.current_score, state.best_score);
+    }
+    assert!(states.windows(2).all(|w| {
+        w[0].elapsed_fraction <= w[1].elapsed_fraction && w[1].best_score <= w[0].best_score
+    }));
+}
@@ -0,0 +1,112 @@
+use std::num::NonZero;
+
+use ordered_float::NotNan;
+use rand::{SeedableRng, rngs::StdRng};
+
+use super::{SchafferModel, SchafferScore};
+use crate::{
+    Duration, LocalsearchError, ObjectiveVector,
+    optim::{AmosaOptimizer, ParetoArchive},
+};
+
+fn score(f1: f64, f2: f64) -> SchafferScore {
+    [NotNan::new(f1).unwrap(), NotNan::new(f2).unwrap()]
+}
+
+#[test]
+fn test_pareto_archive() {
+    let mut archive = ParetoArchive::new(3, 4);
+    assert!(archive.insert("a", score(1.0, 5.0)));
+    assert!(archive.insert("b", score(5.0, 1.0)));
+    // dominated or equal scores are rejected
+    assert!(!archive.insert("c", score(2.0, 6.0)));
+    assert!(!archive.insert("d", score(1.0, 5.0)));
+    // a dominating score removes the members it dominates
+    assert!(archive.insert("e", score(1.0, 4.0)));
+    assert_eq!(archive.len(), 2);
+    assert!(archive.covers(&score(1.0, 5.0)));
+    assert_eq!(archive.dominating(&score(6.0, 6.0)).count(), 2);
+
+    for (name, f1) in [("f", 2.0), ("g", 2.2), ("h", 3.0)] {
+        assert!(archive.insert(name, score(f1, 5.0 - f1)));
+    }
+    // above the soft limit, the most crowded member is pruned and the extremes are kept
+    assert_eq!(archive.len(), 3);
+    let mut names: Vec<_> = archive.members().iter().map(|(name, _)| *name).collect();
+    names.sort();
+    assert_eq!(names, ["b", "e", "h"]);
+}
+
+#[test]
+fn test_amosa() {
+    let model = SchafferModel;
+    let opt = AmosaOptimizer::new(20, 40, 1.0, 1.0, NonZero::new(10).unwrap())
+        .with_n_initial(10)
+        .tune_cooling_rate(5000);
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let mut max_archive_len = 0;
+    let result = opt
+        .run_with_rng(
+            &model,
+            None,
+            5000,
+            Duration::from_secs(10),
+            &mut rng,
+            &mut |progress| max_archive_len = max_archive_len.max(progress.archive.len()),
+        )
+        .unwrap();
+    assert_eq!(result.n_iterations, 5000);
+    assert!(max_archive_len <= 40);
+
+    let front = &result.front;
+    assert!(front.len() >= 10 && front.len() <= 40);
+    for (i, (_, a)) in front.iter().enumerate() {
+        for (_, b) in &front[i + 1..] {
+            assert!(!a.dominates(b) && !b.dominates(a));
+        }
+    }
+    for (x, _) in front {
+        assert!((-0.05..=2.05).contains(x), "{x}");
+    }
+    // the front spreads from one extreme to the other
+    assert!(front.first().unwrap().0 < 0.2);
+    assert!(front.last().unwrap().0 > 1.8);
+}
+
+#[test]
+fn test_empty_initial_solutions() {
+    let model = SchafferModel;
+    let opt = AmosaOptimizer::new(20, 40, 1.0, 0.99, NonZero::new(10).unwrap());
+    let mut rng = StdRng::seed_from_u64(0);
+    let result = opt.run_with_rng(
+        &model,
+        Some(vec![]),
+        100,
+        Duration::from_secs(10),
+        &mut rng,
+        &mut |_| {},
+    );
+    assert!(matches!(
+        result,
+        Err(LocalsearchError::InvalidParameter {
+            name: "initial_solutions",
+            ..
+        })
+    ));
+    let result = opt.optimize(
+        &model,
+        vec![],
+        100,
+        Duration::from_secs(10),
+        &mut rng,
+        &mut |_| {},
+    );
+    assert!(matches!(
+        result,
+        Err(LocalsearchError::InvalidParameter {
+            name: "initial_solutions",
+            ..
+        })
+    ));
+}
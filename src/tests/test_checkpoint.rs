@@ -0,0 +1,154 @@
+use rand::{SeedableRng, rngs::StdRng};
+
+use super::{
+    QuadraticModel, ScoreType, SolutionType, TransitionType, test_reproducibility,
+    test_tabu_search::MyTabuList,
+};
+use crate::{
+    Checkpoint, Duration, OptControl, OptResult, StopReason,
+    optim::{
+        EpsilonGreedyOptimizer, LocalSearchOptimizer, MetropolisOptimizer, Schedule,
+        SimulatedAnnealingOptimizer, TabuSearchOptimizer,
+    },
+    stop_condition::Never,
+};
+
+const N_ITER: usize = 60;
+
+fn optimizers() -> Vec<Box<dyn LocalSearchOptimizer<QuadraticModel> + Sync>> {
+    let mut optimizers = test_reproducibility::optimizers();
+    optimizers.push(Box::new(TabuSearchOptimizer::<MyTabuList>::new(
+        100, 10, 20, 10,
+    )));
+    // scheduled parameters continue from the checkpoint
+    optimizers.push(Box::new(
+        EpsilonGreedyOptimizer::new(100, 10, 20, 0.5)
+            .with_epsilon_schedule(Schedule::linear(0.5, 0.0)),
+    ));
+    optimizers.push(Box::new(
+        MetropolisOptimizer::new(100, 10, 20, 1.0)
+            .with_beta_schedule(Schedule::geometric(1.0, 100.0)),
+    ));
+    optimizers
+}
+
+fn model() -> QuadraticModel {
+    QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0))
+}
+
+/// Run until half of `N_ITER`, take a checkpoint and stop
+fn run_until_checkpoint(
+    opt: &dyn LocalSearchOptimizer<QuadraticModel>,
+    rng: &mut StdRng,
+) -> Checkpoint<SolutionType, ScoreType, TransitionType> {
+    let control = OptControl::new();
+    let callback_control = control.clone();
+    let result = opt
+        .run_with_control(
+            &model(),
+            None,
+            N_ITER,
+            Duration::from_secs(60),
+            &mut Never,
+            Some(&control),
+            rng,
+            &mut |progress| {
+                if progress.iter + 1 >= N_ITER / 2 {
+                    callback_control.request_checkpoint();
+                    callback_control.cancel();
+                }
+            },
+        )
+        .unwrap();
+    assert_eq!(result.stop_reason, StopReason::Cancelled);
+    control.take_checkpoint().unwrap()
+}
+
+fn resume(
+    opt: &dyn LocalSearchOptimizer<QuadraticModel>,
+    checkpoint: Checkpoint<SolutionType, ScoreType, TransitionType>,
+    rng: &mut StdRng,
+) -> OptResult<SolutionType, ScoreType> {
+    opt.resume(
+        &model(),
+        checkpoint,
+        N_ITER,
+        Duration::from_secs(60),
+        &mut Never,
+        None,
+        rng,
+        &mut |_| {},
+    )
+    .unwrap()
+}
+
+fn run_uninterrupted(
+    opt: &dyn LocalSearchOptimizer<QuadraticModel>,
+    seed: u64,
+) -> OptResult<SolutionType, ScoreType> {
+    opt.run_with_rng(
+        &model(),
+        None,
+        N_ITER,
+        Duration::from_secs(60),
+        &mut StdRng::seed_from_u64(seed),
+        &mut |_| {},
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_resume_continues_as_if_uninterrupted() {
+    for opt in optimizers() {
+        // the generator is not part of the checkpoint, the same one continues
+        let mut rng = StdRng::seed_from_u64(42);
+        let checkpoint = run_until_checkpoint(&opt, &mut rng);
+        assert_eq!(checkpoint.iter, N_ITER / 2);
+        let resumed = resume(&opt, checkpoint, &mut rng);
+
+        let expected = run_uninterrupted(&opt, 42);
+        assert_eq!(resumed.solution, expected.solution);
+        assert_eq!(resumed.score, expected.score);
+        assert_eq!(resumed.n_iterations, expected.n_iterations);
+        assert_eq!(resumed.n_evaluations, expected.n_evaluations);
+        assert_eq!(resumed.stop_reason, expected.stop_reason);
+        assert_eq!(resumed.algorithm_state, expected.algorithm_state);
+    }
+}
+
+#[test]
+fn test_checkpoint_state() {
+    let update_frequency = std::num::NonZero::new(10).unwrap();
+    let opt = SimulatedAnnealingOptimizer::new(100, 10, 20, 1.0, 0.5, update_frequency);
+    let checkpoint = run_until_checkpoint(&opt, &mut StdRng::seed_from_u64(0));
+    // cooled at iterations 10 and 20
+    assert_eq!(checkpoint.algorithm_state.beta, Some(0.25));
+    assert_eq!(checkpoint.n_evaluations, N_ITER / 2 * 10);
+    assert_eq!(checkpoint.current.len(), 1);
+    assert!(checkpoint.best_score <= checkpoint.current[0].1);
+
+    let opt = TabuSearchOptimizer::<MyTabuList>::new(100, 10, 20, 10);
+    let checkpoint = run_until_checkpoint(&opt, &mut StdRng::seed_from_u64(0));
+    assert!(!checkpoint.tabu_list.is_empty());
+    assert!(checkpoint.tabu_list.len() <= 10);
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_serde_roundtrip() {
+    let opt = TabuSearchOptimizer::<MyTabuList>::new(100, 10, 20, 10);
+    let mut rng = StdRng::seed_from_u64(42);
+    let checkpoint = run_until_checkpoint(&opt, &mut rng);
+
+    let json = serde_json::to_string(&checkpoint).unwrap();
+    let restored: Checkpoint<SolutionType, ScoreType, TransitionType> =
+        serde_json::from_str(&json).unwrap();
+    assert_eq!(restored.iter, checkpoint.iter);
+    assert_eq!(restored.best_solution, checkpoint.best_solution);
+    assert_eq!(restored.tabu_list, checkpoint.tabu_list);
+
+    let resumed = resume(&opt, restored, &mut rng);
+    let expected = run_uninterrupted(&opt, 42);
+    assert_eq!(resumed.solution, expected.solution);
+    assert_eq!(resumed.n_evaluations, expected.n_evaluations);
+}
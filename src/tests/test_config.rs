@@ -0,0 +1,242 @@
+use std::num::NonZero;
+
+use ordered_float::NotNan;
+
+use super::{QuadraticModel, test_tabu_search::MyTabuList};
+use crate::{
+    Duration, LocalsearchError,
+    optim::{
+        AdaptiveAnnealingOptimizer, AdaptiveScheduler, AmosaOptimizer, EpsilonGreedyOptimizer,
+        GenericLocalSearchOptimizer, HillClimbingOptimizer, LocalSearchOptimizer, OptimizerConfig,
+        ParallelTemperingConfig, ParallelTemperingOptimizer, PopulationAnnealingOptimizer,
+        ReheatPolicy, SimulatedAnnealingOptimizer, TabuSearchOptimizer, TargetAccScheduleMode,
+        TsallisRelativeAnnealingOptimizer,
+    },
+};
+
+/// Name of the parameter rejected by a builder
+fn invalid_parameter<T>(result: Result<T, LocalsearchError>) -> &'static str {
+    match result {
+        Err(LocalsearchError::InvalidParameter { name, .. }) => name,
+        Err(e) => panic!("unexpected error {e}"),
+        Ok(_) => panic!("invalid parameters were accepted"),
+    }
+}
+
+#[test]
+fn test_from_name_builds_every_algorithm() {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    for name in OptimizerConfig::ALGORITHMS {
+        let config = OptimizerConfig::from_name(name).unwrap();
+        assert_eq!(config.name(), name);
+        let opt = config
+            .build_with_tabu_list::<QuadraticModel, MyTabuList>()
+            .unwrap();
+        let result = opt.run(&model, None, 10, Duration::from_secs(10)).unwrap();
+        assert!(result.n_iterations > 0);
+    }
+}
+
+#[test]
+fn test_build_ordinal() {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    for name in OptimizerConfig::ALGORITHMS {
+        let opt = OptimizerConfig::from_name(name)
+            .unwrap()
+            .build_ordinal_with_tabu_list::<QuadraticModel, MyTabuList>();
+        if OptimizerConfig::ORDINAL_ALGORITHMS.contains(&name) {
+            let result = opt
+                .unwrap()
+                .run(&model, None, 10, Duration::from_secs(10))
+                .unwrap();
+            assert!(result.n_iterations > 0);
+        } else {
+            assert_eq!(invalid_parameter(opt), "algorithm", "{name}");
+        }
+    }
+}
+
+#[test]
+fn test_invalid_config() {
+    assert!(matches!(
+        OptimizerConfig::from_name("unknown"),
+        Err(LocalsearchError::ConfigError(_))
+    ));
+    let tabu = OptimizerConfig::from_name("tabu_search").unwrap();
+    assert!(tabu.build::<QuadraticModel>().is_err());
+    let empty_ladder = OptimizerConfig::ParallelTempering(ParallelTemperingConfig {
+        betas: Some(vec![]),
+        ..Default::default()
+    });
+    assert!(matches!(
+        empty_ladder.build::<QuadraticModel>(),
+        Err(LocalsearchError::InvalidParameter { name: "betas", .. })
+    ));
+}
+
+#[test]
+fn test_builders() {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let opt = SimulatedAnnealingOptimizer::builder()
+        .with_n_trials(8)
+        .with_initial_beta(0.5)
+        .with_cooling_rate(1.05)
+        .with_update_frequency(NonZero::new(10).unwrap())
+        .with_reheat(ReheatPolicy::Cyclic {
+            period: NonZero::new(500).unwrap(),
+        })
+        .build()
+        .unwrap();
+    let result = opt
+        .run(&model, None, 1000, Duration::from_secs(10))
+        .unwrap();
+    assert!(result.algorithm_state.n_reheats.unwrap() > 0);
+
+    let opt = ParallelTemperingOptimizer::builder()
+        .with_betas(vec![0.1, 1.0, 10.0])
+        .build()
+        .unwrap();
+    let result = opt.run(&model, None, 100, Duration::from_secs(10)).unwrap();
+    assert_eq!(result.algorithm_state.betas.unwrap().len(), 3);
+    let opt = TabuSearchOptimizer::<MyTabuList>::builder()
+        .with_tabu_size(5)
+        .build::<MyTabuList>()
+        .unwrap();
+    assert!(opt.run(&model, None, 100, Duration::from_secs(10)).is_ok());
+    let opt = GenericLocalSearchOptimizer::builder(|_, _| 0.0)
+        .with_n_trials(4)
+        .with_return_iter(10)
+        .build()
+        .unwrap();
+    let result = opt.run(&model, None, 100, Duration::from_secs(10)).unwrap();
+    assert_eq!(result.n_evaluations, 400);
+}
+
+#[test]
+fn test_builder_validation() {
+    let name = invalid_parameter(HillClimbingOptimizer::builder().with_n_trials(0).build());
+    assert_eq!(name, "n_trials");
+    let greedy = |_: NotNan<f64>, _: NotNan<f64>| 0.0;
+    let name = invalid_parameter(
+        GenericLocalSearchOptimizer::builder(greedy)
+            .with_n_trials(0)
+            .build(),
+    );
+    assert_eq!(name, "n_trials");
+    let name = invalid_parameter(EpsilonGreedyOptimizer::builder().with_epsilon(1.5).build());
+    assert_eq!(name, "epsilon");
+    let sa = SimulatedAnnealingOptimizer::builder();
+    assert_eq!(
+        invalid_parameter(sa.with_initial_beta(-1.0).build()),
+        "initial_beta"
+    );
+    assert_eq!(
+        invalid_parameter(sa.with_cooling_rate(f64::NAN).build()),
+        "cooling_rate"
+    );
+    let reheat = ReheatPolicy::LowAcceptance {
+        threshold: 0.1,
+        fraction: 2.0,
+    };
+    assert_eq!(
+        invalid_parameter(sa.with_reheat(reheat).build()),
+        "fraction"
+    );
+    let scheduler = AdaptiveScheduler::new(0.5, 0.0, TargetAccScheduleMode::Exponential, 0.05);
+    assert_eq!(
+        invalid_parameter(
+            AdaptiveAnnealingOptimizer::builder()
+                .with_scheduler(scheduler)
+                .build()
+        ),
+        "final_target_acc"
+    );
+    assert_eq!(
+        invalid_parameter(
+            TsallisRelativeAnnealingOptimizer::builder()
+                .with_q(1.0)
+                .build()
+        ),
+        "q"
+    );
+    let pt = ParallelTemperingOptimizer::builder();
+    assert_eq!(
+        invalid_parameter(pt.clone().with_n_replicas(0).build()),
+        "n_replicas"
+    );
+    assert_eq!(
+        invalid_parameter(pt.clone().with_beta_max(1e-4).build()),
+        "beta_max"
+    );
+    assert_eq!(
+        invalid_parameter(pt.with_betas(vec![1.0, -1.0]).build()),
+        "betas"
+    );
+    assert_eq!(
+        invalid_parameter(
+            PopulationAnnealingOptimizer::builder()
+                .with_population_size(0)
+                .build()
+        ),
+        "population_size"
+    );
+    assert_eq!(
+        invalid_parameter(AmosaOptimizer::builder().with_soft_limit(10).build()),
+        "soft_limit"
+    );
+
+    // every default configuration is valid
+    for name in OptimizerConfig::ALGORITHMS {
+        let config = OptimizerConfig::from_name(name).unwrap();
+        assert!(
+            config
+                .build_with_tabu_list::<QuadraticModel, MyTabuList>()
+                .is_ok()
+        );
+    }
+    assert!(AmosaOptimizer::builder().build().is_ok());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn test_deserialize() {
+    use crate::optim::SimulatedAnnealingConfig;
+
+    let config: OptimizerConfig = serde_json::from_str(
+        r#"{"algorithm": "simulated_annealing", "n_trials": 32, "update_frequency": 10}"#,
+    )
+    .unwrap();
+    let OptimizerConfig::SimulatedAnnealing(sa) = config else {
+        panic!("expected simulated annealing, got {config:?}");
+    };
+    assert_eq!(sa.n_trials, 32);
+    assert_eq!(sa.update_frequency.get(), 10);
+    assert_eq!(sa.patience, SimulatedAnnealingConfig::default().patience);
+
+    let config: OptimizerConfig = serde_json::from_str(
+        r#"{"algorithm": "adaptive_annealing", "scheduler": {"schedule_mode": "linear"}}"#,
+    )
+    .unwrap();
+    assert_eq!(config.name(), "adaptive_annealing");
+    assert!(
+        serde_json::from_str::<OptimizerConfig>(
+            r#"{"algorithm": "adaptive_annealing", "scheduler": {"schedule_mode": "unknown"}}"#
+        )
+        .is_err()
+    );
+
+    assert!(
+        serde_json::from_str::<OptimizerConfig>(
+            r#"{"algorithm": "simulated_annealing", "update_frequency": 0}"#
+        )
+        .is_err()
+    );
+    assert!(serde_json::from_str::<OptimizerConfig>(r#"{"algorithm": "unknown"}"#).is_err());
+
+    for name in OptimizerConfig::ALGORITHMS {
+        let config = OptimizerConfig::from_name(name).unwrap();
+        let json = serde_json::to_string(&config).unwrap();
+        let restored: OptimizerConfig = serde_json::from_str(&json).unwrap();
+        assert_eq!(restored.name(), name);
+    }
+}
@@ -0,0 +1,155 @@
+use std::{
+    num::NonZero,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use ordered_float::NotNan;
+use rand::{SeedableRng, rngs::StdRng};
+
+use super::KnapsackModel;
+use crate::{
+    ConstrainedOptModel, Duration, LocalsearchError, OptModel, PenalizedModel, PenalizedScore,
+    StrategicOscillation, TrialSolution,
+    optim::{LocalSearchOptimizer, SimulatedAnnealingOptimizer},
+    stop_condition::Never,
+};
+
+/// Knapsack minimizing the negated value, with the excess weight over the capacity as the only violation
+struct ConstrainedKnapsack {
+    knapsack: KnapsackModel<NotNan<f64>>,
+    /// repair overweight solutions by removing items
+    repair: bool,
+    n_repairs: AtomicUsize,
+}
+
+impl ConstrainedKnapsack {
+    fn new(repair: bool) -> Self {
+        Self {
+            knapsack: KnapsackModel::new(|_, value| NotNan::new(-value as f64).unwrap()),
+            repair,
+            n_repairs: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl OptModel for ConstrainedKnapsack {
+    type SolutionType = Vec<bool>;
+    type TransitionType = usize;
+    type ScoreType = NotNan<f64>;
+
+    fn generate_random_solution<R: rand::Rng>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        self.knapsack.generate_random_solution(rng)
+    }
+
+    fn generate_trial_solution<R: rand::Rng>(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+        rng: &mut R,
+    ) -> TrialSolution<Self> {
+        self.knapsack
+            .generate_trial_solution(current_solution, current_score, rng)
+    }
+}
+
+impl ConstrainedOptModel for ConstrainedKnapsack {
+    fn violations(&self, solution: &Self::SolutionType) -> Vec<f64> {
+        vec![(self.knapsack.weight(solution) - self.knapsack.capacity).max(0) as f64]
+    }
+
+    fn repair(
+        &self,
+        solution: &Self::SolutionType,
+        _score: Self::ScoreType,
+        _violations: &[f64],
+    ) -> Option<(Self::SolutionType, Self::ScoreType)> {
+        if !self.repair {
+            return None;
+        }
+        self.n_repairs.fetch_add(1, Ordering::Relaxed);
+        let mut repaired = solution.clone();
+        for k in 0..repaired.len() {
+            if self.knapsack.weight(&repaired) <= self.knapsack.capacity {
+                break;
+            }
+            repaired[k] = false;
+        }
+        let score = self.knapsack.evaluate_solution(&repaired);
+        Some((repaired, score))
+    }
+}
+
+#[test]
+fn test_penalized_score() {
+    let model = PenalizedModel::new(ConstrainedKnapsack::new(false), 2.0).with_weights(vec![3.0]);
+    // weight 12 + 7 + 11 + 8 + 9 = 47, value 91
+    let solution = vec![
+        true, true, true, true, true, false, false, false, false, false,
+    ];
+    let score = PenalizedScore::new(
+        model.inner().knapsack.evaluate_solution(&solution),
+        0.0,
+        2.0,
+    );
+    // the violations are evaluated by the model
+    let (_, score) = model.preprocess_solution(solution, score).unwrap();
+    assert!(!score.is_feasible());
+    assert_eq!(score.objective(), -91.0);
+    assert_eq!(score.violation(), 21.0);
+    assert_eq!(score.penalized(), -91.0 + 2.0 * 21.0);
+    assert!(model.best_feasible().is_none());
+
+    // a feasible solution is compared by its objective alone
+    let feasible = PenalizedScore::new(NotNan::new(-60.0).unwrap(), 0.0, 2.0);
+    assert!(feasible < score);
+}
+
+#[test]
+fn test_repair() {
+    let model = PenalizedModel::new(ConstrainedKnapsack::new(true), 1.0);
+    let mut rng = StdRng::seed_from_u64(0);
+    for _ in 0..20 {
+        let (solution, score) = model.generate_random_solution(&mut rng).unwrap();
+        assert!(score.is_feasible());
+        assert!(model.inner().knapsack.weight(&solution) <= 40);
+    }
+    assert!(model.inner().n_repairs.load(Ordering::Relaxed) > 0);
+    let (solution, objective) = model.best_feasible().unwrap();
+    assert_eq!(
+        model.inner().knapsack.evaluate_solution(&solution),
+        objective
+    );
+}
+
+#[test]
+fn test_strategic_oscillation() {
+    // without penalty, the search prefers overweight solutions
+    let model = PenalizedModel::new(ConstrainedKnapsack::new(false), 1e-3);
+    let opt =
+        SimulatedAnnealingOptimizer::new(10000, 10, 200, 1.0, 0.999, NonZero::new(10).unwrap());
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let mut oscillation = StrategicOscillation::new(&model, NonZero::new(20).unwrap(), 2.0);
+    opt.run_with_observer(
+        &model,
+        None,
+        3000,
+        Duration::from_secs(10),
+        &mut Never,
+        None,
+        &mut rng,
+        &mut oscillation,
+    )
+    .unwrap();
+    assert!(model.multiplier() > 1e-3);
+    let (solution, objective) = model.best_feasible().unwrap();
+    assert!(model.inner().knapsack.weight(&solution) <= 40);
+    assert!(objective.into_inner() < -70.0);
+
+    model.reset();
+    assert_eq!(model.multiplier(), 1e-3);
+    assert!(model.best_feasible().is_none());
+}
@@ -0,0 +1,107 @@
+use std::thread;
+
+use super::{QuadraticModel, test_reproducibility, test_tabu_search::MyTabuList};
+use crate::{
+    Duration, OptControl, StopReason,
+    optim::{HillClimbingOptimizer, LocalSearchOptimizer, TabuSearchOptimizer},
+    stop_condition::Never,
+};
+
+/// Optimizers that only stop when cancelled
+fn optimizers() -> Vec<Box<dyn LocalSearchOptimizer<QuadraticModel> + Sync>> {
+    let mut optimizers = test_reproducibility::optimizers_with_patience(usize::MAX);
+    optimizers.push(Box::new(TabuSearchOptimizer::<MyTabuList>::new(
+        usize::MAX,
+        10,
+        usize::MAX,
+        10,
+    )));
+    optimizers
+}
+
+#[test]
+fn test_cancel() {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    for opt in optimizers() {
+        let control = OptControl::new();
+        let result = thread::scope(|s| {
+            let handle = s.spawn(|| {
+                opt.run_with_control(
+                    &model,
+                    None,
+                    usize::MAX,
+                    Duration::from_secs(60),
+                    &mut Never,
+                    Some(&control),
+                    &mut rand::rng(),
+                    &mut |_| {},
+                )
+                .unwrap()
+            });
+            while control.best_score().is_none() {
+                thread::yield_now();
+            }
+            control.cancel();
+            handle.join().unwrap()
+        });
+        assert_eq!(result.stop_reason, StopReason::Cancelled);
+        assert_eq!(control.best_score(), Some(result.score));
+    }
+}
+
+#[test]
+fn test_pause_and_resume() {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let opt = HillClimbingOptimizer::new(usize::MAX, 10);
+    let control = OptControl::new();
+    control.pause();
+    let pause_duration = Duration::from_millis(500);
+    let result = thread::scope(|s| {
+        let handle = s.spawn(|| {
+            opt.run_with_control(
+                &model,
+                None,
+                10,
+                Duration::from_millis(100),
+                &mut Never,
+                Some(&control),
+                &mut rand::rng(),
+                &mut |_| {},
+            )
+            .unwrap()
+        });
+        thread::sleep(pause_duration);
+        assert!(!handle.is_finished());
+        control.resume();
+        handle.join().unwrap()
+    });
+    // time spent paused does not count toward the time limit
+    assert_eq!(result.stop_reason, StopReason::MaxIterations);
+    assert_eq!(result.n_iterations, 10);
+    assert!(result.elapsed < pause_duration);
+}
+
+#[test]
+fn test_inject() {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let optimum = vec![2.0, 0.0, -3.5];
+    for opt in optimizers() {
+        let control = OptControl::new();
+        control.inject(optimum.clone(), model.evaluate_solution(&optimum));
+        let result = opt
+            .run_with_control(
+                &model,
+                None,
+                20,
+                Duration::from_secs(60),
+                &mut Never,
+                Some(&control),
+                &mut rand::rng(),
+                &mut |_| {},
+            )
+            .unwrap();
+        assert_eq!(result.solution, optimum);
+        assert_eq!(result.score.into_inner(), 0.0);
+        assert_eq!(control.best(), Some((result.solution, result.score)));
+    }
+}
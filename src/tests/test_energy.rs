@@ -0,0 +1,145 @@
+use std::num::NonZero;
+
+use ordered_float::NotNan;
+use rand::{RngExt as _, SeedableRng, rngs::StdRng};
+
+use crate::{
+    Duration, Energy, LocalsearchError, OptModel,
+    optim::{
+        EnergySample, LocalSearchOptimizer, MetropolisOptimizer, OptimizerConfig,
+        SimulatedAnnealingOptimizer, TemperatureEstimator,
+    },
+};
+
+/// Custom score type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Cost(u32);
+
+impl Energy for Cost {
+    fn energy(&self) -> f64 {
+        self.0 as f64
+    }
+}
+
+/// Integer quadratic model, with the squared distance to the centers converted to `ST`
+struct IntegerModel<ST> {
+    centers: Vec<i64>,
+    to_score: fn(i64) -> ST,
+}
+
+impl<ST> IntegerModel<ST> {
+    fn new(to_score: fn(i64) -> ST) -> Self {
+        Self {
+            centers: vec![7, -3, 0, 12],
+            to_score,
+        }
+    }
+
+    fn evaluate_solution(&self, solution: &[i64]) -> ST {
+        let distance = solution
+            .iter()
+            .zip(&self.centers)
+            .map(|(x, c)| (x - c).pow(2))
+            .sum();
+        (self.to_score)(distance)
+    }
+}
+
+impl<ST: Energy> OptModel for IntegerModel<ST> {
+    type SolutionType = Vec<i64>;
+    type TransitionType = usize;
+    type ScoreType = ST;
+
+    fn generate_random_solution<R: rand::Rng>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        let solution: Vec<i64> = self
+            .centers
+            .iter()
+            .map(|_| rng.random_range(-20..=20))
+            .collect();
+        let score = self.evaluate_solution(&solution);
+        Ok((solution, score))
+    }
+
+    fn generate_trial_solution<R: rand::Rng>(
+        &self,
+        mut current_solution: Self::SolutionType,
+        _current_score: Self::ScoreType,
+        rng: &mut R,
+    ) -> Result<(Self::SolutionType, Self::TransitionType, Self::ScoreType), LocalsearchError> {
+        let k = rng.random_range(0..current_solution.len());
+        current_solution[k] += if rng.random() { 1 } else { -1 };
+        let score = self.evaluate_solution(&current_solution);
+        Ok((current_solution, k, score))
+    }
+}
+
+#[test]
+fn test_energy() {
+    assert_eq!(3u8.energy_delta(&1), -2.0);
+    assert_eq!((-4i64).energy(), -4.0);
+    assert_eq!(NotNan::new(1.5f32).unwrap().energy(), 1.5);
+    assert_eq!(Cost(2).energy_delta(&Cost(5)), 3.0);
+}
+
+#[test]
+fn test_every_algorithm_on_integer_scores() {
+    let model = IntegerModel::new(|distance| distance);
+    for name in OptimizerConfig::ALGORITHMS {
+        if name == "tabu_search" {
+            continue;
+        }
+        let opt = OptimizerConfig::from_name(name)
+            .unwrap()
+            .build::<IntegerModel<i64>>()
+            .unwrap();
+        let result = opt.run(&model, None, 50, Duration::from_secs(10)).unwrap();
+        assert!(result.n_iterations > 0, "{name}");
+    }
+}
+
+#[test]
+fn test_annealing_custom_scores() {
+    let mut rng = StdRng::seed_from_u64(0);
+
+    let model = IntegerModel::new(|distance| Cost(distance as u32));
+    let sample = EnergySample::collect(&model, 4, 50, &mut rng).unwrap();
+    // the neighbor of an integer solution changes the score by an odd integer
+    assert!(sample.deltas().all(|delta| delta >= 1.0));
+    let opt = SimulatedAnnealingOptimizer::new(10000, 8, 500, 1.0, 0.99, NonZero::new(10).unwrap())
+        .estimate_initial_temperature(
+            &sample,
+            TemperatureEstimator::BenAmeur {
+                target_acceptance: 0.5,
+            },
+        )
+        .unwrap()
+        .tune_cooling_rate(3000);
+    let result = opt
+        .run_with_rng(
+            &model,
+            None,
+            3000,
+            Duration::from_secs(10),
+            &mut rng,
+            &mut |_| {},
+        )
+        .unwrap();
+    assert_eq!(result.score, Cost(0));
+
+    let model = IntegerModel::new(|distance| NotNan::new(distance as f32).unwrap());
+    let opt = MetropolisOptimizer::new(10000, 8, 500, 2.0);
+    let result = opt
+        .run_with_rng(
+            &model,
+            None,
+            3000,
+            Duration::from_secs(10),
+            &mut rng,
+            &mut |_| {},
+        )
+        .unwrap();
+    assert_eq!(result.score.into_inner(), 0.0);
+}
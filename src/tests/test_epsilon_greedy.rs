@@ -11,7 +11,8 @@ fn test() {
     let opt = EpsilonGreedyOptimizer::new(1000, 10, 200, 0.1);
     let (final_solution, final_score) = opt
         .run(&model, None, 10000, Duration::from_secs(10))
-        .unwrap();
+        .unwrap()
+        .into_solution_and_score();
     assert_abs_diff_eq!(2.0, final_solution[0], epsilon = 0.05);
     assert_abs_diff_eq!(0.0, final_solution[1], epsilon = 0.05);
     assert_abs_diff_eq!(-3.5, final_solution[2], epsilon = 0.05);
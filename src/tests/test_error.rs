@@ -0,0 +1,215 @@
+use std::num::NonZero;
+
+use ordered_float::NotNan;
+use rand::{RngExt as _, SeedableRng, rngs::StdRng};
+
+use super::{
+    QuadraticModel, ScoreType, SolutionType, TransitionType, test_tabu_search::MyTabuList,
+};
+use crate::{
+    Duration, LocalsearchError, OptModel, StopReason, TrialSolution,
+    optim::{
+        LocalSearchOptimizer, OptimizerConfig, ParallelTemperingOptimizer,
+        SimulatedAnnealingOptimizer,
+    },
+};
+
+/// Error payload of [`FaultyModel`]
+#[derive(Debug, thiserror::Error)]
+#[error("invalid state")]
+struct InvalidState;
+
+/// How [`FaultyModel`] fails
+enum Fault {
+    /// No valid move with the given probability
+    NoValidMove(f64),
+    /// Trial generation returns an error
+    Error,
+    /// Trial generation panics
+    Panic,
+    /// Postprocessing returns an error
+    Postprocess,
+}
+
+/// Quadratic model failing as configured
+struct FaultyModel {
+    quadratic: QuadraticModel,
+    fault: Fault,
+}
+
+impl FaultyModel {
+    fn new(fault: Fault) -> Self {
+        Self {
+            quadratic: QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0)),
+            fault,
+        }
+    }
+}
+
+impl OptModel for FaultyModel {
+    type SolutionType = SolutionType;
+    type TransitionType = TransitionType;
+    type ScoreType = ScoreType;
+
+    fn generate_random_solution<R: rand::Rng>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        self.quadratic.generate_random_solution(rng)
+    }
+
+    fn generate_trial_solution<R: rand::Rng>(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+        rng: &mut R,
+    ) -> TrialSolution<Self> {
+        match self.fault {
+            Fault::NoValidMove(p) if rng.random_bool(p) => Err(LocalsearchError::NoValidMove),
+            Fault::Error => Err(LocalsearchError::model(InvalidState)),
+            Fault::Panic => panic!("invalid state"),
+            _ => self
+                .quadratic
+                .generate_trial_solution(current_solution, current_score, rng),
+        }
+    }
+
+    fn postprocess_solution(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        match self.fault {
+            Fault::Postprocess => Err(LocalsearchError::model(InvalidState)),
+            _ => Ok((current_solution, current_score)),
+        }
+    }
+}
+
+/// Run every algorithm from the zero solution, with the score of the result
+fn run_every_algorithm(
+    model: &FaultyModel,
+    n_iter: usize,
+) -> impl Iterator<Item = (&'static str, Result<ScoreType, LocalsearchError>)> {
+    let initial_solution = vec![0.0; 3];
+    let initial_score = model.quadratic.evaluate_solution(&initial_solution);
+    OptimizerConfig::ALGORITHMS.into_iter().map(move |name| {
+        let opt = OptimizerConfig::from_name(name)
+            .unwrap()
+            .build_with_tabu_list::<FaultyModel, MyTabuList>()
+            .unwrap();
+        let result = opt.run_with_rng(
+            model,
+            Some((initial_solution.clone(), initial_score)),
+            n_iter,
+            Duration::from_secs(10),
+            &mut StdRng::seed_from_u64(0),
+            &mut |_| {},
+        );
+        (name, result.map(|result| result.score))
+    })
+}
+
+#[test]
+fn test_no_valid_move() {
+    let initial_score = NotNan::new(16.25).unwrap();
+    // moves that are sometimes invalid are rejected without stopping the optimization
+    let model = FaultyModel::new(Fault::NoValidMove(0.5));
+    for (name, score) in run_every_algorithm(&model, 200) {
+        assert!(score.unwrap() < initial_score, "{name}");
+    }
+
+    // without any valid move, the initial solution is returned
+    let model = FaultyModel::new(Fault::NoValidMove(1.0));
+    for (name, score) in run_every_algorithm(&model, 50) {
+        assert_eq!(score.unwrap(), initial_score, "{name}");
+    }
+}
+
+#[test]
+fn test_model_error() {
+    for fault in [Fault::Error, Fault::Postprocess] {
+        let model = FaultyModel::new(fault);
+        for (name, score) in run_every_algorithm(&model, 200) {
+            let Err(LocalsearchError::ModelError(payload)) = score else {
+                panic!("{name} did not return the model error");
+            };
+            assert!(payload.downcast_ref::<InvalidState>().is_some(), "{name}");
+        }
+    }
+}
+
+#[test]
+fn test_model_panic() {
+    let model = FaultyModel::new(Fault::Panic);
+    for (name, score) in run_every_algorithm(&model, 200) {
+        let Err(LocalsearchError::ModelPanic(message)) = score else {
+            panic!("{name} did not catch the panic");
+        };
+        assert_eq!(message, "invalid state", "{name}");
+    }
+}
+
+#[test]
+fn test_session_error() {
+    let model = FaultyModel::new(Fault::Error);
+    for name in OptimizerConfig::ALGORITHMS {
+        let opt = OptimizerConfig::from_name(name)
+            .unwrap()
+            .build_with_tabu_list::<FaultyModel, MyTabuList>()
+            .unwrap();
+        // population annealing draws trial solutions to initialize its population
+        let Ok(mut session) = opt.start(&model, None, 200, Duration::from_secs(10)) else {
+            assert_eq!(name, "population_annealing");
+            continue;
+        };
+        assert!(session.step(10).is_err(), "{name}");
+        // the error ends the session, which is not stepped any further
+        assert_eq!(
+            session.step(10).unwrap(),
+            Some(StopReason::Failed),
+            "{name}"
+        );
+        assert!(session.next().is_none(), "{name}");
+        assert_eq!(session.into_result().stop_reason, StopReason::Failed);
+
+        // the iterator ends after the error
+        let mut session = opt
+            .start(&model, None, 200, Duration::from_secs(10))
+            .unwrap();
+        assert!(matches!(session.next(), Some(Err(_))), "{name}");
+        assert!(session.next().is_none(), "{name}");
+    }
+}
+
+#[test]
+fn test_tuning_error() {
+    let mut rng = StdRng::seed_from_u64(0);
+    let opt = SimulatedAnnealingOptimizer::new(100, 10, 20, 1.0, 0.99, NonZero::new(10).unwrap());
+    // the error of the model is returned instead of tuning from the other trials
+    let model = FaultyModel::new(Fault::Error);
+    let result = opt
+        .clone()
+        .tune_initial_temperature(&model, None, 50, 0.5, &mut rng);
+    assert!(matches!(result, Err(LocalsearchError::ModelError(_))));
+    let model = FaultyModel::new(Fault::Panic);
+    let result = ParallelTemperingOptimizer::with_geometric_betas(
+        100,
+        10,
+        20,
+        4,
+        1e-2,
+        1e2,
+        NonZero::new(10).unwrap(),
+    )
+    .tune_temperature(&model, None, 50, 0.5, 0.01, &mut rng);
+    assert!(matches!(result, Err(LocalsearchError::ModelPanic(_))));
+
+    // trials without a valid move are left out
+    let model = FaultyModel::new(Fault::NoValidMove(1.0));
+    let opt = opt
+        .tune_initial_temperature(&model, None, 50, 0.5, &mut rng)
+        .unwrap();
+    let result = opt.run(&model, None, 10, Duration::from_secs(10)).unwrap();
+    assert_eq!(result.algorithm_state.beta, Some(1.0));
+}
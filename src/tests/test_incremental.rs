@@ -0,0 +1,78 @@
+use std::{num::NonZero, time::Duration};
+
+use approx::assert_abs_diff_eq;
+
+use super::{QuadraticModel, test_tabu_search::MyTabuList};
+use crate::{
+    IncrementalOptModel, OptModel,
+    optim::{
+        GenericLocalSearchOptimizer, IncrementalOptimizer, LocalSearchOptimizer,
+        SimulatedAnnealingOptimizer, TabuSearchOptimizer,
+    },
+};
+
+#[test]
+fn test_apply_transition() {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let mut rng = rand::rng();
+    let (mut solution, score) = model.generate_random_solution(&mut rng).unwrap();
+    let (transition, trial_score) = model
+        .propose_transition(&solution, score, &mut rng)
+        .unwrap();
+    model.apply_transition(&mut solution, &transition);
+    assert_abs_diff_eq!(
+        model.evaluate_solution(&solution).into_inner(),
+        trial_score.into_inner(),
+        epsilon = 1e-9
+    );
+}
+
+#[test]
+fn test_generic() {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let opt =
+        IncrementalOptimizer::new(GenericLocalSearchOptimizer::new(1000, 10, 200, |_, _| 0.0));
+    let (final_solution, final_score) = opt
+        .run(&model, None, 10000, Duration::from_secs(10))
+        .unwrap()
+        .into_solution_and_score();
+    assert_abs_diff_eq!(2.0, final_solution[0], epsilon = 0.05);
+    assert_abs_diff_eq!(0.0, final_solution[1], epsilon = 0.05);
+    assert_abs_diff_eq!(-3.5, final_solution[2], epsilon = 0.05);
+    assert_abs_diff_eq!(0.0, final_score.into_inner(), epsilon = 0.05);
+}
+
+#[test]
+fn test_tabu_search() {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let opt = IncrementalOptimizer::new(TabuSearchOptimizer::<MyTabuList>::new(1000, 25, 5, 10));
+    let (final_solution, final_score) = opt
+        .run(&model, None, 10000, Duration::from_secs(10))
+        .unwrap()
+        .into_solution_and_score();
+    assert_abs_diff_eq!(2.0, final_solution[0], epsilon = 0.1);
+    assert_abs_diff_eq!(0.0, final_solution[1], epsilon = 0.1);
+    assert_abs_diff_eq!(-3.5, final_solution[2], epsilon = 0.1);
+    assert_abs_diff_eq!(0.0, final_score.into_inner(), epsilon = 0.05);
+}
+
+#[test]
+fn test_simulated_annealing() {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let opt = IncrementalOptimizer::new(SimulatedAnnealingOptimizer::new(
+        10000,
+        10,
+        10,
+        1.0,
+        0.99,
+        NonZero::new(1).expect("update_frequency must be >= 1"),
+    ));
+    let (final_solution, final_score) = opt
+        .run(&model, None, 5000, Duration::from_secs(10))
+        .unwrap()
+        .into_solution_and_score();
+    assert_abs_diff_eq!(2.0, final_solution[0], epsilon = 0.05);
+    assert_abs_diff_eq!(0.0, final_solution[1], epsilon = 0.05);
+    assert_abs_diff_eq!(-3.5, final_solution[2], epsilon = 0.05);
+    assert_abs_diff_eq!(0.0, final_score.into_inner(), epsilon = 0.05);
+}
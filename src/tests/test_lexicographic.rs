@@ -0,0 +1,124 @@
+use std::time::Duration;
+
+use super::KnapsackModel;
+use crate::{
+    HardMediumSoftScore,
+    optim::{
+        AcceptanceCriterion, GenericLocalSearchOptimizer, HillClimbingOptimizer,
+        LexicographicAnnealing, LexicographicAnnealingOptimizer, LocalSearchOptimizer,
+        OptimizerConfig, Schedule,
+    },
+};
+
+/// Knapsack: the excess weight over the capacity is the hard level and the negated value the soft level
+fn knapsack() -> KnapsackModel<HardMediumSoftScore> {
+    KnapsackModel::new(|excess_weight, value| HardMediumSoftScore::new(excess_weight, 0, -value))
+}
+
+#[test]
+fn test_score() {
+    let score = HardMediumSoftScore::new(1, -5, -100);
+    // the hard level dominates the lower ones
+    assert!(HardMediumSoftScore::new(0, 10, 100) < score);
+    assert!(HardMediumSoftScore::new(1, -6, 100) < score);
+    assert!(HardMediumSoftScore::new(1, -5, -101) < score);
+    assert!(!score.is_feasible());
+    assert!(HardMediumSoftScore::of_soft(3).is_feasible());
+
+    assert_eq!(score.to_string(), "1hard/-5medium/-100soft");
+    assert_eq!(
+        score + HardMediumSoftScore::of_medium(2) - HardMediumSoftScore::of_hard(1),
+        HardMediumSoftScore::new(0, -3, -100)
+    );
+    assert_eq!(
+        [score, -score].into_iter().sum::<HardMediumSoftScore>(),
+        HardMediumSoftScore::default()
+    );
+
+    assert_eq!(
+        score.deciding_level(&HardMediumSoftScore::new(1, -2, -200)),
+        Some((1, 3))
+    );
+    assert_eq!(score.deciding_level(&score), None);
+}
+
+#[test]
+fn test_acceptance_probability() {
+    let acceptance = LexicographicAnnealing::new(2.0, 1.0, 0.5);
+    let current = HardMediumSoftScore::new(0, 0, -10);
+
+    // the deciding level alone sets the probability, whatever the lower levels do
+    let p = acceptance.acceptance_probability(current, HardMediumSoftScore::new(1, 0, -20));
+    assert_eq!(p, (-2.0f64).exp());
+    let p = acceptance.acceptance_probability(current, HardMediumSoftScore::new(0, 3, -20));
+    assert_eq!(p, (-3.0f64).exp());
+    let p = acceptance.acceptance_probability(current, HardMediumSoftScore::new(0, 0, -6));
+    assert_eq!(p, (-2.0f64).exp());
+    let p = acceptance.acceptance_probability(current, current);
+    assert_eq!(p, 1.0);
+}
+
+#[test]
+fn test_optimize() {
+    let model = knapsack();
+
+    let acceptance =
+        LexicographicAnnealing::new(2.0, 2.0, 0.2).with_schedule(Schedule::geometric(1.0, 50.0));
+    let opt = GenericLocalSearchOptimizer::new(5000, 10, 200, acceptance);
+    let result = opt
+        .run(&model, None, 2000, Duration::from_secs(10))
+        .unwrap();
+    assert!(result.score.is_feasible());
+    assert!(result.score.soft < 0);
+    let state = result.algorithm_state;
+    let betas = state.betas.unwrap();
+    assert_eq!(betas.len(), 3);
+    assert!(betas[2] > 0.2 * 40.0);
+    assert_eq!(state.beta, Some(betas[2]));
+
+    // Ord-only optimizers take the score as is
+    let opt = HillClimbingOptimizer::new(1000, 20);
+    let result = opt
+        .run(&model, None, 2000, Duration::from_secs(10))
+        .unwrap();
+    assert!(result.score.is_feasible());
+
+    // as are the ones built from their name
+    let opt = OptimizerConfig::from_name("hill_climbing")
+        .unwrap()
+        .build_ordinal::<KnapsackModel<HardMediumSoftScore>>()
+        .unwrap();
+    let result = opt
+        .run(&model, None, 2000, Duration::from_secs(10))
+        .unwrap();
+    assert!(result.score.is_feasible());
+}
+
+#[test]
+fn test_lexicographic_annealing_optimizer() {
+    let model = knapsack();
+    let opt = LexicographicAnnealingOptimizer::builder()
+        .with_patience(5000)
+        .with_n_trials(10)
+        .with_return_iter(200)
+        .with_hard_beta(2.0)
+        .with_medium_beta(2.0)
+        .with_soft_beta(0.2)
+        .with_final_factor(50.0)
+        .build()
+        .unwrap();
+    let result = opt
+        .run(&model, None, 2000, Duration::from_secs(10))
+        .unwrap();
+    assert!(result.score.is_feasible());
+    assert!(result.score.soft < 0);
+    // the levels cooled together up to the final factor
+    let betas = result.algorithm_state.betas.unwrap();
+    assert!(betas[2] > 0.2 * 40.0);
+    assert_eq!(betas[0], betas[1]);
+
+    let invalid = LexicographicAnnealingOptimizer::builder()
+        .with_soft_beta(-1.0)
+        .build();
+    assert!(invalid.is_err());
+}
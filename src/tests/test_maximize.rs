@@ -0,0 +1,92 @@
+use std::cmp::Reverse;
+
+use ordered_float::NotNan;
+
+use super::{QuadraticModel, ScoreType, SolutionType, TransitionType};
+use crate::{
+    Duration, Energy, LocalsearchError, Maximize, OptModel,
+    optim::{LocalSearchOptimizer, OptimizerConfig},
+};
+
+/// Revenue to maximize, `1000 - distance` of the quadratic model, negative far from the centers
+struct RevenueModel {
+    quadratic: QuadraticModel,
+}
+
+fn revenue(distance: ScoreType) -> ScoreType {
+    NotNan::new(1000.0).unwrap() - distance
+}
+
+impl OptModel for RevenueModel {
+    type SolutionType = SolutionType;
+    type TransitionType = TransitionType;
+    type ScoreType = ScoreType;
+
+    fn generate_random_solution<R: rand::Rng>(
+        &self,
+        rng: &mut R,
+    ) -> Result<(Self::SolutionType, Self::ScoreType), LocalsearchError> {
+        let (solution, distance) = self.quadratic.generate_random_solution(rng)?;
+        Ok((solution, revenue(distance)))
+    }
+
+    fn generate_trial_solution<R: rand::Rng>(
+        &self,
+        current_solution: Self::SolutionType,
+        current_score: Self::ScoreType,
+        rng: &mut R,
+    ) -> Result<(Self::SolutionType, Self::TransitionType, Self::ScoreType), LocalsearchError> {
+        let (solution, transition, distance) = self.quadratic.generate_trial_solution(
+            current_solution,
+            revenue(current_score),
+            rng,
+        )?;
+        Ok((solution, transition, revenue(distance)))
+    }
+}
+
+#[test]
+fn test_reverse_energy() {
+    let low = Reverse(NotNan::new(-2.0).unwrap());
+    let high = Reverse(NotNan::new(3.0).unwrap());
+    assert!(high < low);
+    assert_eq!(high.energy(), -3.0);
+    assert_eq!(high.energy_delta(&low), 5.0);
+    assert_eq!(Reverse(4u32).energy_delta(&Reverse(1)), 3.0);
+}
+
+#[test]
+fn test_maximize() {
+    let model = Maximize::new(RevenueModel {
+        quadratic: QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-30.0, 30.0)),
+    });
+    // far from the centers, the revenue is negative
+    let initial_solution = vec![30.0, -30.0, 30.0];
+    let initial_score = Reverse(revenue(
+        model.inner().quadratic.evaluate_solution(&initial_solution),
+    ));
+    assert!(initial_score.0.into_inner() < 0.0);
+
+    for name in OptimizerConfig::ALGORITHMS {
+        if name == "tabu_search" {
+            continue;
+        }
+        let opt = OptimizerConfig::from_name(name)
+            .unwrap()
+            .build::<Maximize<RevenueModel>>()
+            .unwrap();
+        let result = opt
+            .run(
+                &model,
+                Some((initial_solution.clone(), initial_score)),
+                3000,
+                Duration::from_secs(10),
+            )
+            .unwrap();
+        let Reverse(best) = result.score;
+        assert!(best > initial_score.0, "{name}");
+        if name != "random_search" {
+            assert!(best.into_inner() > 900.0, "{name}: {best}");
+        }
+    }
+}
@@ -0,0 +1,164 @@
+use std::num::NonZero;
+
+use rand::{SeedableRng, rngs::StdRng};
+
+use super::{
+    QuadraticModel, ScoreType, SolutionType, TransitionType, test_tabu_search::MyTabuList,
+};
+use crate::{
+    Duration, OptObserver, OptProgress, OptResult, StopReason,
+    optim::{
+        EpsilonGreedyOptimizer, LocalSearchOptimizer, ParallelTemperingOptimizer,
+        PopulationAnnealingOptimizer, SimulatedAnnealingOptimizer, TabuSearchOptimizer,
+    },
+    stop_condition::Never,
+};
+
+#[derive(Default)]
+struct Recorder {
+    n_iterations: usize,
+    improvements: Vec<ScoreType>,
+    n_returns: usize,
+    betas: Vec<(usize, f64)>,
+    exchanges: Vec<(usize, bool)>,
+    resample_weights: Vec<Vec<f64>>,
+    n_tabu_appends: usize,
+    stops: Vec<(usize, StopReason)>,
+}
+
+impl OptObserver<SolutionType, ScoreType, TransitionType> for Recorder {
+    fn on_iteration(&mut self, _progress: OptProgress<SolutionType, ScoreType>) {
+        self.n_iterations += 1;
+    }
+
+    fn on_improvement(&mut self, _iter: usize, _solution: &SolutionType, score: ScoreType) {
+        self.improvements.push(score);
+    }
+
+    fn on_return_to_best(&mut self, _iter: usize, _score: ScoreType) {
+        self.n_returns += 1;
+    }
+
+    fn on_temperature_update(&mut self, iter: usize, beta: f64) {
+        self.betas.push((iter, beta));
+    }
+
+    fn on_replica_exchange(&mut self, _iter: usize, replica: usize, accepted: bool) {
+        self.exchanges.push((replica, accepted));
+    }
+
+    fn on_population_resample(&mut self, _iter: usize, weights: &[f64]) {
+        self.resample_weights.push(weights.to_vec());
+    }
+
+    fn on_tabu_append(&mut self, _iter: usize, _transition: &TransitionType) {
+        self.n_tabu_appends += 1;
+    }
+
+    fn on_stop(&mut self, iter: usize, stop_reason: StopReason) {
+        self.stops.push((iter, stop_reason));
+    }
+}
+
+fn observe(
+    opt: &dyn LocalSearchOptimizer<QuadraticModel>,
+    n_iter: usize,
+) -> (Recorder, OptResult<SolutionType, ScoreType>) {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let mut recorder = Recorder::default();
+    let result = opt
+        .run_with_observer(
+            &model,
+            None,
+            n_iter,
+            Duration::from_secs(10),
+            &mut Never,
+            None,
+            &mut StdRng::seed_from_u64(0),
+            &mut recorder,
+        )
+        .unwrap();
+    (recorder, result)
+}
+
+#[test]
+fn test_improvement_and_stop_events() {
+    let opt = EpsilonGreedyOptimizer::new(1000, 10, 5, 0.3);
+    let (recorder, result) = observe(&opt, 100);
+
+    assert_eq!(recorder.n_iterations, 100);
+    assert_eq!(recorder.stops, vec![(100, StopReason::MaxIterations)]);
+    assert!(recorder.improvements.windows(2).all(|w| w[1] < w[0]));
+    // the model has no postprocessing, so the last improvement is the result
+    assert_eq!(recorder.improvements.last(), Some(&result.score));
+    assert!(recorder.n_returns > 0);
+}
+
+#[test]
+fn test_temperature_events() {
+    let update_frequency = NonZero::new(10).unwrap();
+    let opt = SimulatedAnnealingOptimizer::new(1000, 10, 20, 1.0, 0.5, update_frequency);
+    let (recorder, result) = observe(&opt, 50);
+    let expected: Vec<_> = [10, 20, 30, 40]
+        .into_iter()
+        .zip([0.5, 0.25, 0.125, 0.0625])
+        .collect();
+    assert_eq!(recorder.betas, expected);
+    assert_eq!(result.algorithm_state.beta, Some(0.0625));
+}
+
+#[test]
+fn test_replica_events() {
+    let update_frequency = NonZero::new(10).unwrap();
+    let opt = ParallelTemperingOptimizer::with_geometric_betas(
+        1000,
+        10,
+        20,
+        4,
+        1e-2,
+        1e2,
+        update_frequency,
+    );
+    let (recorder, _) = observe(&opt, 100);
+    // three adjacent pairs per round
+    assert_eq!(recorder.exchanges.len(), 3 * recorder.n_iterations);
+    assert!(recorder.exchanges.iter().all(|&(replica, _)| replica < 3));
+    assert_eq!(recorder.stops.len(), 1);
+
+    let opt = PopulationAnnealingOptimizer::new(1000, 10, 20, 1.0, 0.99, update_frequency, 8);
+    let (recorder, _) = observe(&opt, 100);
+    assert_eq!(recorder.resample_weights.len(), recorder.n_iterations);
+    assert_eq!(recorder.betas.len(), recorder.n_iterations);
+    assert!(recorder.resample_weights.iter().all(|w| w.len() == 8));
+}
+
+#[test]
+fn test_tabu_events() {
+    let opt = TabuSearchOptimizer::<MyTabuList>::new(1000, 10, 20, 10);
+    let (recorder, result) = observe(&opt, 100);
+    assert!(recorder.n_tabu_appends > 0);
+    assert!(recorder.n_tabu_appends <= result.n_iterations);
+    assert_eq!(
+        recorder.stops,
+        vec![(result.n_iterations, result.stop_reason)]
+    );
+}
+
+#[test]
+fn test_closure_as_observer() {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let opt = EpsilonGreedyOptimizer::new(1000, 10, 5, 0.3);
+    let mut iters = vec![];
+    opt.run_with_observer(
+        &model,
+        None,
+        20,
+        Duration::from_secs(10),
+        &mut Never,
+        None,
+        &mut rand::rng(),
+        &mut |progress: OptProgress<SolutionType, ScoreType>| iters.push(progress.iter),
+    )
+    .unwrap();
+    assert_eq!(iters, (0..20).collect::<Vec<_>>());
+}
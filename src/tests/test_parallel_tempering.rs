@@ -29,7 +29,8 @@ fn test_parallel_tempering_basic() {
             200,
             Duration::from_secs(1),
         )
-        .unwrap();
+        .unwrap()
+        .into_solution_and_score();
 
     assert!(best_score.into_inner().is_finite());
 }
@@ -3,7 +3,10 @@ use std::{num::NonZero, time::Duration};
 use approx::assert_abs_diff_eq;
 
 use super::QuadraticModel;
-use crate::optim::{LocalSearchOptimizer, PopulationAnnealingOptimizer};
+use crate::{
+    LocalsearchError,
+    optim::{LocalSearchOptimizer, PopulationAnnealingOptimizer},
+};
 
 #[test]
 fn test() {
@@ -17,13 +20,25 @@ fn test() {
         NonZero::new(100).expect("update_frequency must be >= 1"),
         32,
     )
-    .tune_initial_temperature(&model, None, 1000, 0.8)
+    .tune_initial_temperature(&model, None, 1000, 0.8, &mut rand::rng())
+    .unwrap()
     .tune_cooling_rate(5000);
     let (final_solution, final_score) = opt
         .run(&model, None, 5000, Duration::from_secs(10))
-        .unwrap();
+        .unwrap()
+        .into_solution_and_score();
     assert_abs_diff_eq!(2.0, final_solution[0], epsilon = 0.05);
     assert_abs_diff_eq!(0.0, final_solution[1], epsilon = 0.05);
     assert_abs_diff_eq!(-3.5, final_solution[2], epsilon = 0.05);
     assert_abs_diff_eq!(0.0, final_score.into_inner(), epsilon = 0.05);
 }
+
+#[test]
+fn test_invalid_weights() {
+    // a negative inverse temperature makes the weights of the worse members overflow
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let opt =
+        PopulationAnnealingOptimizer::new(10000, 10, 1000, -1e3, 1.0, NonZero::new(1).unwrap(), 8);
+    let result = opt.run(&model, None, 100, Duration::from_secs(10));
+    assert!(matches!(result, Err(LocalsearchError::ModelError(_))));
+}
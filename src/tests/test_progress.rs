@@ -0,0 +1,173 @@
+use std::{
+    num::NonZero,
+    sync::{Arc, mpsc},
+    thread,
+};
+
+use approx::assert_abs_diff_eq;
+
+use super::{QuadraticModel, ScoreType, SolutionType};
+use crate::{
+    Duration, OptMetrics, OptProgress, ProgressSnapshot, channel_callback,
+    optim::{
+        GreatDelugeOptimizer, HillClimbingOptimizer, LocalSearchOptimizer,
+        ParallelTemperingOptimizer, PopulationAnnealingOptimizer, SimulatedAnnealingOptimizer,
+    },
+};
+
+fn collect_progress(
+    opt: &dyn LocalSearchOptimizer<QuadraticModel>,
+    n_iter: usize,
+) -> Vec<OptProgress<SolutionType, ScoreType>> {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let mut progresses = vec![];
+    opt.run_with_callback(
+        &model,
+        None,
+        n_iter,
+        Duration::from_secs(10),
+        &mut |progress| progresses.push(progress),
+    )
+    .unwrap();
+    progresses
+}
+
+#[test]
+fn test_progress_statistics() {
+    let opt = HillClimbingOptimizer::new(1000, 10);
+    let progresses = collect_progress(&opt, 50);
+    assert_eq!(progresses.len(), 50);
+    let mut elapsed = Duration::ZERO;
+    for progress in progresses {
+        assert_eq!(progress.n_evaluations, (progress.iter + 1) * 10);
+        assert!(progress.score <= progress.current_score);
+        assert!(progress.elapsed >= elapsed);
+        elapsed = progress.elapsed;
+        assert_eq!(progress.metrics, OptMetrics::default());
+    }
+}
+
+#[test]
+fn test_annealing_metrics() {
+    let update_frequency = NonZero::new(10).unwrap();
+    let opt = SimulatedAnnealingOptimizer::new(1000, 10, 20, 1.0, 2.0, update_frequency);
+    for progress in collect_progress(&opt, 50) {
+        // beta used during the iteration, doubled every 10 iterations
+        let expected = 2f64.powi((progress.iter.saturating_sub(1) / 10) as i32);
+        assert_eq!(progress.metrics.beta, Some(expected));
+    }
+
+    let opt = GreatDelugeOptimizer::new(1000, 10, 20, 1.1);
+    for progress in collect_progress(&opt, 50) {
+        let water_level = progress.metrics.water_level.unwrap();
+        assert!(water_level >= progress.score.into_inner());
+    }
+}
+
+#[test]
+fn test_replica_metrics() {
+    let update_frequency = NonZero::new(10).unwrap();
+    let opt = ParallelTemperingOptimizer::with_geometric_betas(
+        1000,
+        10,
+        20,
+        4,
+        1e-2,
+        1e2,
+        update_frequency,
+    );
+    for progress in collect_progress(&opt, 100) {
+        assert_eq!(progress.metrics.betas.unwrap().len(), 4);
+        let replica_scores = progress.metrics.replica_scores.unwrap();
+        assert_eq!(replica_scores.len(), 4);
+        assert!(
+            replica_scores
+                .iter()
+                .all(|&score| score >= progress.current_score.into_inner())
+        );
+        let swap_rates = progress.metrics.swap_rates.unwrap();
+        assert_eq!(swap_rates.len(), 3);
+        assert!(swap_rates.iter().all(|rate| (0.0..=1.0).contains(rate)));
+        assert_eq!(progress.n_evaluations, progress.iter * 4 * 10);
+    }
+
+    let opt = PopulationAnnealingOptimizer::new(1000, 10, 20, 1.0, 0.99, update_frequency, 8);
+    for progress in collect_progress(&opt, 100) {
+        assert!(progress.metrics.beta.is_some());
+        let weights = progress.metrics.population_weights.unwrap();
+        assert_eq!(weights.len(), 8);
+        assert_abs_diff_eq!(weights.iter().sum::<f64>(), 1.0, epsilon = 1e-9);
+    }
+}
+
+#[test]
+fn test_channel_callback() {
+    fn assert_send<T: Send>() {}
+    assert_send::<ProgressSnapshot<SolutionType, ScoreType>>();
+
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let update_frequency = NonZero::new(10).unwrap();
+    let optimizers: Vec<Box<dyn LocalSearchOptimizer<QuadraticModel>>> = vec![
+        Box::new(HillClimbingOptimizer::new(1000, 10)),
+        Box::new(ParallelTemperingOptimizer::with_geometric_betas(
+            1000,
+            10,
+            20,
+            4,
+            1e-2,
+            1e2,
+            update_frequency,
+        )),
+        Box::new(PopulationAnnealingOptimizer::new(
+            1000,
+            10,
+            20,
+            1.0,
+            0.99,
+            update_frequency,
+            8,
+        )),
+    ];
+    for opt in optimizers {
+        let (sender, receiver) = mpsc::channel();
+        let ui = thread::spawn(move || receiver.into_iter().collect::<Vec<_>>());
+        let mut callback = channel_callback(sender, Duration::ZERO);
+        let result = opt
+            .run_with_callback(&model, None, 100, Duration::from_secs(10), &mut callback)
+            .unwrap();
+        drop(callback);
+        let snapshots = ui.join().unwrap();
+
+        assert!(!snapshots.is_empty());
+        let last = snapshots.last().unwrap();
+        assert_eq!(last.score, result.score);
+        assert_eq!(*last.solution, result.solution);
+        // an unchanged best solution is shared instead of cloned
+        for pair in snapshots.windows(2) {
+            if pair[0].score == pair[1].score {
+                assert!(Arc::ptr_eq(&pair[0].solution, &pair[1].solution));
+            }
+        }
+    }
+}
+
+#[test]
+fn test_channel_callback_throttling() {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let opt = HillClimbingOptimizer::new(1000, 10);
+
+    let (sender, receiver) = mpsc::channel();
+    let mut callback = channel_callback(sender, Duration::from_secs(3600));
+    opt.run_with_callback(&model, None, 100, Duration::from_secs(10), &mut callback)
+        .unwrap();
+    assert_eq!(receiver.try_iter().count(), 1);
+
+    // a full bounded channel drops snapshots instead of blocking the optimizer
+    let (sender, receiver) = mpsc::sync_channel(1);
+    let mut callback = channel_callback(sender, Duration::ZERO);
+    let result = opt
+        .run_with_callback(&model, None, 100, Duration::from_secs(10), &mut callback)
+        .unwrap();
+    assert_eq!(result.n_iterations, 100);
+    assert_eq!(receiver.try_iter().count(), 1);
+}
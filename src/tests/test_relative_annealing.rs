@@ -12,7 +12,8 @@ fn test() {
     let opt = RelativeAnnealingOptimizer::new(5000, 10, 200, 1e1);
     let (final_solution, final_score) = opt
         .run(&model, None, 10000, Duration::from_secs(10))
-        .unwrap();
+        .unwrap()
+        .into_solution_and_score();
     assert_abs_diff_eq!(2.0, final_solution[0], epsilon = 0.05);
     assert_abs_diff_eq!(0.0, final_solution[1], epsilon = 0.05);
     assert_abs_diff_eq!(-3.5, final_solution[2], epsilon = 0.05);
@@ -35,7 +36,8 @@ fn test_initial_score_zero() {
             1000,
             Duration::from_secs(10),
         )
-        .unwrap();
+        .unwrap()
+        .into_solution_and_score();
     assert!(final_score.into_inner().is_finite());
     assert_abs_diff_eq!(0.0, final_solution[0] - 2.0, epsilon = 0.05);
     assert_abs_diff_eq!(0.0, final_solution[1] - 0.0, epsilon = 0.05);
@@ -0,0 +1,116 @@
+use std::num::NonZero;
+
+use rand::{SeedableRng, rngs::StdRng};
+
+use super::{QuadraticModel, ScoreType, SolutionType};
+use crate::{
+    Duration,
+    optim::{
+        AdaptiveAnnealingOptimizer, EpsilonGreedyOptimizer, GreatDelugeOptimizer,
+        HillClimbingOptimizer, LocalSearchOptimizer, LogisticAnnealingOptimizer,
+        MetropolisOptimizer, ParallelTemperingOptimizer, PopulationAnnealingOptimizer,
+        RandomSearchOptimizer, RelativeAnnealingOptimizer, SimulatedAnnealingOptimizer,
+        TsallisRelativeAnnealingOptimizer,
+    },
+};
+
+pub(super) fn optimizers() -> Vec<Box<dyn LocalSearchOptimizer<QuadraticModel> + Sync>> {
+    optimizers_with_patience(100)
+}
+
+/// One optimizer of each algorithm except tabu search, stopping after `patience` iterations without improvement
+pub(super) fn optimizers_with_patience(
+    patience: usize,
+) -> Vec<Box<dyn LocalSearchOptimizer<QuadraticModel> + Sync>> {
+    let update_frequency = NonZero::new(10).expect("update_frequency must be >= 1");
+    vec![
+        Box::new(RandomSearchOptimizer::new(patience)),
+        Box::new(HillClimbingOptimizer::new(patience, 10)),
+        Box::new(EpsilonGreedyOptimizer::new(patience, 10, 20, 0.1)),
+        Box::new(MetropolisOptimizer::new(patience, 10, 20, 1.0)),
+        Box::new(SimulatedAnnealingOptimizer::new(
+            patience,
+            10,
+            20,
+            1.0,
+            0.99,
+            update_frequency,
+        )),
+        Box::new(AdaptiveAnnealingOptimizer::new(
+            patience,
+            10,
+            20,
+            1.0,
+            Default::default(),
+            update_frequency,
+        )),
+        Box::new(GreatDelugeOptimizer::new(patience, 10, 20, 1.1)),
+        Box::new(LogisticAnnealingOptimizer::new(patience, 10, 20, 1e1)),
+        Box::new(RelativeAnnealingOptimizer::new(patience, 10, 20, 1e1)),
+        Box::new(TsallisRelativeAnnealingOptimizer::new(
+            patience,
+            10,
+            20,
+            1e1,
+            update_frequency,
+            1.5,
+            1.0,
+        )),
+        Box::new(ParallelTemperingOptimizer::with_geometric_betas(
+            patience,
+            10,
+            20,
+            4,
+            1e-2,
+            1e2,
+            update_frequency,
+        )),
+        Box::new(PopulationAnnealingOptimizer::new(
+            patience,
+            10,
+            20,
+            1.0,
+            0.99,
+            update_frequency,
+            8,
+        )),
+    ]
+}
+
+fn run_seeded(
+    opt: &dyn LocalSearchOptimizer<QuadraticModel>,
+    seed: u64,
+) -> (SolutionType, ScoreType) {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let mut rng = StdRng::seed_from_u64(seed);
+    opt.run_with_rng(
+        &model,
+        None,
+        500,
+        Duration::from_secs(60),
+        &mut rng,
+        &mut |_| {},
+    )
+    .unwrap()
+    .into_solution_and_score()
+}
+
+#[test]
+fn test_same_seed_same_result() {
+    for opt in optimizers() {
+        assert_eq!(run_seeded(&opt, 42), run_seeded(&opt, 42));
+    }
+}
+
+#[test]
+fn test_result_independent_of_thread_count() {
+    let single_thread = rayon::ThreadPoolBuilder::new()
+        .num_threads(1)
+        .build()
+        .unwrap();
+    for opt in optimizers() {
+        let expected = run_seeded(&opt, 7);
+        let actual = single_thread.install(|| run_seeded(&opt, 7));
+        assert_eq!(expected, actual);
+    }
+}
@@ -0,0 +1,168 @@
+use std::num::NonZero;
+
+use ordered_float::NotNan;
+use rand::{SeedableRng, rngs::StdRng};
+
+use super::{QuadraticModel, test_tabu_search::MyTabuList};
+use crate::{
+    Duration, OptResult, StopReason,
+    optim::{
+        GreatDelugeOptimizer, HillClimbingOptimizer, LocalSearchOptimizer,
+        ParallelTemperingOptimizer, SimulatedAnnealingOptimizer, TabuSearchOptimizer,
+    },
+    stop_condition::{Never, TargetScore},
+};
+
+fn run<O: LocalSearchOptimizer<QuadraticModel>>(
+    opt: &O,
+    n_iter: usize,
+    time_limit: Duration,
+) -> OptResult<Vec<f64>, NotNan<f64>> {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    opt.run_with_rng(
+        &model,
+        None,
+        n_iter,
+        time_limit,
+        &mut StdRng::seed_from_u64(0),
+        &mut |_| {},
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_max_iterations() {
+    let result = run(
+        &HillClimbingOptimizer::new(usize::MAX, 10),
+        100,
+        Duration::from_secs(60),
+    );
+    assert_eq!(result.stop_reason, StopReason::MaxIterations);
+    assert_eq!(result.n_iterations, 100);
+    assert_eq!(result.n_evaluations, 1000);
+    assert!(result.algorithm_state.beta.is_none());
+
+    let result = run(
+        &TabuSearchOptimizer::<MyTabuList>::new(usize::MAX, 10, usize::MAX, 10),
+        100,
+        Duration::from_secs(60),
+    );
+    assert_eq!(result.stop_reason, StopReason::MaxIterations);
+    assert_eq!(result.n_iterations, 100);
+    assert_eq!(result.n_evaluations, 1000);
+
+    let result = run(
+        &ParallelTemperingOptimizer::with_geometric_betas(
+            usize::MAX,
+            10,
+            usize::MAX,
+            4,
+            1e-2,
+            1e2,
+            NonZero::new(10).unwrap(),
+        ),
+        100,
+        Duration::from_secs(60),
+    );
+    assert_eq!(result.stop_reason, StopReason::MaxIterations);
+    assert_eq!(result.n_iterations, 100);
+    assert_eq!(result.n_evaluations, 4 * 1000);
+}
+
+#[test]
+fn test_time_limit() {
+    let result = run(
+        &HillClimbingOptimizer::new(usize::MAX, 10),
+        usize::MAX,
+        Duration::from_millis(50),
+    );
+    assert_eq!(result.stop_reason, StopReason::TimeLimit);
+    assert!(result.elapsed >= Duration::from_millis(50));
+}
+
+#[test]
+fn test_patience() {
+    let result = run(
+        &HillClimbingOptimizer::new(10, 10),
+        usize::MAX,
+        Duration::from_secs(60),
+    );
+    assert_eq!(result.stop_reason, StopReason::Patience);
+}
+
+#[test]
+fn test_stop_condition() {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let target = NotNan::new(1.0).unwrap();
+    let result = HillClimbingOptimizer::new(usize::MAX, 10)
+        .run_with_stop_condition(
+            &model,
+            None,
+            usize::MAX,
+            Duration::from_secs(60),
+            &mut TargetScore::new(target),
+            &mut StdRng::seed_from_u64(0),
+            &mut |_| {},
+        )
+        .unwrap();
+    assert_eq!(result.stop_reason, StopReason::StopCondition);
+    assert!(result.score <= target);
+}
+
+#[test]
+fn test_algorithm_state() {
+    let opt = SimulatedAnnealingOptimizer::new(
+        usize::MAX,
+        10,
+        usize::MAX,
+        1.0,
+        2.0,
+        NonZero::new(10).unwrap(),
+    );
+    let result = run(&opt, 100, Duration::from_secs(60));
+    // beta is updated at iterations 10, 20, ..., 90
+    let beta = result.algorithm_state.beta.unwrap();
+    assert!((beta - 2f64.powi(9)).abs() < 1e-9);
+
+    let result = run(
+        &GreatDelugeOptimizer::new(usize::MAX, 10, usize::MAX, 1.1),
+        100,
+        Duration::from_secs(60),
+    );
+    assert!(result.algorithm_state.water_level.is_some());
+}
+
+#[test]
+fn test_optimize_matches_optimize_with_result() {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let opt = HillClimbingOptimizer::new(usize::MAX, 10);
+    let initial_solution = vec![0.0; 3];
+    let initial_score = model.evaluate_solution(&initial_solution);
+    let (solution, score) = opt
+        .optimize(
+            &model,
+            initial_solution.clone(),
+            initial_score,
+            100,
+            Duration::from_secs(60),
+            &mut Never,
+            None,
+            &mut StdRng::seed_from_u64(0),
+            &mut |_| {},
+        )
+        .unwrap();
+    let result = opt
+        .optimize_with_result(
+            &model,
+            initial_solution,
+            initial_score,
+            100,
+            Duration::from_secs(60),
+            &mut Never,
+            None,
+            &mut StdRng::seed_from_u64(0),
+            &mut |_| {},
+        )
+        .unwrap();
+    assert_eq!((solution, score), result.into_solution_and_score());
+}
@@ -0,0 +1,115 @@
+use std::num::NonZero;
+
+use approx::assert_abs_diff_eq;
+use ordered_float::NotNan;
+use rand::{SeedableRng, rngs::StdRng};
+
+use super::{SchafferModel, SchafferScore};
+use crate::{
+    Duration, ObjectiveVector,
+    optim::{
+        HillClimbingOptimizer, Scalarization, ScalarizationSweep, SimulatedAnnealingOptimizer,
+        SweepResult,
+    },
+};
+
+fn assert_non_dominated(result: &SweepResult<f64, SchafferScore>) {
+    let front = &result.front;
+    assert!(!front.is_empty());
+    for (i, (_, a)) in front.iter().enumerate() {
+        for (_, b) in &front[i + 1..] {
+            assert!(!a.dominates(b) && !b.dominates(a));
+        }
+    }
+}
+
+#[test]
+fn test_scalarization() {
+    let objectives = [NotNan::new(1.0).unwrap(), NotNan::new(4.0).unwrap()];
+    assert_eq!(
+        Scalarization::WeightedSum(vec![0.25, 0.75]).value(&objectives),
+        3.25
+    );
+    let chebyshev = Scalarization::Chebyshev {
+        weights: vec![0.5, 0.5],
+        reference: vec![0.0, 1.0],
+    };
+    assert_eq!(chebyshev.value(&objectives), 1.5);
+    let epsilon = Scalarization::EpsilonConstraint {
+        objective: 0,
+        bounds: vec![0.0, 3.0],
+        penalty: 10.0,
+    };
+    assert_eq!(epsilon.value(&objectives), 11.0);
+    let score = epsilon.score(objectives);
+    assert_eq!(score.value(), 11.0);
+    assert_eq!(score.objectives(), objectives);
+
+    let grid = ScalarizationSweep::weight_grid(2, 2);
+    assert_eq!(grid, vec![vec![1.0, 0.0], vec![0.5, 0.5], vec![0.0, 1.0]]);
+    let grid = ScalarizationSweep::weight_grid(3, 4);
+    assert_eq!(grid.len(), 15);
+    for weights in grid {
+        assert_abs_diff_eq!(weights.iter().sum::<f64>(), 1.0);
+    }
+}
+
+#[test]
+fn test_weighted_sum_sweep() {
+    let model = SchafferModel;
+    let opt = HillClimbingOptimizer::new(200, 16);
+    let sweep = ScalarizationSweep::weighted_sum(2, 10);
+    let mut rng = StdRng::seed_from_u64(0);
+    let result = sweep
+        .run_with_rng(&model, &opt, 1000, Duration::from_secs(10), &mut rng)
+        .unwrap();
+    assert_eq!(result.runs.len(), 11);
+    // the optimum of w1 x^2 + w2 (x - 2)^2 is 2 w2
+    for (run, scalarization) in result.runs.iter().zip(sweep.subproblems()) {
+        let Scalarization::WeightedSum(weights) = scalarization else {
+            unreachable!()
+        };
+        assert_abs_diff_eq!(run.solution, 2.0 * weights[1], epsilon = 0.05);
+    }
+    assert_non_dominated(&result);
+    assert_eq!(result.front.len(), 11);
+
+    // seeded sweeps are reproducible whatever the thread scheduling
+    let mut rng = StdRng::seed_from_u64(0);
+    let again = sweep
+        .run_with_rng(&model, &opt, 1000, Duration::from_secs(10), &mut rng)
+        .unwrap();
+    assert_eq!(again.front, result.front);
+}
+
+#[test]
+fn test_epsilon_constraint_and_chebyshev_sweeps() {
+    let model = SchafferModel;
+    let opt = SimulatedAnnealingOptimizer::new(1000, 16, 100, 1.0, 0.99, NonZero::new(10).unwrap())
+        .tune_cooling_rate(1000);
+    let mut rng = StdRng::seed_from_u64(0);
+
+    // minimize x^2 subject to (x - 2)^2 <= eps, whose optimum is 2 - sqrt(eps)
+    let bounds = [0.25, 1.0, 2.25]
+        .into_iter()
+        .map(|eps| vec![f64::INFINITY, eps])
+        .collect();
+    let sweep = ScalarizationSweep::epsilon_constraint(0, bounds, 100.0);
+    let result = sweep
+        .run_with_rng(&model, &opt, 1000, Duration::from_secs(10), &mut rng)
+        .unwrap();
+    for (run, x) in result.runs.iter().zip([1.5, 1.0, 0.5]) {
+        assert_abs_diff_eq!(run.solution, x, epsilon = 0.05);
+    }
+    assert_non_dominated(&result);
+
+    let sweep = ScalarizationSweep::chebyshev(vec![0.0, 0.0], 8);
+    let result = sweep
+        .run_with_rng(&model, &opt, 1000, Duration::from_secs(10), &mut rng)
+        .unwrap();
+    assert_eq!(result.runs.len(), 9);
+    assert_non_dominated(&result);
+    for (x, _) in &result.front {
+        assert!((-0.05..=2.05).contains(x), "{x}");
+    }
+}
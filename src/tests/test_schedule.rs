@@ -0,0 +1,194 @@
+use std::num::NonZero;
+
+use approx::assert_abs_diff_eq;
+
+use super::{QuadraticModel, ScoreType, SolutionType, TransitionType};
+use crate::{
+    Duration, OptObserver, OptResult, StopReason,
+    optim::{
+        AcceptanceState, EpsilonGreedyOptimizer, GreatDelugeOptimizer, LocalSearchOptimizer,
+        LogisticAnnealingOptimizer, MetropolisOptimizer, PopulationAnnealingOptimizer,
+        RelativeAnnealingOptimizer, Schedule, ScheduleClock, SimulatedAnnealingOptimizer,
+    },
+    stop_condition::Never,
+};
+
+#[test]
+fn test_curves() {
+    let linear = Schedule::linear(1.0, 3.0);
+    assert_abs_diff_eq!(linear.value(0.5), 2.0);
+
+    let geometric = Schedule::geometric(1.0, 100.0);
+    assert_abs_diff_eq!(geometric.value(0.5), 10.0, epsilon = 1e-9);
+
+    let logarithmic = Schedule::logarithmic(0.0, 1.0, 100.0);
+    assert!(logarithmic.value(0.1) > 0.5);
+
+    let lundy_mees = Schedule::lundy_mees(10.0, 0.1);
+    assert_abs_diff_eq!(1.0 / lundy_mees.value(0.5), 5.05, epsilon = 1e-9);
+
+    let cosine = Schedule::cosine(2.0, 0.0);
+    assert_abs_diff_eq!(cosine.value(0.5), 1.0, epsilon = 1e-9);
+
+    for schedule in [linear, geometric, logarithmic, lundy_mees, cosine] {
+        let start = schedule.value(0.0);
+        let end = schedule.value(1.0);
+        // progress is clamped to [0, 1]
+        assert_eq!(schedule.value(-1.0), start);
+        assert_eq!(schedule.value(2.0), end);
+        let values: Vec<_> = (0..=10).map(|i| schedule.value(i as f64 / 10.0)).collect();
+        assert!(
+            values
+                .windows(2)
+                .all(|w| (w[1] - w[0]) * (end - start) >= 0.0)
+        );
+    }
+
+    let piecewise = Schedule::piecewise(vec![(0.8, 0.0), (0.2, 1.0), (0.5, 0.5)]);
+    assert_eq!(piecewise.value(0.1), 1.0);
+    assert_abs_diff_eq!(piecewise.value(0.35), 0.75, epsilon = 1e-9);
+    assert_abs_diff_eq!(piecewise.value(0.65), 0.25, epsilon = 1e-9);
+    assert_eq!(piecewise.value(0.9), 0.0);
+
+    let custom = Schedule::from_fn(|t| 1.0 - t * t);
+    assert_abs_diff_eq!(custom.value(0.5), 0.75);
+}
+
+#[test]
+fn test_clock() {
+    let state = AcceptanceState {
+        iter: 25,
+        n_iter: 100,
+        elapsed_fraction: 0.5,
+        acceptance_ratio: 0.0,
+        stagnation: 0,
+        best_score: 0.0,
+        current_score: 0.0,
+    };
+    let schedule = Schedule::linear(0.0, 1.0);
+    assert_eq!(schedule.clock(), ScheduleClock::Iterations);
+    assert_eq!(schedule.value_at(&state), 0.25);
+    let schedule = schedule.with_clock(ScheduleClock::ElapsedTime);
+    assert_eq!(schedule.value_at(&state), 0.5);
+}
+
+#[derive(Default)]
+struct Temperatures(Vec<f64>);
+
+impl OptObserver<SolutionType, ScoreType, TransitionType> for Temperatures {
+    fn on_temperature_update(&mut self, _iter: usize, beta: f64) {
+        self.0.push(beta);
+    }
+}
+
+fn run(
+    opt: &dyn LocalSearchOptimizer<QuadraticModel>,
+    n_iter: usize,
+    time_limit: Duration,
+    observer: &mut Temperatures,
+) -> OptResult<SolutionType, ScoreType> {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    opt.run_with_observer(
+        &model,
+        None,
+        n_iter,
+        time_limit,
+        &mut Never,
+        None,
+        &mut rand::rng(),
+        observer,
+    )
+    .unwrap()
+}
+
+#[test]
+fn test_scheduled_parameters() {
+    let time_limit = Duration::from_secs(10);
+
+    let opt = EpsilonGreedyOptimizer::new(1000, 10, 20, 0.5)
+        .with_epsilon_schedule(Schedule::linear(0.5, 0.0));
+    let result = run(&opt, 50, time_limit, &mut Temperatures::default());
+    // the last update is done at the end of iteration 49
+    assert_abs_diff_eq!(
+        result.algorithm_state.epsilon.unwrap(),
+        0.01,
+        epsilon = 1e-9
+    );
+
+    let opt = LogisticAnnealingOptimizer::new(1000, 10, 20, 1.0)
+        .with_w_schedule(Schedule::linear(1.0, 2.0));
+    let result = run(&opt, 50, time_limit, &mut Temperatures::default());
+    assert_abs_diff_eq!(result.algorithm_state.w.unwrap(), 1.98, epsilon = 1e-9);
+
+    // annealed relative acceptance
+    let opt = RelativeAnnealingOptimizer::new(1000, 10, 20, 1.0)
+        .with_beta_schedule(Schedule::geometric(1.0, 1e3));
+    let mut temperatures = Temperatures::default();
+    run(&opt, 50, time_limit, &mut temperatures);
+    assert_eq!(temperatures.0.len(), 49);
+    assert!(temperatures.0.windows(2).all(|w| w[0] < w[1]));
+
+    let opt = MetropolisOptimizer::new(1000, 10, 20, 1.0)
+        .with_beta_schedule(Schedule::piecewise(vec![(0.0, 1.0), (0.5, 10.0)]));
+    let mut temperatures = Temperatures::default();
+    let result = run(&opt, 50, time_limit, &mut temperatures);
+    assert_eq!(temperatures.0.len(), 25);
+    assert_eq!(result.algorithm_state.beta, Some(10.0));
+
+    // a constant schedule is the fixed parameter of the constructor
+    let opt = MetropolisOptimizer::new(1000, 10, 20, 2.0);
+    let mut temperatures = Temperatures::default();
+    let result = run(&opt, 50, time_limit, &mut temperatures);
+    assert!(temperatures.0.is_empty());
+    assert_eq!(result.algorithm_state.beta, Some(2.0));
+
+    // keep the water level at its initial value
+    let opt =
+        GreatDelugeOptimizer::new(1000, 10, 20, 1.1).with_level_schedule(Schedule::constant(0.0));
+    let result = run(&opt, 50, time_limit, &mut Temperatures::default());
+    assert_eq!(
+        result.algorithm_state.water_level,
+        result.algorithm_state.initial_water_level
+    );
+}
+
+#[test]
+fn test_elapsed_time_clock() {
+    let schedule = Schedule::linear(0.0, 10.0).with_clock(ScheduleClock::ElapsedTime);
+    let opt =
+        MetropolisOptimizer::new(usize::MAX, 10, usize::MAX, 0.0).with_beta_schedule(schedule);
+    let result = run(
+        &opt,
+        usize::MAX,
+        Duration::from_millis(100),
+        &mut Temperatures::default(),
+    );
+    // the schedule advances with the time limit even though n_iter is never reached
+    assert!(result.algorithm_state.beta.unwrap() > 5.0);
+}
+
+#[test]
+fn test_time_driven_annealing() {
+    let time_limit = Duration::from_millis(200);
+    let update_frequency = NonZero::new(10).unwrap();
+
+    // n_iter is never reached, annealing completes at the time limit instead
+    let opt = SimulatedAnnealingOptimizer::new(usize::MAX, 10, 20, 1.0, 0.99, update_frequency)
+        .tune_cooling_schedule(ScheduleClock::ElapsedTime);
+    let result = run(&opt, usize::MAX, time_limit, &mut Temperatures::default());
+    assert_eq!(result.stop_reason, StopReason::TimeLimit);
+    assert!(result.algorithm_state.beta.unwrap() > 10.0);
+
+    let opt = PopulationAnnealingOptimizer::new(usize::MAX, 10, 20, 1.0, 0.99, update_frequency, 4)
+        .tune_cooling_schedule(ScheduleClock::ElapsedTime);
+    let result = run(&opt, usize::MAX, time_limit, &mut Temperatures::default());
+    assert!(result.algorithm_state.beta.unwrap() > 10.0);
+
+    let opt =
+        GreatDelugeOptimizer::new(usize::MAX, 10, 20, 2.0).with_clock(ScheduleClock::ElapsedTime);
+    let result = run(&opt, usize::MAX, time_limit, &mut Temperatures::default());
+    let initial_level = result.algorithm_state.initial_water_level.unwrap();
+    let level = result.algorithm_state.water_level.unwrap();
+    let best = result.score.into_inner();
+    assert!(level - best < 0.5 * (initial_level - best));
+}
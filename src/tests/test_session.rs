@@ -0,0 +1,159 @@
+use std::num::NonZero;
+
+use ordered_float::NotNan;
+use rand::{SeedableRng, rngs::StdRng};
+
+use super::{QuadraticModel, SchafferModel, test_tabu_search::MyTabuList};
+use crate::{
+    Duration, StopReason,
+    optim::{AmosaOptimizer, HillClimbingOptimizer, LocalSearchOptimizer, OptimizerConfig},
+};
+
+const N_ITER: usize = 60;
+
+fn model() -> QuadraticModel {
+    QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0))
+}
+
+#[test]
+fn test_session_matches_optimize() {
+    let model = model();
+    for name in OptimizerConfig::ALGORITHMS {
+        let opt = OptimizerConfig::from_name(name)
+            .unwrap()
+            .build_with_tabu_list::<QuadraticModel, MyTabuList>()
+            .unwrap();
+        let expected = opt
+            .run_with_rng(
+                &model,
+                None,
+                N_ITER,
+                Duration::from_secs(60),
+                &mut StdRng::seed_from_u64(0),
+                &mut |_| {},
+            )
+            .unwrap();
+
+        let mut session = opt
+            .start_with_rng(
+                &model,
+                None,
+                N_ITER,
+                Duration::from_secs(60),
+                Box::new(StdRng::seed_from_u64(0)),
+            )
+            .unwrap();
+        while session.step(7).unwrap().is_none() {
+            assert!(!session.is_finished(), "{name}");
+        }
+        let result = session.finish().unwrap();
+        assert_eq!(result.solution, expected.solution, "{name}");
+        assert_eq!(result.score, expected.score, "{name}");
+        assert_eq!(result.n_iterations, expected.n_iterations, "{name}");
+        assert_eq!(result.n_evaluations, expected.n_evaluations, "{name}");
+        assert_eq!(result.stop_reason, expected.stop_reason, "{name}");
+    }
+}
+
+#[test]
+fn test_session_iterator() {
+    let model = model();
+    let opt = HillClimbingOptimizer::new(1000, 10);
+    let mut session = opt
+        .start(&model, None, N_ITER, Duration::from_secs(60))
+        .unwrap();
+    let iters = session
+        .by_ref()
+        .map(|progress| progress.unwrap().iter)
+        .collect::<Vec<_>>();
+    assert_eq!(iters.len(), N_ITER);
+    assert!(iters.windows(2).all(|w| w[0] < w[1]));
+    assert_eq!(session.stop_reason(), Some(StopReason::MaxIterations));
+    assert!(session.next().is_none());
+}
+
+#[test]
+fn test_session_state() {
+    let model = model();
+    let opt = HillClimbingOptimizer::new(1000, 10);
+    let mut session = opt
+        .start(&model, None, N_ITER, Duration::from_secs(60))
+        .unwrap();
+    assert_eq!(session.step(10).unwrap(), None);
+    let checkpoint = session.checkpoint();
+    assert_eq!(checkpoint.iter, 10);
+    assert_eq!(checkpoint.best_score, session.best_score());
+
+    // the injected optimum is taken at the next iteration
+    session.inject(vec![2.0, 0.0, -3.5], NotNan::new(0.0).unwrap());
+    session.step(1).unwrap();
+    assert_eq!(session.best_score(), NotNan::new(0.0).unwrap());
+
+    // restoring goes back to the snapshot, even once the optimization stopped
+    assert_eq!(
+        session.step(usize::MAX).unwrap(),
+        Some(StopReason::MaxIterations)
+    );
+    session.restore(checkpoint.clone());
+    assert!(!session.is_finished());
+    assert_eq!(session.n_iterations(), 10);
+    assert_eq!(session.best_score(), checkpoint.best_score);
+
+    // a session ended while running is cancelled
+    session.step(5).unwrap();
+    let result = session.into_result();
+    assert_eq!(result.stop_reason, StopReason::Cancelled);
+    assert_eq!(result.n_iterations, 15);
+}
+
+#[test]
+fn test_amosa_session() {
+    let model = SchafferModel;
+    let opt = AmosaOptimizer::new(20, 40, 1.0, 1.0, NonZero::new(10).unwrap())
+        .with_n_initial(10)
+        .tune_cooling_rate(500);
+    let expected = opt
+        .run_with_rng(
+            &model,
+            None,
+            500,
+            Duration::from_secs(60),
+            &mut StdRng::seed_from_u64(0),
+            &mut |_| {},
+        )
+        .unwrap();
+
+    let mut session = opt
+        .start_with_rng(
+            &model,
+            None,
+            500,
+            Duration::from_secs(60),
+            Box::new(StdRng::seed_from_u64(0)),
+        )
+        .unwrap();
+    while session.step(30).unwrap().is_none() {
+        assert!(session.archive().len() <= 40);
+    }
+    assert_eq!(session.n_iterations(), 500);
+    let result = session.finish().unwrap();
+    assert_eq!(result.front, expected.front);
+    assert_eq!(result.n_evaluations, expected.n_evaluations);
+    assert_eq!(result.stop_reason, StopReason::MaxIterations);
+
+    // an injected solution becomes the current one and enters the archive
+    let mut session = opt
+        .start_with_rng(
+            &model,
+            None,
+            500,
+            Duration::from_secs(60),
+            Box::new(StdRng::seed_from_u64(0)),
+        )
+        .unwrap();
+    let score = [NotNan::new(0.0).unwrap(), NotNan::new(4.0).unwrap()];
+    session.inject(0.0, score);
+    assert_eq!(session.current(), (&0.0, score));
+    assert!(session.archive().members().iter().any(|(x, _)| *x == 0.0));
+    assert_eq!(session.into_result().stop_reason, StopReason::Cancelled);
+}
@@ -2,8 +2,12 @@ use std::{num::NonZero, time::Duration};
 
 use approx::assert_abs_diff_eq;
 
-use super::QuadraticModel;
-use crate::optim::{LocalSearchOptimizer, SimulatedAnnealingOptimizer};
+use super::{QuadraticModel, ScoreType, SolutionType, TransitionType};
+use crate::{
+    OptObserver, OptProgress,
+    optim::{LocalSearchOptimizer, ReheatPolicy, SimulatedAnnealingOptimizer},
+    stop_condition::Never,
+};
 
 #[test]
 fn test() {
@@ -18,9 +22,83 @@ fn test() {
     );
     let (final_solution, final_score) = opt
         .run(&model, None, 5000, Duration::from_secs(10))
-        .unwrap();
+        .unwrap()
+        .into_solution_and_score();
     assert_abs_diff_eq!(2.0, final_solution[0], epsilon = 0.05);
     assert_abs_diff_eq!(0.0, final_solution[1], epsilon = 0.05);
     assert_abs_diff_eq!(-3.5, final_solution[2], epsilon = 0.05);
     assert_abs_diff_eq!(0.0, final_score.into_inner(), epsilon = 0.05);
 }
+
+#[derive(Default)]
+struct Reheats {
+    reheats: Vec<(usize, f64)>,
+    n_reheats: Option<usize>,
+}
+
+impl OptObserver<SolutionType, ScoreType, TransitionType> for Reheats {
+    fn on_iteration(&mut self, progress: OptProgress<SolutionType, ScoreType>) {
+        self.n_reheats = progress.metrics.n_reheats;
+    }
+
+    fn on_reheat(&mut self, iter: usize, beta: f64) {
+        self.reheats.push((iter, beta));
+    }
+}
+
+fn run_with_reheat(policy: ReheatPolicy, update_frequency: usize) -> (Reheats, f64) {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let update_frequency = NonZero::new(update_frequency).unwrap();
+    let opt = SimulatedAnnealingOptimizer::new(1000, 10, 100, 1.0, 2.0, update_frequency)
+        .with_reheat(policy);
+    let mut reheats = Reheats::default();
+    let result = opt
+        .run_with_observer(
+            &model,
+            None,
+            50,
+            Duration::from_secs(10),
+            &mut Never,
+            None,
+            &mut rand::rng(),
+            &mut reheats,
+        )
+        .unwrap();
+    assert_eq!(
+        result.algorithm_state.n_reheats,
+        Some(reheats.reheats.len())
+    );
+    assert_eq!(reheats.n_reheats, Some(reheats.reheats.len()));
+    (reheats, result.algorithm_state.beta.unwrap())
+}
+
+#[test]
+fn test_reheat() {
+    let period = NonZero::new(10).unwrap();
+    let (reheats, beta) = run_with_reheat(ReheatPolicy::Cyclic { period }, 1);
+    let expected: Vec<_> = [9, 19, 29, 39, 49]
+        .into_iter()
+        .map(|it| (it, 1.0))
+        .collect();
+    assert_eq!(reheats.reheats, expected);
+    assert_eq!(beta, 1.0);
+
+    // the acceptance ratio is always below the threshold, so every temperature update is undone
+    let policy = ReheatPolicy::LowAcceptance {
+        threshold: 1.1,
+        fraction: 0.5,
+    };
+    let (reheats, beta) = run_with_reheat(policy, 5);
+    let expected: Vec<_> = (1..10).map(|i| (5 * i, 0.5)).collect();
+    assert_eq!(reheats.reheats, expected);
+    assert_eq!(beta, 0.5);
+
+    let patience = NonZero::new(1).unwrap();
+    let policy = ReheatPolicy::Stagnation {
+        patience,
+        fraction: 0.5,
+    };
+    let (reheats, _) = run_with_reheat(policy, 1);
+    assert!(!reheats.reheats.is_empty());
+    assert!(reheats.reheats.iter().all(|&(_, beta)| beta == 0.5));
+}
@@ -0,0 +1,111 @@
+use std::num::NonZero;
+
+use ordered_float::NotNan;
+use rand::{SeedableRng, rngs::StdRng};
+
+use super::{QuadraticModel, test_reproducibility::optimizers};
+use crate::{
+    Duration, Instant,
+    optim::{HillClimbingOptimizer, LocalSearchOptimizer, ParallelTemperingOptimizer},
+    stop_condition::{Deadline, EvaluationBudget, StopCondition, StopState, TargetScore},
+};
+
+#[test]
+fn test_checked_every_iteration() {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    for opt in optimizers() {
+        let mut n_checks = 0;
+        let mut stop = |_: &StopState<_>| {
+            n_checks += 1;
+            n_checks == 3
+        };
+        let mut rng = StdRng::seed_from_u64(0);
+        let _ = opt
+            .run_with_stop_condition(
+                &model,
+                None,
+                usize::MAX,
+                Duration::from_secs(60),
+                &mut stop,
+                &mut rng,
+                &mut |_| {},
+            )
+            .unwrap();
+        assert_eq!(n_checks, 3);
+    }
+}
+
+#[test]
+fn test_target_score() {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    for opt in optimizers() {
+        let target = NotNan::new(f64::MAX).unwrap();
+        let mut n_callbacks = 0;
+        let (_, score) = opt
+            .run_with_stop_condition(
+                &model,
+                None,
+                usize::MAX,
+                Duration::from_secs(60),
+                &mut TargetScore::new(target),
+                &mut StdRng::seed_from_u64(0),
+                &mut |_| n_callbacks += 1,
+            )
+            .unwrap()
+            .into_solution_and_score();
+        assert!(score <= target);
+        // stops at the end of the first iteration, before the callback
+        assert_eq!(n_callbacks, 0);
+    }
+}
+
+#[test]
+fn test_evaluation_budget() {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let mut last_evaluations = 0;
+    let mut budget = EvaluationBudget::new(1000);
+    let mut stop = |state: &StopState<_>| {
+        last_evaluations = state.n_evaluations;
+        budget.should_stop(state)
+    };
+    let opt = HillClimbingOptimizer::new(usize::MAX, 10);
+    let _ = opt
+        .run_with_stop_condition(
+            &model,
+            None,
+            usize::MAX,
+            Duration::from_secs(60),
+            &mut stop,
+            &mut StdRng::seed_from_u64(0),
+            &mut |_| {},
+        )
+        .unwrap();
+    assert_eq!(last_evaluations, 1000);
+}
+
+#[test]
+fn test_deadline_bounds_replicas() {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let opt = ParallelTemperingOptimizer::with_geometric_betas(
+        usize::MAX,
+        10,
+        usize::MAX,
+        4,
+        1e-2,
+        1e2,
+        NonZero::new(usize::MAX).unwrap(),
+    );
+    let start = Instant::now();
+    let _ = opt
+        .run_with_stop_condition(
+            &model,
+            None,
+            usize::MAX,
+            Duration::from_secs(60),
+            &mut Deadline::after(Duration::from_millis(100)),
+            &mut StdRng::seed_from_u64(0),
+            &mut |_| {},
+        )
+        .unwrap();
+    assert!(start.elapsed() < Duration::from_secs(10));
+}
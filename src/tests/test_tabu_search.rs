@@ -9,7 +9,7 @@ use crate::{
 };
 
 #[derive(Debug)]
-struct MyTabuList {
+pub(super) struct MyTabuList {
     buff: RingBuffer<TransitionType>,
 }
 
@@ -42,6 +42,10 @@ impl TabuList for MyTabuList {
     fn append(&mut self, transition: TransitionType) {
         self.buff.append(transition);
     }
+
+    fn items(&self) -> Vec<TransitionType> {
+        self.buff.iter().copied().collect()
+    }
 }
 
 #[test]
@@ -50,7 +54,8 @@ fn test() {
     let opt = TabuSearchOptimizer::<MyTabuList>::new(1000, 25, 5, 10);
     let (final_solution, final_score) = opt
         .run(&model, None, 10000, Duration::from_secs(10))
-        .unwrap();
+        .unwrap()
+        .into_solution_and_score();
     assert_abs_diff_eq!(2.0, final_solution[0], epsilon = 0.1);
     assert_abs_diff_eq!(0.0, final_solution[1], epsilon = 0.1);
     assert_abs_diff_eq!(-3.5, final_solution[2], epsilon = 0.1);
@@ -0,0 +1,116 @@
+use std::num::NonZero;
+
+use approx::assert_abs_diff_eq;
+use rand::{SeedableRng, rngs::StdRng};
+
+use super::QuadraticModel;
+use crate::{
+    Duration, LocalsearchError,
+    optim::{
+        EnergySample, LocalSearchOptimizer, ParallelTemperingOptimizer,
+        SimulatedAnnealingOptimizer, TemperatureEstimator,
+    },
+};
+
+#[test]
+fn test_estimators() {
+    // downhill and flat transitions are dropped
+    let sample = EnergySample::new(vec![
+        (0.0, 1.0),
+        (0.0, 2.0),
+        (0.0, 3.0),
+        (0.0, 4.0),
+        (1.0, 0.5),
+        (1.0, 1.0),
+    ]);
+    assert_eq!(sample.len(), 4);
+    let ln2 = 2f64.ln();
+
+    let estimator = TemperatureEstimator::SmallestDelta {
+        target_acceptance: 0.5,
+    };
+    assert_abs_diff_eq!(estimator.estimate(&sample).unwrap().unwrap(), ln2);
+
+    let estimator = TemperatureEstimator::Percentile {
+        target_acceptance: 0.5,
+        percentile: 1.0,
+    };
+    assert_abs_diff_eq!(estimator.estimate(&sample).unwrap().unwrap(), ln2 / 4.0);
+    let estimator = TemperatureEstimator::Percentile {
+        target_acceptance: 0.5,
+        percentile: 0.5,
+    };
+    assert_abs_diff_eq!(estimator.estimate(&sample).unwrap().unwrap(), ln2 / 3.0);
+
+    // from a common origin, the acceptance ratio is the mean acceptance probability
+    let estimator = TemperatureEstimator::BenAmeur {
+        target_acceptance: 0.5,
+    };
+    let beta = estimator.estimate(&sample).unwrap().unwrap();
+    let acceptance = sample.deltas().map(|d| (-beta * d).exp()).sum::<f64>() / 4.0;
+    assert_abs_diff_eq!(acceptance, 0.5, epsilon = 1e-3);
+
+    assert_eq!(estimator.estimate(&EnergySample::default()).unwrap(), None);
+
+    // the target acceptance must be a probability strictly between 0 and 1
+    for target_acceptance in [0.0, 1.0, 1.5, f64::NAN] {
+        let estimator = TemperatureEstimator::SmallestDelta { target_acceptance };
+        assert!(matches!(
+            estimator.estimate(&sample),
+            Err(LocalsearchError::InvalidParameter {
+                name: "target_acceptance",
+                ..
+            })
+        ));
+    }
+}
+
+#[test]
+fn test_estimated_temperatures() {
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let mut rng = StdRng::seed_from_u64(0);
+    let sample = EnergySample::collect(&model, 8, 50, &mut rng).unwrap();
+    assert!(!sample.is_empty());
+    assert!(sample.len() <= 8 * 50);
+    assert!(sample.deltas().all(|d| d > 0.0));
+
+    let hottest = TemperatureEstimator::BenAmeur {
+        target_acceptance: 0.8,
+    };
+    let coldest = TemperatureEstimator::SmallestDelta {
+        target_acceptance: 0.01,
+    };
+    let initial_beta = hottest.estimate(&sample).unwrap().unwrap();
+    let final_beta = coldest.estimate(&sample).unwrap().unwrap();
+    assert!(0.0 < initial_beta && initial_beta < final_beta);
+
+    let update_frequency = NonZero::new(1).unwrap();
+    let opt = SimulatedAnnealingOptimizer::new(1000, 10, 100, 1.0, 1.0, update_frequency)
+        .estimate_initial_temperature(&sample, hottest)
+        .unwrap()
+        .estimate_cooling_rate(&sample, coldest, 100)
+        .unwrap();
+    let result = opt.run(&model, None, 101, Duration::from_secs(10)).unwrap();
+    assert_abs_diff_eq!(
+        result.algorithm_state.beta.unwrap(),
+        final_beta,
+        epsilon = 1e-6 * final_beta
+    );
+
+    let opt = ParallelTemperingOptimizer::with_geometric_betas(
+        1000,
+        10,
+        20,
+        4,
+        1.0,
+        2.0,
+        update_frequency,
+    )
+    .estimate_betas(&sample, hottest, coldest)
+    .unwrap();
+    let result = opt.run(&model, None, 10, Duration::from_secs(10)).unwrap();
+    let betas = result.algorithm_state.betas.unwrap();
+    assert_eq!(betas.len(), 4);
+    assert_abs_diff_eq!(betas[0], initial_beta);
+    assert_abs_diff_eq!(betas[3], final_beta, epsilon = 1e-9 * final_beta);
+}
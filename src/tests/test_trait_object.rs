@@ -1,9 +1,19 @@
-use std::time::Duration;
+use std::{collections::HashMap, time::Duration};
 
 use approx::assert_abs_diff_eq;
+use rand::{SeedableRng, rngs::StdRng};
 
-use super::QuadraticModel;
-use crate::optim::{EpsilonGreedyOptimizer, HillClimbingOptimizer, LocalSearchOptimizer};
+use super::{
+    QuadraticModel, SchafferModel, SchafferScore, ScoreType, SolutionType, TransitionType,
+    test_tabu_search::MyTabuList,
+};
+use crate::{
+    BoxedOptModel, DynOptModel,
+    optim::{
+        AmosaOptimizer, EpsilonGreedyOptimizer, HillClimbingOptimizer, LocalSearchOptimizer,
+        OptimizerConfig, SimulatedAnnealingOptimizer,
+    },
+};
 
 #[test]
 fn test() {
@@ -16,10 +26,72 @@ fn test() {
     for opt in opts.iter() {
         let (final_solution, final_score) = opt
             .run(&model, None, 10000, Duration::from_secs(10))
-            .unwrap();
+            .unwrap()
+            .into_solution_and_score();
         assert_abs_diff_eq!(2.0, final_solution[0], epsilon = 0.05);
         assert_abs_diff_eq!(0.0, final_solution[1], epsilon = 0.05);
         assert_abs_diff_eq!(-3.5, final_solution[2], epsilon = 0.05);
         assert_abs_diff_eq!(0.0, final_score.into_inner(), epsilon = 0.05);
     }
 }
+
+#[test]
+fn test_boxed_models() {
+    let mut registry: HashMap<&str, BoxedOptModel<SolutionType, TransitionType, ScoreType>> =
+        HashMap::new();
+    registry.insert(
+        "quadratic",
+        Box::new(QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0))),
+    );
+    registry.insert(
+        "quadratic_2d",
+        Box::new(QuadraticModel::new(2, vec![-1.0, 4.0], (-10.0, 10.0))),
+    );
+    for name in OptimizerConfig::ALGORITHMS {
+        let opt = OptimizerConfig::from_name(name)
+            .unwrap()
+            .build_with_tabu_list::<BoxedOptModel<_, _, _>, MyTabuList>()
+            .unwrap();
+        for model in registry.values() {
+            let result = opt.run(model, None, 200, Duration::from_secs(10)).unwrap();
+            assert!(result.n_iterations > 0, "{name}");
+        }
+    }
+
+    // a boxed model draws the same random numbers as the model itself
+    let model = QuadraticModel::new(3, vec![2.0, 0.0, -3.5], (-10.0, 10.0));
+    let opt = SimulatedAnnealingOptimizer::builder().build().unwrap();
+    let run = |model: &dyn DynOptModel<
+        SolutionType = SolutionType,
+        TransitionType = TransitionType,
+        ScoreType = ScoreType,
+    >| {
+        opt.run_with_rng(
+            &model,
+            None,
+            500,
+            Duration::from_secs(10),
+            &mut StdRng::seed_from_u64(0),
+            &mut |_| {},
+        )
+        .unwrap()
+    };
+    let direct = opt
+        .run_with_rng(
+            &model,
+            None,
+            500,
+            Duration::from_secs(10),
+            &mut StdRng::seed_from_u64(0),
+            &mut |_| {},
+        )
+        .unwrap();
+    assert_eq!(run(&model).solution, direct.solution);
+    assert_eq!(run(&Box::new(model)).solution, direct.solution);
+
+    // multi-objective optimizers accept boxed models too
+    let model: BoxedOptModel<f64, (), SchafferScore> = Box::new(SchafferModel);
+    let opt = AmosaOptimizer::builder().build().unwrap();
+    let result = opt.run(&model, 500, Duration::from_secs(10)).unwrap();
+    assert!(!result.front.is_empty());
+}
@@ -1,6 +1,15 @@
 //! Utilities
 
-use std::collections::VecDeque;
+use std::{
+    any::Any,
+    collections::VecDeque,
+    panic::{AssertUnwindSafe, catch_unwind},
+    sync::{Mutex, MutexGuard, PoisonError},
+};
+
+use rand::{SeedableRng as _, rngs::SmallRng};
+
+use crate::LocalsearchError;
 
 /// RingBuffer to be used to implement a Tabu List
 #[derive(Debug, Clone)]
@@ -29,3 +38,38 @@ impl<T> RingBuffer<T> {
         self.buff.iter()
     }
 }
+
+/// Split `rng` into `n` independent generators.
+///
+/// The child generators are drawn sequentially from `rng` before any parallel work starts,
+/// so the random streams only depend on the state of `rng` and not on rayon scheduling or thread count.
+pub fn split_rng<R: rand::Rng + ?Sized>(rng: &mut R, n: usize) -> Vec<SmallRng> {
+    (0..n).map(|_| SmallRng::from_rng(rng)).collect()
+}
+
+/// Lock `mutex` even if a holder panicked, as the guarded values of this crate stay consistent
+pub(crate) fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Run model code, turning a panic into [`LocalsearchError::ModelPanic`]
+/// so that it is reported as an error instead of unwinding through rayon workers.
+pub(crate) fn catch_panic<T>(
+    f: impl FnOnce() -> Result<T, LocalsearchError>,
+) -> Result<T, LocalsearchError> {
+    catch_unwind(AssertUnwindSafe(f)).unwrap_or_else(|payload| {
+        Err(LocalsearchError::ModelPanic(panic_message(
+            payload.as_ref(),
+        )))
+    })
+}
+
+fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}